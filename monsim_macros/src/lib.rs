@@ -61,6 +61,7 @@ fn construct_accessor(input: TokenStream, accessor_name: TokenStream2) -> TokenS
 /// implementations of `Event` for each of the event structs.  
 /// ```
 ///     event event_name_1(<ContextType>) => <EventReturnType>,
+///     event event_name_2(<ContextType>, <ValueType>) => <EventReturnType>,
 ///     ...
 ///     event event_name_n(<ContextType>) => <EventReturnType>,
 /// 
@@ -79,7 +80,12 @@ pub fn generate_events(input: TokenStream) -> TokenStream {
     let mut event_handler_deck_defaults_tokens = quote![];
 
     for event_expr in event_exprs {
-        let EventExpr { event_name_pascal_case, event_context_type_name_pascal_case, event_return_type_name } = event_expr;
+        let EventExpr { event_name_pascal_case, event_context_type_name_pascal_case, maybe_event_value_type_name, event_return_type_name } = event_expr;
+        // Modifier events pass the value being modified to their handlers alongside the context.
+        let event_context_type_tokens = match maybe_event_value_type_name {
+            Some(event_value_type_name) => quote!((#event_context_type_name_pascal_case, #event_value_type_name)),
+            None => quote!(#event_context_type_name_pascal_case),
+        };
         let event_name_snake_case = event_name_pascal_case.to_string().to_case(convert_case::Case::Snake);
         let event_name_snake_case = Ident::new(&event_name_snake_case, event_name_pascal_case.span());
         let event_trait_literal = proc_macro2::Literal::string(&event_name_pascal_case.to_string());
@@ -95,13 +101,13 @@ pub fn generate_events(input: TokenStream) -> TokenStream {
 
             impl Event for #event_name_pascal_case {
                 type EventReturnType = #event_return_type_name;
-                type ContextType = #event_context_type_name_pascal_case;
+                type ContextType = #event_context_type_tokens;
                 
-                fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                    event_handler_deck.#event_name_snake_case
+                fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                    &event_handler_deck.#event_name_snake_case
                 }
 
-                fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+                fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                     &mut event_handler_deck.#event_name_snake_case
                 }
     
@@ -117,11 +123,11 @@ pub fn generate_events(input: TokenStream) -> TokenStream {
         ]);
 
         event_handler_deck_field_tokens.extend(quote![
-            pub #event_name_snake_case: Option<EventHandler<#event_name_pascal_case>>,
+            pub #event_name_snake_case: EventHandlers<#event_name_pascal_case>,
         ]);
 
         event_handler_deck_defaults_tokens.extend(quote![
-            #event_name_snake_case: None,
+            #event_name_snake_case: MaxSizedVec::empty(),
         ]);
     }
 
@@ -151,7 +157,8 @@ pub fn generate_events(input: TokenStream) -> TokenStream {
 /// {
 ///     *MonsterName*: "*OptionalMonsterNickname*" {
 ///         moveset: (*MoveName* { *optional_specifiers* }, ..0-3 more ),
-///         ability: *AbilityName*
+///         ability: *AbilityName*,
+///         item: *OptionalItemName*
 ///     },
 ///     ..0-5 more
 /// },
@@ -159,7 +166,8 @@ pub fn generate_events(input: TokenStream) -> TokenStream {
 /// {
 ///     *MonsterName*: "*OptionalMonsterNickname*" {
 ///         moveset: (*MoveName* { *optional_specifiers* }, ..0-3 more ),
-///         ability: *AbilityName*
+///         ability: *AbilityName*,
+///         item: *OptionalItemName*
 ///     },
 ///     ..0-5 more
 /// }
@@ -174,7 +182,7 @@ pub fn battle(input: TokenStream) -> TokenStream {
     let get_team_tokens = |team_expr: MonsterTeamExpr, method_ident: TokenStream2| {
         let team_monster_tokens = team_expr.monster_exprs()
             .fold( quote!(), |mut tokens_so_far, monster_expr|  {
-                    let MonsterExpr { monster_ident, maybe_nickname_literal, moveset_expr, ability_expr } = monster_expr;
+                    let MonsterExpr { monster_ident, maybe_nickname_literal, moveset_expr, ability_expr, maybe_item_expr } = monster_expr;
 
                     let nickname_tokens = maybe_nickname_literal.map_or(quote!(), |nickname| { quote!(.with_nickname(#nickname)) });
 
@@ -213,6 +221,8 @@ pub fn battle(input: TokenStream) -> TokenStream {
                             
                     let ability_tokens = quote!(#ability_expr.spawn());
 
+                    let item_tokens = maybe_item_expr.map_or(quote!(), |item_expr| { quote!(.with_item(#item_expr.spawn())) });
+
                     let monster_tokens = quote!(
                         .add_monster(#monster_ident.spawn(
                                 (
@@ -222,6 +232,7 @@ pub fn battle(input: TokenStream) -> TokenStream {
                                 #ability_tokens
                             )
                             #nickname_tokens
+                            #item_tokens
                         )
                     );
                     tokens_so_far.extend(monster_tokens);
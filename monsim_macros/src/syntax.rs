@@ -59,6 +59,8 @@ pub mod event_system_macro_syntax {
     pub struct EventExpr {
         pub event_name_pascal_case: Ident,
         pub event_context_type_name_pascal_case: Ident,
+        /// The type of the value modifier events pass through their handlers, _e.g._ `u16` in `OnModifyBasePower(MoveHitContext, u16)`.
+        pub maybe_event_value_type_name: Option<Ident>,
         pub event_return_type_name: Ident,
     }
 
@@ -75,11 +77,18 @@ pub mod event_system_macro_syntax {
             let event_name: Ident = input.parse()?;
             let content; let _ = parenthesized!(content in input);
             let event_context_type_name: Ident = content.parse()?;
+            let maybe_event_value_type_name = if content.peek(Comma) {
+                let _: Comma = content.parse()?;
+                Some(content.parse()?)
+            } else {
+                None
+            };
             let _ : Token![=>] = input.parse()?;
             let event_return_type_name: Ident = input.parse()?;
             Ok(EventExpr {
                 event_name_pascal_case: event_name,
                 event_context_type_name_pascal_case: event_context_type_name,
+                maybe_event_value_type_name,
                 event_return_type_name,
             })
         }
@@ -96,6 +105,7 @@ pub mod battle_macro_syntax {
 
         custom_keyword!(moveset);
         custom_keyword!(ability);
+        custom_keyword!(item);
         custom_keyword!(team);
         custom_keyword!(power_points);
     }
@@ -184,7 +194,8 @@ pub mod battle_macro_syntax {
     /// ```no_compile
     /// *MonsterName*: "*OptionalNicknameStrLiteral*" {
     ///     moveset: ExprMoveSet,
-    ///     ability: ExprAbility
+    ///     ability: ExprAbility,
+    ///     item: ExprItem (optional)
     /// }
     /// ```
     #[derive(Clone)]
@@ -193,6 +204,7 @@ pub mod battle_macro_syntax {
         pub maybe_nickname_literal: Option<LitStr>,
         pub moveset_expr: MoveSetExpr,
         pub ability_expr: AbilityExpr,
+        pub maybe_item_expr: Option<ItemExpr>,
     }
 
     impl Parse for MonsterExpr {
@@ -210,12 +222,21 @@ pub mod battle_macro_syntax {
             let ability_expr: AbilityExpr = braced_content.parse()?;
             // Last comma is optional
             let _ = braced_content.parse::<Token![,]>();
+            // Item field is optional
+            let maybe_item_expr: Option<ItemExpr> = if braced_content.peek(keywords::item) {
+                let item_expr = braced_content.parse()?;
+                let _ = braced_content.parse::<Token![,]>();
+                Some(item_expr)
+            } else {
+                None
+            };
             
             Ok(MonsterExpr {
                 monster_ident,
                 maybe_nickname_literal,
                 moveset_expr,
                 ability_expr,
+                maybe_item_expr,
             })
         }
     }
@@ -309,4 +330,29 @@ pub mod battle_macro_syntax {
         }
     }
 
-}
\ No newline at end of file
+    /// syntax: `item: *ItemName*`
+    #[derive(Clone)]
+    pub struct ItemExpr {
+        pub item_ident: Ident,
+    }
+
+    impl Parse for ItemExpr {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let _: keywords::item = input.parse()?;
+            let _ : Token![:] = input.parse()?;
+            let item_ident: Ident = input.parse()?;
+
+            Ok(ItemExpr {
+                item_ident,
+            })
+        }
+    }
+
+    impl ToTokens for ItemExpr {
+        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+            let item_ident = &self.item_ident;
+            tokens.extend(quote!(#item_ident));
+        }
+    }
+
+}
@@ -190,6 +190,7 @@ macro_rules! not {
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 /// A type that can be deferenced to get data marked as belonging to the Ally Team
 pub struct Ally<T>(T);
 
@@ -258,6 +259,7 @@ impl<T: Clone> Ally<T> {
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 /// A type that can be deferenced to get data marked as belonging to the Opponent Team
 pub struct Opponent<T>(T);
 
@@ -48,11 +48,9 @@ impl<T: Clone, const CAP: usize> MaxSizedVec<T, CAP> {
 }
 
 impl<T, const CAP: usize> MaxSizedVec<T, CAP> {
-    pub fn empty() -> Self {
+    pub const fn empty() -> Self {
         MaxSizedVec {
-            elements: std::array::from_fn::<_, CAP, _>(|_| {
-                None
-            }),
+            elements: [const { None }; CAP],
             count: 0,
         }
     }
@@ -113,7 +111,7 @@ impl<T, const CAP: usize> MaxSizedVec<T, CAP> {
 
     /// Fails if the array is full.
     pub fn try_push(&mut self, item: T) -> Result<(), &'static str> {
-        *self.elements.get_mut(self.count - 1).ok_or("Push failed due to array being full.")? = Some(item);
+        *self.elements.get_mut(self.count).ok_or("Push failed due to array being full.")? = Some(item);
         self.count += 1;
         Ok(NOTHING)
     }
@@ -227,4 +225,23 @@ impl<T, const CAP: usize> IntoIterator for MaxSizedVec<T, CAP>{
             .take(self.count)
             .flatten()
     }
-}
\ No newline at end of file
+}
+
+/// Serialized as a sequence of the valid elements, the padding is rebuilt on deserialization.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const CAP: usize> serde::Serialize for MaxSizedVec<T, CAP> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const CAP: usize> serde::Deserialize<'de> for MaxSizedVec<T, CAP> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let elements = Vec::<T>::deserialize(deserializer)?;
+        if elements.len() > CAP {
+            return Err(serde::de::Error::invalid_length(elements.len(), &"at most CAP elements"));
+        }
+        Ok(Self::from_vec(elements))
+    }
+}
@@ -28,9 +28,9 @@ pub const FlashFire: AbilitySpecies = AbilitySpecies::from_dex_data(
             
             #[cfg(not(feature="debug"))]
             let out = EventHandlerDeck::empty()
-                .add(OnTryMove, |sim, MoveUseContext { move_user_id, move_used_id, target_id: target}| {
+                .add(OnTryMoveHit, |sim, effector_id, MoveHitContext { move_user_id, move_used_id, target_id}| {
                     if mov![move_used_id].is_type(Type::Fire) {
-                        let activation_succeeded = ActivateAbility(sim, AbilityUseContext::new(target));
+                        let activation_succeeded = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
                         return not!(activation_succeeded);
                     }
                     Outcome::Success
@@ -38,8 +38,7 @@ pub const FlashFire: AbilitySpecies = AbilitySpecies::from_dex_data(
             out
         },
         on_activate_effect: Effect::from(|sim, effector_id, AbilityUseContext { ability_used_id, ability_owner_id }| {
-            let effector_name = mon![effector_id].name();
-            sim.push_message(format!["{effector_name}'s Flash Fire activated!"]);
+            sim.log_species_message(&FlashFire, "activated", "{monster}'s Flash Fire activated!", effector_id);
         }),
         event_filtering_options: EventFilteringOptions::default(),
         order: 0,
@@ -1,143 +1,92 @@
 use std::io::{self, StdoutLock, Write};
 
-use monsim_macros::{mon, mov};
 use monsim_utils::{MaxSizedVec, Nothing, NOTHING};
 
-use crate::{sim::{AvailableChoices, BattleSimulator, BattleState, FullySpecifiedActionChoice, PartiallySpecifiedActionChoice}, BattleFormat, FieldPosition, MonsimResult, MonsterID, TargetFlags};
-
-enum TurnStage {
-    ChooseActions,
-    SimulateTurn(Vec<FullySpecifiedActionChoice>),
-    BattleEnded,
-}
+use crate::{sim::{AvailableChoices, BattleSimulator, BattleState, Decision, DecisionRequest, FullySpecifiedActionChoice, PartiallySpecifiedActionChoice}, MonsimResult, MonsterID, TargetFlags};
 
 pub fn run(battle: BattleState) -> MonsimResult<Nothing> {
 
     let mut sim = BattleSimulator::init(battle);
 
-    let mut turn_stage = TurnStage::ChooseActions;
-
     // We lock stdout so that we don't have to acquire the lock every time with `println!`
     let mut locked_stdout = io::stdout().lock();
+
+    let mut turn_of_last_status_shown = None;
     
     'main: loop {
-        let mut monsters_already_chosen_for_switching = Vec::new();
-        match turn_stage {
-            TurnStage::ChooseActions => {
-                
-                // We are using one buffer since they will be mixed up when priority sorting anyway.
-                let mut chosen_actions_for_turn = Vec::new();
-                
-                write_empty_line(&mut locked_stdout)?;
-                writeln!(locked_stdout, "Current Battle Status:")?;
-                write_empty_line(&mut locked_stdout)?;
-                
-                for active_monsters_per_team in sim.battle.active_monsters_by_team() {
-                    for active_monster in active_monsters_per_team {
-                        writeln!(locked_stdout, "{} Active Monster: {}", active_monster.id.team_id, active_monster.status_string())?;
-                    }
-                }
-                
-                writeln!(locked_stdout, "Ally Team:")?;
-                writeln!(locked_stdout, "{}", sim.battle.ally_team().team_status_string())?;
-                writeln!(locked_stdout, "Opponent Team:")?;
-                writeln!(locked_stdout, "{}", sim.battle.opponent_team().team_status_string())?;
-                
-                for active_monsters_per_team in sim.battle.active_monsters_by_team() {
-                    for active_monster in active_monsters_per_team {
-                        writeln!(locked_stdout, "Choose an Action for {}", active_monster.full_name())?;
-                        write_empty_line(&mut locked_stdout)?;
-                        let available_action_choices_for_monster = sim.battle.available_choices_for(active_monster, &monsters_already_chosen_for_switching);
-                        display_choices(&available_action_choices_for_monster, &mut locked_stdout)?;
-                        
-                        match receive_user_input_and_convert_to_choice(&sim.battle, available_action_choices_for_monster, &mut locked_stdout, &mut monsters_already_chosen_for_switching)? {
-                            UIChoice::QuitAction => {
-                                writeln!(locked_stdout, "Exiting...")?;
-                                break 'main;
-                            },
-                            UIChoice::BattleAction(fully_specified_action_choice) => {
-                                chosen_actions_for_turn.push(fully_specified_action_choice);
-                            },
-                        };    
-                    }
+        // The simulator asks for one decision after another and moves the battle on by itself once it has all it needs.
+        let Some(pending_request) = sim.pending_requests().first().copied() else {
+            println!("The simulator terminated successfully.");
+            println!("Exitting...");
+            break 'main;
+        };
+
+        let decision = match pending_request {
+            DecisionRequest::TurnAction { monster_id, available_choices } => {
+                if turn_of_last_status_shown != Some(sim.battle.turn_number) {
+                    write_battle_status(&sim.battle, &mut locked_stdout)?;
+                    turn_of_last_status_shown = Some(sim.battle.turn_number);
                 }
-               
-                turn_stage = TurnStage::SimulateTurn(chosen_actions_for_turn.clone());
-            },
-            TurnStage::SimulateTurn(chosen_actions_for_turn) => {
 
-                sim.battle.message_log.snap_last_turn_cursor_to_end();
-                
-                sim.simulate_turn(chosen_actions_for_turn)?;
-                
-                // Show the message log
-                sim.battle.message_log.show_last_turn_messages();
+                writeln!(locked_stdout, "Choose an Action for {}", sim.battle.monster(monster_id).full_name())?;
+                write_empty_line(&mut locked_stdout)?;
+                display_choices(&available_choices, &mut locked_stdout)?;
                 
-                if sim.battle.is_finished() {
-                    turn_stage = TurnStage::BattleEnded;
-                } else {
-                    turn_stage = TurnStage::ChooseActions;
+                match receive_user_input_and_convert_to_choice(&sim.battle, available_choices, &mut locked_stdout)? {
+                    UIChoice::QuitAction => {
+                        writeln!(locked_stdout, "Exiting...")?;
+                        break 'main;
+                    },
+                    UIChoice::BattleAction(fully_specified_action_choice) => Decision::TurnAction(fully_specified_action_choice),
                 }
-
-                // Check if any board positions are empty and replace them with Monsters if there are any possible.
-                let empty_field_positions = valid_positions_in(sim.battle.format())
-                    .into_iter()
-                    .filter(|position| { sim.battle.monster_at_position(*position).is_none() })
-                    .collect::<Vec<_>>();
-
-                monsters_already_chosen_for_switching.clear();
-
-                // Replace each monster that we can.
-                for field_position in empty_field_positions {
-                    let team_id = field_position.side();
-                    /*
-                    TEMP: The queues is empty at the moment and we don't refill it because we directly replace the monster instead of waiting
-                    for it to be naturally activated in order, so no use holding the MonsterID as the switch is already carried out and will
-                    be taken into account in the next iteration of the loop. This is temporary because the real solution is to integrate the
-                    choice selection into the engine itself, so that we can deal with these battle logic specific issues in the engine.
-                    */
-                    let switchable_benched_monster_ids = sim.battle.switchable_benched_monster_ids(team_id, &monsters_already_chosen_for_switching);
-                    if switchable_benched_monster_ids.is_empty() {
-                        sim.push_message(format!["{} is empty but {} is out of switchable Monsters!", field_position, team_id]);
-                        continue;
-                    }
-                    let switchable_benched_monster_names = switchable_benched_monster_ids.into_iter().map(|benched_monster_id| mon![benched_monster_id].full_name()).enumerate();
-                    writeln!(locked_stdout, "Choose a monster to switch in to {}", field_position)?;
-                    for (index, monster_name) in switchable_benched_monster_names {
-                        writeln!(locked_stdout, "[{}] {}", index + 1, monster_name)?;
-                    }
-                    let switchable_benched_monster_choice_index = receive_user_input_and_convert_to_choice_index(&mut locked_stdout, switchable_benched_monster_ids.count()).unwrap();
-                    let chosen_switchable_benched_monster_id = switchable_benched_monster_ids[switchable_benched_monster_choice_index];
-
-                    // We directly replace the empty position with the chosen Monster
-                    // FIXME: Effects might have no originator, how to deal with this? I just passed something that passes the type check here, but the actual argument doesn't makes sense here.
-                    crate::sim::effects::ReplaceFaintedMonster(&mut sim, chosen_switchable_benched_monster_id, (chosen_switchable_benched_monster_id, field_position));
-                    sim.battle.message_log.show_last_message();
-                } 
             },
-            TurnStage::BattleEnded => {
-                println!("The simulator terminated successfully.");
-                println!("Exitting...");
-                break 'main;
+            DecisionRequest::ForcedSwitch { field_position, switchable_benched_monster_ids } => {
+                writeln!(locked_stdout, "Choose a monster to switch in to {}", field_position)?;
+                let benched_monster_id = receive_switchee_choice(&sim.battle, switchable_benched_monster_ids, &mut locked_stdout)?;
+                Decision::ForcedSwitch { field_position, benched_monster_id }
             },
+            DecisionRequest::MidMoveSwitch { active_monster_id, switchable_benched_monster_ids } => {
+                writeln!(locked_stdout, "Choose a monster to switch in for {}", sim.battle.monster(active_monster_id).full_name())?;
+                let benched_monster_id = receive_switchee_choice(&sim.battle, switchable_benched_monster_ids, &mut locked_stdout)?;
+                Decision::MidMoveSwitch { active_monster_id, benched_monster_id }
+            },
+        };
+
+        sim.battle.message_log.snap_last_turn_cursor_to_end();
+        sim.submit(decision)?;
+        // Show whatever happened as a result of the decision, which is the whole turn once the last action is chosen.
+        sim.battle.message_log.show_last_turn_styled_messages();
+    }
+    Ok(NOTHING)
+}
+
+fn write_battle_status(battle: &BattleState, locked_stdout: &mut StdoutLock) -> MonsimResult<Nothing> {
+    write_empty_line(locked_stdout)?;
+    writeln!(locked_stdout, "Current Battle Status:")?;
+    write_empty_line(locked_stdout)?;
+    
+    for active_monsters_per_team in battle.active_monsters_by_team() {
+        for active_monster in active_monsters_per_team {
+            writeln!(locked_stdout, "{} Active Monster: {}", active_monster.id.team_id, active_monster.status_string())?;
         }
     }
+    
+    writeln!(locked_stdout, "Ally Team:")?;
+    writeln!(locked_stdout, "{}", battle.ally_team().team_status_string())?;
+    writeln!(locked_stdout, "Opponent Team:")?;
+    writeln!(locked_stdout, "{}", battle.opponent_team().team_status_string())?;
+
     Ok(NOTHING)
 }
 
-fn valid_positions_in(battle_format: BattleFormat) -> Vec<FieldPosition> {
-    match battle_format {
-        BattleFormat::Single => {
-            vec![FieldPosition::AllySideCentre, FieldPosition::OpponentSideCentre]
-        
-        },
-        BattleFormat::Double => {
-            vec![FieldPosition::AllySideCentre, FieldPosition::AllySideRight, FieldPosition::OpponentSideCentre, FieldPosition::OpponentSideRight]
-        },
-        BattleFormat::Triple => {
-            vec![FieldPosition::AllySideLeft, FieldPosition::AllySideCentre, FieldPosition::AllySideRight, FieldPosition::OpponentSideLeft, FieldPosition::OpponentSideCentre, FieldPosition::OpponentSideRight]
-        },
+fn receive_switchee_choice(battle: &BattleState, switchable_benched_monster_ids: MaxSizedVec<MonsterID, 5>, locked_stdout: &mut StdoutLock) -> MonsimResult<MonsterID> {
+    let switchable_benched_monster_names = switchable_benched_monster_ids.into_iter().map(|id| battle.monster(id).full_name()).enumerate();
+    for (index, switchee_name) in switchable_benched_monster_names {
+        writeln!(locked_stdout, "[{}] {}", index + 1, switchee_name)?;
     }
+    write_empty_line(locked_stdout)?;
+    let chosen_switchable_benched_monster_choice_index = receive_user_input_and_convert_to_choice_index(locked_stdout, switchable_benched_monster_ids.count())?;
+    Ok(switchable_benched_monster_ids[chosen_switchable_benched_monster_choice_index])
 }
 
 fn display_choices(available_actions_for_team: &AvailableChoices, locked_stdout: &mut StdoutLock) -> MonsimResult<Nothing> {
@@ -167,7 +116,6 @@ fn receive_user_input_and_convert_to_choice(
     battle: &BattleState, 
     available_choices_for_monster: AvailableChoices, 
     locked_stdout: &mut StdoutLock, 
-    monsters_already_chosen_for_switching: &mut Vec<MonsterID>,
 ) -> MonsimResult<UIChoice> {
 
     let available_actions_count = available_choices_for_monster.count();
@@ -211,17 +159,8 @@ fn receive_user_input_and_convert_to_choice(
         },
         PartiallySpecifiedActionChoice::SwitchOut { active_monster_id, switchable_benched_monster_ids, activation_order, .. } => {
             // Switchee prompt
-            let switchable_benched_monster_names = switchable_benched_monster_ids.into_iter().map(|id| battle.monster(id).full_name()).enumerate();
-            let _ = writeln!(locked_stdout, "Choose a benched monster to switch in");
-            for (index, switchee_name) in switchable_benched_monster_names {
-                writeln!(locked_stdout, "[{}] {}", index + 1, switchee_name)?;
-            }
-            write_empty_line(locked_stdout)?;
-            let chosen_switchable_benched_monster_choice_index = receive_user_input_and_convert_to_choice_index(locked_stdout, switchable_benched_monster_ids.count()).unwrap();
-            let benched_monster_id = switchable_benched_monster_ids[chosen_switchable_benched_monster_choice_index];
-            // TEMP: This is another component of what I wrote above where we have to keep track of who's already been selected for switching out
-            // because this whole process needs to occur inside the engine but the infrastructure for that doesn't exist yet.
-            monsters_already_chosen_for_switching.push(benched_monster_id);
+            writeln!(locked_stdout, "Choose a benched monster to switch in")?;
+            let benched_monster_id = receive_switchee_choice(battle, switchable_benched_monster_ids, locked_stdout)?;
             FullySpecifiedActionChoice::SwitchOut { active_monster_id, benched_monster_id, activation_order }
         },
     };
@@ -236,7 +175,7 @@ fn receive_user_input_and_convert_to_choice_index(locked_stdout: &mut StdoutLock
         let input = input.trim();
         let chosen_action_index = input.chars().next();
         if input.len() == 1 {
-            let maybe_action_choice_index = chosen_action_index.map(|char| { char.to_digit(10) }).flatten();
+            let maybe_action_choice_index = chosen_action_index.and_then(|char| { char.to_digit(10) });
             if let Some(action_choice_index) = maybe_action_choice_index {
                 if 0 < action_choice_index && action_choice_index <= total_action_choices as u32 {
                     return Ok(action_choice_index as usize - 1); // The -1 converts back to zero based counting 
@@ -248,6 +187,6 @@ fn receive_user_input_and_convert_to_choice_index(locked_stdout: &mut StdoutLock
 }
 
 fn write_empty_line(locked_stdout: &mut StdoutLock<'_>) -> MonsimResult<Nothing> {
-    writeln!(locked_stdout, "")?;
+    writeln!(locked_stdout)?;
     Ok(NOTHING)
 }
\ No newline at end of file
@@ -0,0 +1,275 @@
+#![allow(non_upper_case_globals, clippy::zero_prefixed_literal, unused)]
+
+use monsim_macros::{mon, mov};
+use monsim_utils::{not, Nothing, Outcome, Percent};
+use monsim::{effects::*, event_dex::*, sim::{
+        BattleSimulator, FieldEffectDexEntry, FieldEffectKind, FieldEffectSpecies, MajorStatus, MonsterID, MoveID, Stat, Type
+}, EventHandlerDeck, MoveHitContext, StatContext, StatusContext};
+
+#[cfg(feature = "debug")]
+use monsim::source_code_location;
+
+// Weather -----------------
+
+pub const Rain: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 001,
+        name: "Rain",
+        kind: FieldEffectKind::Weather,
+        duration: Some(5),
+        start_message: "It started to rain!",
+        end_message: "The rain stopped.",
+        end_of_turn_effect: Effect::from(|_sim, _effector_id, _monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_and_weaken_types(sim, move_used_id, damage, Type::Water, Type::Fire)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_and_weaken_types(sim, move_used_id, damage, Type::Water, Type::Fire)
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+pub const HarshSunlight: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 002,
+        name: "Harsh Sunlight",
+        kind: FieldEffectKind::Weather,
+        duration: Some(5),
+        start_message: "The sunlight turned harsh!",
+        end_message: "The harsh sunlight faded.",
+        end_of_turn_effect: Effect::from(|_sim, _effector_id, _monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_and_weaken_types(sim, move_used_id, damage, Type::Fire, Type::Water)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_and_weaken_types(sim, move_used_id, damage, Type::Fire, Type::Water)
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+pub const Sandstorm: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 003,
+        name: "Sandstorm",
+        kind: FieldEffectKind::Weather,
+        duration: Some(5),
+        start_message: "A sandstorm kicked up!",
+        end_message: "The sandstorm subsided.",
+        end_of_turn_effect: Effect::from(|sim, _effector_id, monster_id| {
+            let is_immune = [Type::Rock, Type::Ground, Type::Steel].into_iter().any(|type_| mon![monster_id].is_type(type_));
+            if not!(is_immune) {
+                sim.log_species_message(&Sandstorm, "buffeted", "{monster} is buffeted by the sandstorm!", monster_id);
+                deal_weather_damage(sim, monster_id);
+            }
+        }),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyStat, |sim, _effector_id, (StatContext { monster_id, stat }, value)| {
+                    sandstorm_special_defense_boost(sim, monster_id, stat, value)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyStat, |sim, _effector_id, (StatContext { monster_id, stat }, value)| {
+                    sandstorm_special_defense_boost(sim, monster_id, stat, value)
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+pub const Hail: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 004,
+        name: "Hail",
+        kind: FieldEffectKind::Weather,
+        duration: Some(5),
+        start_message: "It started to hail!",
+        end_message: "The hail stopped.",
+        end_of_turn_effect: Effect::from(|sim, _effector_id, monster_id| {
+            let is_immune = mon![monster_id].is_type(Type::Ice);
+            if not!(is_immune) {
+                sim.log_species_message(&Hail, "buffeted", "{monster} is buffeted by the hail!", monster_id);
+                deal_weather_damage(sim, monster_id);
+            }
+        }),
+        event_handlers: EventHandlerDeck::empty,
+        order: 0,
+    }
+);
+
+// Terrain -----------------
+
+pub const ElectricTerrain: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 005,
+        name: "Electric Terrain",
+        kind: FieldEffectKind::Terrain,
+        duration: Some(5),
+        start_message: "An electric current ran across the battlefield!",
+        end_message: "The electricity disappeared from the battlefield.",
+        end_of_turn_effect: Effect::from(|_sim, _effector_id, _monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_type(sim, move_used_id, damage, Type::Electric)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_type(sim, move_used_id, damage, Type::Electric)
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+pub const GrassyTerrain: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 006,
+        name: "Grassy Terrain",
+        kind: FieldEffectKind::Terrain,
+        duration: Some(5),
+        start_message: "Grass grew to cover the battlefield!",
+        end_message: "The grass disappeared from the battlefield.",
+        end_of_turn_effect: Effect::from(|sim, _effector_id, monster_id| {
+            let amount = (mon![monster_id].max_health() / 16).max(1);
+            if RestoreHealth(sim, monster_id, (monster_id, amount)) > 0 {
+                sim.log_species_message(&GrassyTerrain, "healed", "{monster} was healed by the Grassy Terrain!", monster_id);
+            }
+        }),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_type(sim, move_used_id, damage, Type::Grass)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_type(sim, move_used_id, damage, Type::Grass)
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+pub const MistyTerrain: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 007,
+        name: "Misty Terrain",
+        kind: FieldEffectKind::Terrain,
+        duration: Some(5),
+        start_message: "Mist swirled around the battlefield!",
+        end_message: "The mist disappeared from the battlefield.",
+        end_of_turn_effect: Effect::from(|_sim, _effector_id, _monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    weaken_type(sim, move_used_id, damage, Type::Dragon)
+                }, source_code_location!())
+                .add(OnTryInflictStatus, |sim, _effector_id, StatusContext { monster_id, .. }| {
+                    sim.log_species_message(&MistyTerrain, "protected", "{monster} surrounds itself with a protective mist!", monster_id);
+                    Outcome::Failure
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    weaken_type(sim, move_used_id, damage, Type::Dragon)
+                })
+                .add(OnTryInflictStatus, |sim, _effector_id, StatusContext { monster_id, .. }| {
+                    sim.log_species_message(&MistyTerrain, "protected", "{monster} surrounds itself with a protective mist!", monster_id);
+                    Outcome::Failure
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+pub const PsychicTerrain: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 008,
+        name: "Psychic Terrain",
+        kind: FieldEffectKind::Terrain,
+        duration: Some(5),
+        start_message: "The battlefield got weird!",
+        end_message: "The weirdness disappeared from the battlefield.",
+        end_of_turn_effect: Effect::from(|_sim, _effector_id, _monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_type(sim, move_used_id, damage, Type::Psychic)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    boost_type(sim, move_used_id, damage, Type::Psychic)
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+// Helpers -----------------
+
+fn boost_and_weaken_types(sim: &mut BattleSimulator, move_used_id: MoveID, damage: u16, boosted_type: Type, weakened_type: Type) -> u16 {
+    if mov![move_used_id].is_type(boosted_type) {
+        scale(damage, Percent(150))
+    } else if mov![move_used_id].is_type(weakened_type) {
+        scale(damage, Percent(50))
+    } else {
+        damage
+    }
+}
+
+fn boost_type(sim: &mut BattleSimulator, move_used_id: MoveID, damage: u16, boosted_type: Type) -> u16 {
+    if mov![move_used_id].is_type(boosted_type) { scale(damage, Percent(130)) } else { damage }
+}
+
+fn weaken_type(sim: &mut BattleSimulator, move_used_id: MoveID, damage: u16, weakened_type: Type) -> u16 {
+    if mov![move_used_id].is_type(weakened_type) { scale(damage, Percent(50)) } else { damage }
+}
+
+fn sandstorm_special_defense_boost(sim: &mut BattleSimulator, monster_id: MonsterID, stat: Stat, value: u16) -> u16 {
+    if stat == Stat::SpecialDefense && mon![monster_id].is_type(Type::Rock) { scale(value, Percent(150)) } else { value }
+}
+
+fn scale(value: u16, multiplier: Percent) -> u16 {
+    (value as f64 * multiplier) as u16
+}
+
+fn deal_weather_damage(sim: &mut BattleSimulator, monster_id: MonsterID) {
+    let damage = (mon![monster_id].max_health() / 16).max(1);
+    _ = DealDirectDamage(sim, monster_id, (monster_id, damage));
+}
@@ -1,3 +1,5 @@
+// `features` rather than `feature` is deliberate: the TUI was written against the old choice API and no longer
+// compiles, so it is kept out of every build, even with the `monsim_tui` feature on, until it is rewritten.
 #[cfg(features="monsim_tui")]
 pub(crate) mod tui;
 pub(crate) mod cli;
@@ -8,6 +10,7 @@ mod test;
 
 use std::error::Error;
 pub use sim::*;
+// Switched off along with the `tui` module above.
 #[cfg(features="monsim_tui")]
 pub use tui::run as run_tui;
 pub use cli::run as run_cli;
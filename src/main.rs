@@ -1,10 +1,14 @@
 use monsim::*;
 
 mod ability_dex;
+mod field_effect_dex;
+mod item_dex;
 mod monster_dex;
 mod move_dex;
+mod side_condition_dex;
 
 use ability_dex::*;
+use item_dex::*;
 use monster_dex::*;
 use move_dex::*;
 
@@ -22,6 +26,7 @@ fn main() -> MonsimResult<()> {
                         ), 
                         FlashFire.spawn()
                     )
+                    .with_item(SitrusBerry.spawn())
                 )
                 .add_monster(
                     Zombler.spawn(
@@ -41,7 +46,7 @@ fn main() -> MonsimResult<()> {
                             Ember.spawn(),
                             Some(Growl.spawn()),
                             Some(Scratch.spawn()),
-                            None
+                            Some(UTurn.spawn()),
                         ),
                         FlashFire.spawn()
                     )
@@ -99,13 +104,14 @@ fn main() -> MonsimResult<()> {
 
     println!("{:?}", battle.format());
 
-    #[cfg(feature="macro")]
+    #[cfg(feature="battle_builder")]
     let _battle2 = battle!(
         team: Opponents
         {
             Merkey: "Blub" {
                 moveset: (Bubble, Tackle),
                 ability: FlashFire,
+                item: SitrusBerry,
             },
             Squirecoal: "Cheep" {
                 moveset: (Scratch, Tackle, Growl),
@@ -1,15 +1,16 @@
 #![allow(non_upper_case_globals, clippy::zero_prefixed_literal, unused)]
 
-use monsim::{effects::*, sim::{BattleState, EventFilteringOptions, MonsterID, MoveCategory, MoveSpecies, Stat, Type}, EventHandlerDeck, Hits, MoveDexEntry, TargetFlags};
+use monsim::{effects::*, sim::{BattleState, BuiltInEffect, EffectDescription, EffectTarget, EventFilteringOptions, MonsterID, MoveCategory, MoveSpecies, Outcome, Stat, Type}, EventHandlerDeck, Hits, MoveDexEntry, TargetFlags};
 
 pub const Tackle: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 001,
         name: "Tackle",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Physical,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -27,10 +28,11 @@ pub const Scratch: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 002,
         name: "Scratch",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Physical,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -48,10 +50,11 @@ pub const Ember: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 003,
         name: "Ember",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Special,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -68,10 +71,11 @@ pub const Bubble: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 004,
         name: "Bubble",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Special,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -88,12 +92,11 @@ pub const Growl: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 005,
         name: "Growl",
-        on_hit_effect: Effect::from(|sim, effector_id, context| { 
-            _ = LowerStat(sim, context.move_user_id, (context.target_id, Stat::PhysicalAttack, 1)); 
-        }),
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::LowerStat { target: EffectTarget::Target, stat: Stat::PhysicalAttack, stages: 1 }),
+        base_accuracy: Some(100),
         base_power: 0,
         category: MoveCategory::Status,
+        crit_ratio: 0,
         max_power_points: 40,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -110,13 +113,14 @@ pub const DragonDance: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 006,
         name: "Dragon Dance",
-        on_hit_effect: Effect::from(|sim, effector_id, context| {
-            RaiseStat(sim, context.move_user_id, (context.target_id, Stat::PhysicalAttack, 1));
-            RaiseStat(sim, context.move_user_id, (context.target_id, Stat::Speed,          1));
-        }),
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::Sequence(&[
+            EffectDescription::BuiltIn(BuiltInEffect::RaiseStat { target: EffectTarget::User, stat: Stat::PhysicalAttack, stages: 1 }),
+            EffectDescription::BuiltIn(BuiltInEffect::RaiseStat { target: EffectTarget::User, stat: Stat::Speed,          stages: 1 }),
+        ]),
+        base_accuracy: None,
         base_power: 0,
         category: MoveCategory::Status,
+        crit_ratio: 0,
         max_power_points: 20,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -131,11 +135,12 @@ pub const BulletSeed: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 007,
         name: "Bullet Seed",
-        on_hit_effect: DealDefaultDamage,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
         hits_per_target: Hits::RandomlyInRange { min: 2, max: 5 },
-        base_accuracy: 100,
+        base_accuracy: Some(100),
         base_power: 25,
         category: MoveCategory::Physical,
+        crit_ratio: 0,
         max_power_points: 20,
         priority: 0,
         targets: TargetFlags::ANY
@@ -146,4 +151,31 @@ pub const BulletSeed: MoveSpecies = MoveSpecies::from_dex_entry(
         event_handlers: EventHandlerDeck::empty,
         event_filtering_options: EventFilteringOptions::default(),
     }
-);
\ No newline at end of file
+);
+pub const UTurn: MoveSpecies = MoveSpecies::from_dex_entry(
+    MoveDexEntry {
+        dex_number: 008,
+        name: "U-turn",
+        on_hit_effect: EffectDescription::Custom(Effect::from(|sim, effector_id, context| {
+            let outcome = DealDefaultDamage(sim, effector_id, context);
+            if outcome.succeeded() {
+                _ = RequestSwitchOut(sim, context.move_user_id, context.move_user_id);
+            }
+            outcome
+        })),
+        base_accuracy: Some(100),
+        base_power: 70,
+        category: MoveCategory::Physical,
+        crit_ratio: 0,
+        max_power_points: 20,
+        hits_per_target: Hits::Once,
+        priority: 0,
+        targets: TargetFlags::ANY
+                    .union(TargetFlags::ADJACENT)
+                    .union(TargetFlags::OPPONENTS)
+                    .union(TargetFlags::ALLIES),
+        type_: Type::Bug,
+        event_handlers: EventHandlerDeck::empty,
+        event_filtering_options: EventFilteringOptions::default(),
+    }
+);
@@ -0,0 +1,250 @@
+#![allow(non_upper_case_globals, clippy::zero_prefixed_literal, unused)]
+
+use monsim_macros::{mon, mov};
+use monsim_utils::{not, Nothing, Outcome, Percent};
+use monsim::{effects::*, event_dex::*, sim::{
+        type_matchup, BattleSimulator, EventFilteringOptions, MajorStatus, MonsterID, MoveCategory, MoveID, SideConditionDexEntry, SideConditionSpecies, Stat, Type
+}, EventHandlerDeck, MoveHitContext, StatContext, SwitchInContext, TargetFlags};
+
+#[cfg(feature = "debug")]
+use monsim::source_code_location;
+
+// Screens -----------------
+
+pub const Reflect: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 001,
+        name: "Reflect",
+        duration: Some(5),
+        max_layers: 1,
+        start_message: "{team} is protected by Reflect!",
+        end_message: "{team} is no longer protected by Reflect.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (context, damage)| {
+                    weaken_damage_from_category(sim, context, damage, MoveCategory::Physical)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (context, damage)| {
+                    weaken_damage_from_category(sim, context, damage, MoveCategory::Physical)
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::OPPONENTS,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const LightScreen: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 002,
+        name: "Light Screen",
+        duration: Some(5),
+        max_layers: 1,
+        start_message: "{team} is protected by Light Screen!",
+        end_message: "{team} is no longer protected by Light Screen.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (context, damage)| {
+                    weaken_damage_from_category(sim, context, damage, MoveCategory::Special)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (context, damage)| {
+                    weaken_damage_from_category(sim, context, damage, MoveCategory::Special)
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::OPPONENTS,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const Tailwind: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 003,
+        name: "Tailwind",
+        duration: Some(4),
+        max_layers: 1,
+        start_message: "{team} is blown by a tailwind!",
+        end_message: "{team} is no longer blown by a tailwind.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyStat, |sim, _effector_id, (StatContext { monster_id, stat }, value)| {
+                    if stat == Stat::Speed { value * 2 } else { value }
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyStat, |sim, _effector_id, (StatContext { monster_id, stat }, value)| {
+                    if stat == Stat::Speed { value * 2 } else { value }
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::ALLIES,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+// Entry hazards -----------------
+
+pub const Spikes: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 004,
+        name: "Spikes",
+        duration: None,
+        max_layers: 3,
+        start_message: "{team} is surrounded by spikes!",
+        end_message: "{team} is no longer surrounded by spikes.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, _effector_id, SwitchInContext { monster_id }| {
+                    hurt_by_spikes(sim, monster_id)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, _effector_id, SwitchInContext { monster_id }| {
+                    hurt_by_spikes(sim, monster_id)
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::ALLIES,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const StealthRock: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 005,
+        name: "Stealth Rock",
+        duration: None,
+        max_layers: 1,
+        start_message: "{team} is surrounded by pointed stones!",
+        end_message: "{team} is no longer surrounded by pointed stones.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, _effector_id, SwitchInContext { monster_id }| {
+                    hurt_by_stealth_rock(sim, monster_id)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, _effector_id, SwitchInContext { monster_id }| {
+                    hurt_by_stealth_rock(sim, monster_id)
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::ALLIES,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const ToxicSpikes: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 006,
+        name: "Toxic Spikes",
+        duration: None,
+        max_layers: 2,
+        start_message: "{team} is surrounded by poison spikes!",
+        end_message: "{team} is no longer surrounded by poison spikes.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, _effector_id, SwitchInContext { monster_id }| {
+                    poisoned_by_toxic_spikes(sim, monster_id)
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, _effector_id, SwitchInContext { monster_id }| {
+                    poisoned_by_toxic_spikes(sim, monster_id)
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::ALLIES,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+// Helpers -----------------
+
+/// Screens only weaken moves that hit a Monster on the protected side, which is the side opposing the attacker.
+fn weaken_damage_from_category(sim: &mut BattleSimulator, MoveHitContext { move_user_id, move_used_id, target_id }: MoveHitContext, damage: u16, category: MoveCategory) -> u16 {
+    let is_target_protected = move_user_id.team_id != target_id.team_id;
+    if is_target_protected && mov![move_used_id].category() == category {
+        damage / 2
+    } else {
+        damage
+    }
+}
+
+fn hazard_layers(sim: &BattleSimulator, monster_id: MonsterID, species: &SideConditionSpecies) -> u8 {
+    sim.battle.team(monster_id.team_id)
+        .side_condition(species)
+        .map_or(0, |side_condition| side_condition.layers())
+}
+
+fn hurt_by_spikes(sim: &mut BattleSimulator, monster_id: MonsterID) {
+    if mon![monster_id].is_type(Type::Flying) {
+        return;
+    }
+    let max_health = mon![monster_id].max_health();
+    let damage = match hazard_layers(sim, monster_id, &Spikes) {
+        1 => max_health / 8,
+        2 => max_health / 6,
+        _ => max_health / 4,
+    };
+    sim.log_species_message(&Spikes, "hurt", "{monster} is hurt by the spikes!", monster_id);
+    _ = DealDirectDamage(sim, monster_id, (monster_id, damage.max(1)));
+}
+
+fn hurt_by_stealth_rock(sim: &mut BattleSimulator, monster_id: MonsterID) {
+    let (primary_type, secondary_type) = mon![monster_id].species().type_();
+    let mut type_multiplier = type_matchup(Type::Rock, primary_type);
+    if let Some(secondary_type) = secondary_type {
+        type_multiplier = type_multiplier * type_matchup(Type::Rock, secondary_type);
+    }
+    let damage = (mon![monster_id].max_health() / 8) as f64 * type_multiplier;
+    sim.log_species_message(&StealthRock, "hurt", "Pointed stones dug into {monster}!", monster_id);
+    _ = DealDirectDamage(sim, monster_id, (monster_id, (damage as u16).max(1)));
+}
+
+fn poisoned_by_toxic_spikes(sim: &mut BattleSimulator, monster_id: MonsterID) {
+    // Poison types absorb the spikes when they switch in.
+    if mon![monster_id].is_type(Type::Poison) {
+        _ = RemoveSideCondition(sim, monster_id, (monster_id.team_id, &ToxicSpikes));
+        return;
+    }
+    if mon![monster_id].is_type(Type::Flying) || mon![monster_id].status().is_some() {
+        return;
+    }
+    let status = if hazard_layers(sim, monster_id, &ToxicSpikes) >= 2 { MajorStatus::BadPoison } else { MajorStatus::Poison };
+    _ = InflictStatus(sim, monster_id, (monster_id, status));
+}
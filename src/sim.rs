@@ -1,9 +1,19 @@
 pub mod effects;
 pub mod battle;
+pub mod battle_log;
 pub mod battle_constants;
+pub(crate) mod dex;
+pub mod effect_library;
 pub(crate) mod choice;
+pub(crate) mod decision;
 pub mod game_mechanics;
+#[cfg(feature = "loader")]
+pub mod loader;
+pub mod localisation;
 pub(crate) mod prng;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod species_registry;
 
 mod event_dispatch;
 mod ordering;
@@ -11,28 +21,53 @@ mod targetting;
 
 use std::{error::Error, fmt::Display, ops::RangeInclusive};
 
+use decision::PendingDecision;
+use event_dex::{OnModifyStat, OnTurnEnd, OnTurnStart};
+
 pub use effects::*;
 pub use battle::*;
-pub use builders::{MonsterBuilderExt, MoveBuilderExt, AbilityBuilderExt, BattleFormat};
-#[cfg(feature="macros")]
+pub use battle_log::{BattleLogEntry, BattleLogRenderer, Effectiveness, StatChangeDirection};
+pub use localisation::{EnglishRenderer, Message, MessageArgument, MessageCatalog, SpeciesText};
+pub use builders::{BattleBuilder, MonsterBuilder, MonsterBuilderExt, MoveBuilderExt, AbilityBuilderExt, ItemBuilderExt, BattleFormat};
+#[cfg(feature="battle_builder")]
 pub use monsim_macros::*;
 pub use battle_constants::*;
 pub use choice::*;
+pub use decision::{Decision, DecisionRequest};
 pub use event_dispatch::{
-    contexts::*, events::*, EventHandlerDeck, EventFilteringOptions, EventDispatcher, EventHandler, Event,
+    contexts::*, events::*, event_stack::{EventFrame, EventStack, Suppression, MAX_EVENT_DEPTH}, EventHandlerDeck, EventFilteringOptions, EventDispatcher, EventHandler, Event,
 };
 pub use game_mechanics::*;
 use monsim_utils::MaxSizedVec;
 pub use monsim_utils::{Outcome, Percent, ClampedPercent};
 pub(crate) use monsim_utils::{not, NOTHING, Nothing};
 pub use ordering::ActivationOrder;
+pub use prng::{Prng, Rng};
+pub use species_registry::{RegisteredSpecies, SpeciesRegistry};
+pub use dex::{Dex, DexSection, DexSpecies, DexError};
+pub use effect_library::{BuiltInEffect, EffectDescription, EffectTarget};
 pub use targetting::{TargetFlags, BoardPosition, FieldPosition};
 
 type SimResult = Result<(), SimError>;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SimError {
     InvalidStateReached(String),
+    /// Events kept triggering each other past `MAX_EVENT_DEPTH`. Contains the names of the events in the chain, outermost first.
+    /// The battle is rolled back to how it was before the action or phase of the turn in which this happened, and
+    /// once the turn has started it goes on from there with `BattleSimulator::resume`.
+    EventDepthLimitReached(Vec<&'static str>),
+    /// The decision submitted does not answer any pending `DecisionRequest`, or picks an option the request did not offer.
+    InvalidDecision(Decision),
+    /// A turn was simulated while the battle was waiting on switches, which have to be submitted first. Contains the requests for them.
+    DecisionsPending(Vec<DecisionRequest>),
+    /// A turn was simulated before the previous one finished resolving, which goes on with `BattleSimulator::resume`.
+    TurnInProgress,
+    /// The action choices given for a turn had problems, which are all listed.
+    InvalidChoices(Vec<ChoiceError>),
+    /// The script function backing a script handler failed or returned the wrong type.
+    #[cfg(feature = "scripting")]
+    ScriptFailed { script_function: &'static str, message: String },
 }
 
 impl Error for SimError {}
@@ -41,6 +76,21 @@ impl Display for SimError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SimError::InvalidStateReached(message) => write!(f, "{}", message),
+            SimError::EventDepthLimitReached(event_names) => write!(
+                f,
+                "Events were dispatched more than {} deep, the chain started with {}.",
+                MAX_EVENT_DEPTH, event_names.iter().take(4).copied().collect::<Vec<_>>().join(" -> ")
+            ),
+            SimError::InvalidDecision(decision) => write!(f, "{:?} does not answer any of the pending decision requests.", decision),
+            SimError::DecisionsPending(decision_requests) => write!(f, "The turn cannot be simulated before these switches are submitted: {:?}", decision_requests),
+            SimError::TurnInProgress => write!(f, "The turn cannot be simulated before the previous one is resumed and finished."),
+            SimError::InvalidChoices(choice_errors) => write!(
+                f, 
+                "The action choices are invalid: {}", 
+                choice_errors.iter().map(|choice_error| choice_error.to_string()).collect::<Vec<_>>().join(" ")
+            ),
+            #[cfg(feature = "scripting")]
+            SimError::ScriptFailed { script_function, message } => write!(f, "The script handler `{}` failed: {}", script_function, message),
         }
     }
 }
@@ -52,29 +102,65 @@ just functions that transform a `Battle` from one state to another.
 #[derive(Debug)]
 pub struct BattleSimulator {
     pub battle: BattleState,
+    pub(crate) event_stack: EventStack,
+    /// Turns the entries of the battle log into the text of the message log.
+    log_renderer: &'static dyn BattleLogRenderer,
 }
 
 impl BattleSimulator { // simulation
 
     pub fn init(battle: BattleState) -> BattleSimulator {
-        BattleSimulator {
+        let mut sim = BattleSimulator {
             battle,
+            event_stack: EventStack::new(),
+            log_renderer: &EnglishRenderer,
+        };
+        // A battle that was saved part-way through is still waiting on the decisions or the turn it was saved with.
+        if sim.battle.pending_decisions.is_empty() && not!(sim.battle.is_turn_in_progress) {
+            sim.queue_next_decisions();
         }
+        sim
     }
 
+    /// Simulates a turn with the given actions, which take the place of any turn actions submitted so far.
+    ///
+    /// If an action asks for a decision in the middle of the turn, as U-turn does, the rest of the turn is
+    /// resolved once that decision is submitted.
     pub fn simulate_turn(&mut self, mut action_choices: Vec<FullySpecifiedActionChoice>) -> SimResult {
         
         assert!(not!(self.battle.is_finished()), "The simulator cannot be called on a finished battle.");
 
+        let switch_requests = self.pending_requests()
+            .into_iter()
+            .filter(|decision_request| not!(matches!(decision_request, DecisionRequest::TurnAction { .. })))
+            .collect::<Vec<_>>();
+        if not!(switch_requests.is_empty()) {
+            return Err(SimError::DecisionsPending(switch_requests));
+        }
+        if self.battle.is_turn_in_progress {
+            return Err(SimError::TurnInProgress);
+        }
+
+        self.battle.validate_choices(&action_choices)?;
+
+        let battle_before_turn = self.battle.clone();
         self.battle.turn_number += 1;
         
-        self.battle.message_log.extend(&[
-            "---", 
-            EMPTY_LINE,
-            &format!["Turn {turn_number}", turn_number = self.battle.turn_number], 
-            EMPTY_LINE
-            ]
-        );
+        self.log(BattleLogEntry::TurnStarted { turn_number: self.battle.turn_number });
+
+        EventDispatcher::dispatch_residual_event(self, OnTurnStart, TurnContext::new(self.battle.turn_number));
+        self.roll_back_on_error(battle_before_turn)?;
+        self.battle.pending_decisions.clear();
+        self.battle.submitted_action_choices.clear();
+        if self.announce_battle_end_if_finished() {
+            return Ok(NOTHING);
+        }
+
+        // The speed used for ordering is recalculated here so that Abilities and Items modifying Speed are taken into account.
+        for action_choice in action_choices.iter_mut() {
+            let actor_id = action_choice.actor_id();
+            action_choice.activation_order_mut().speed = self.effective_stat(actor_id, Stat::Speed);
+        }
 
         ordering::sort_by_activation_order(
             &mut self.battle.prng, 
@@ -82,15 +168,24 @@ impl BattleSimulator { // simulation
             |choice| { choice.activation_order() }
         );
 
-        'turn: for action_choice in action_choices.into_iter() {
+        self.battle.action_queue = action_choices.into();
+        self.battle.is_turn_in_progress = true;
+        self.resume_turn()
+    }
+
+    /// Resolves the remaining actions of the turn followed by the end of the turn, unless an action asks for a
+    /// decision, in which case the turn waits for it to be submitted.
+    fn resume_turn(&mut self) -> SimResult {
+
+        while let Some(action_choice) = self.battle.action_queue.pop_front() {
+
+            let battle_before_action = self.battle.clone();
 
             // If the actor fainted we move on to the next action.
             let actor_id = action_choice.actor_id();
             if self.battle.monster(actor_id).is_fainted() {
-                self.push_message(
-                    format!["{} fainted so it was unable to act.", self.battle.monster(actor_id).name()]
-                );
-                continue 'turn;
+                self.log(BattleLogEntry::UnableToAct { monster_id: actor_id });
+                continue;
             }
 
             // Otherwise resolve the action
@@ -98,8 +193,7 @@ impl BattleSimulator { // simulation
                 FullySpecifiedActionChoice::Move { move_id, target_positions, .. } => {
                     // The target position may be empty if the target fainted with no replacement, for example.
                     let target_ids = target_positions.into_iter()
-                        .map(|position| self.battle.monster_at_position(position) )
-                        .flatten()
+                        .filter_map(|position| self.battle.monster_at_position(position) )
                         .map(|monster| monster.id )
                         .collect::<Vec<_>>();
                     UseMove(self, move_id.owner_id, MoveUseContext::new(move_id, MaxSizedVec::from_vec(target_ids)));
@@ -111,37 +205,186 @@ impl BattleSimulator { // simulation
 
             self.push_message(EMPTY_LINE);
 
-            // After each action, we check if the the battle is finished or not.
-            let ally_team_wiped = self.battle.ally_team().monsters().all(|monster| monster.is_fainted());
-            let opponent_team_wiped = self.battle.opponent_team().monsters().all(|monster| monster.is_fainted());
+            if let Err(error) = self.roll_back_on_error(battle_before_action) {
+                self.battle.action_queue.push_front(action_choice);
+                return Err(error);
+            }
 
-            match (ally_team_wiped, opponent_team_wiped) {
-                (true, true) => {
-                    self.push_message("Neither team has any usable Monsters, it's a tie!")
-                },
-                (true, false) => {
-                    self.push_message("Opponent team won!")
-                },
-                (false, true) => {
-                    self.push_message("Ally team won!")
-                },
-                (false, false) => {},
+            // After each action, we check if the the battle is finished or not.
+            if self.announce_battle_end_if_finished() {
+                self.battle.action_queue.clear();
+                self.battle.pending_decisions.clear();
+                self.battle.is_turn_in_progress = false;
+                return Ok(NOTHING);
             }
 
-            if ally_team_wiped || opponent_team_wiped {
-                self.battle.message_log.extend(&[
-                    EMPTY_LINE, 
-                    "The battle ended.",
-                    "---",
-                    EMPTY_LINE,
-                ]);
-                break 'turn;
+            let is_waiting_on_mid_move_switch = self.battle.pending_decisions
+                .iter()
+                .any(|pending_decision| matches!(pending_decision, PendingDecision::MidMoveSwitch(_)));
+            if is_waiting_on_mid_move_switch {
+                return Ok(NOTHING);
             }
         }
 
+        let battle_before_end_of_turn = self.battle.clone();
+        self.resolve_end_of_turn();
+        self.roll_back_on_error(battle_before_end_of_turn)?;
+        self.battle.is_turn_in_progress = false;
+
+        self.queue_next_decisions();
         Ok(NOTHING)
     }
 
+    /// Returns the error recorded while resolving the last step of the turn, if there is one, after restoring
+    /// the battle to `battle_before_step`, so that a failed step does not leave the battle half-resolved.
+    fn roll_back_on_error(&mut self, battle_before_step: BattleState) -> SimResult {
+        match self.event_stack.take_error() {
+            Some(error) => {
+                self.battle = battle_before_step;
+                Err(error)
+            },
+            None => Ok(NOTHING),
+        }
+    }
+
+    /// The end-of-turn phase, in which residual effects are resolved and timed conditions count down.
+    /// `OnTurnEnd` handlers go first, followed by status damage and end-of-turn field effects for each
+    /// active Monster in Speed order. The battle is checked for a winner after each residual.
+    fn resolve_end_of_turn(&mut self) {
+        EventDispatcher::dispatch_residual_event(self, OnTurnEnd, TurnContext::new(self.battle.turn_number));
+        if self.announce_battle_end_if_finished() {
+            return;
+        }
+
+        let active_monster_ids = self.battle.active_monsters().map(|monster| monster.id).collect::<Vec<_>>();
+        let mut residual_order = active_monster_ids.into_iter()
+            .map(|monster_id| (monster_id, ActivationOrder { priority: 0, speed: self.effective_stat(monster_id, Stat::Speed), order: 0 }))
+            .collect::<Vec<_>>();
+        ordering::sort_by_activation_order(&mut self.battle.prng, &mut residual_order, |(_, activation_order)| activation_order);
+        for (monster_id, _) in residual_order {
+            // A Monster that fainted to an earlier residual has no residuals of its own left.
+            if self.battle.monster(monster_id).is_fainted() {
+                continue;
+            }
+            DealStatusDamage(self, monster_id, monster_id);
+            if self.announce_battle_end_if_finished() {
+                return;
+            }
+            ResolveFieldEndOfTurnEffects(self, monster_id, monster_id);
+            if self.announce_battle_end_if_finished() {
+                return;
+            }
+            TickVolatileStatuses(self, monster_id, monster_id);
+        }
+
+        self.tick_field_state();
+        self.tick_side_conditions();
+        self.tick_suppressions();
+    }
+
+    /// Works out what the simulator needs decided before the battle can go on. Empty positions are filled
+    /// first, and once they have been the active Monsters are asked for their actions for the next turn.
+    fn queue_next_decisions(&mut self) {
+        self.battle.pending_decisions.clear();
+        if self.battle.is_finished() {
+            return;
+        }
+
+        let empty_field_positions = self.battle.format()
+            .field_positions()
+            .into_iter()
+            .filter(|field_position| self.battle.monster_at_position(*field_position).is_none())
+            .collect::<Vec<_>>();
+        let (fillable_field_positions, unfillable_field_positions): (Vec<_>, Vec<_>) = empty_field_positions.into_iter()
+            .partition(|field_position| not!(self.battle.switchable_benched_monster_ids(field_position.side(), &[]).is_empty()));
+        if not!(fillable_field_positions.is_empty()) {
+            self.battle.pending_decisions.extend(fillable_field_positions.into_iter().map(PendingDecision::ForcedSwitch));
+            return;
+        }
+        for field_position in unfillable_field_positions {
+            self.log(BattleLogEntry::PositionLeftEmpty { field_position });
+        }
+
+        let active_monster_ids = self.battle.active_monsters().map(|monster| monster.id).collect::<Vec<_>>();
+        self.battle.pending_decisions.extend(active_monster_ids.into_iter().map(PendingDecision::TurnAction));
+    }
+
+    /// Benched Monsters that have already been picked to switch in, by turn actions submitted for the coming
+    /// turn or by actions still to be resolved this turn.
+    fn monsters_already_chosen_for_switch(&self) -> Vec<MonsterID> {
+        self.battle.submitted_action_choices.iter()
+            .chain(self.battle.action_queue.iter())
+            .filter_map(|action_choice| match *action_choice {
+                FullySpecifiedActionChoice::SwitchOut { benched_monster_id, .. } => Some(benched_monster_id),
+                FullySpecifiedActionChoice::Move { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Checks whether either team has run out of usable Monsters, and if so logs the result of the battle.
+    fn announce_battle_end_if_finished(&mut self) -> bool {
+        let ally_team_wiped = self.battle.ally_team().monsters().all(|monster| monster.is_fainted());
+        let opponent_team_wiped = self.battle.opponent_team().monsters().all(|monster| monster.is_fainted());
+
+        let winner = match (ally_team_wiped, opponent_team_wiped) {
+            (true, true) => None,
+            (true, false) => Some(TeamID::Opponents),
+            (false, true) => Some(TeamID::Allies),
+            (false, false) => return false,
+        };
+        self.log(BattleLogEntry::BattleEnded { winner });
+        true
+    }
+
+    /// Counts down the durations of the weather and terrain, clearing any that have run out.
+    pub(crate) fn tick_field_state(&mut self) {
+        let FieldState { weather, terrain } = &mut self.battle.field_state;
+        let mut expired_effects = Vec::new();
+        for field_effect_slot in [weather, terrain] {
+            let Some(field_effect) = field_effect_slot else { continue };
+            if let Some(turns_remaining) = field_effect.turns_remaining.as_mut() {
+                *turns_remaining = turns_remaining.saturating_sub(1);
+                if *turns_remaining == 0 {
+                    expired_effects.push(field_effect.species());
+                    *field_effect_slot = None;
+                }
+            }
+        }
+        for species in expired_effects {
+            self.log(BattleLogEntry::FieldEffectEnded { species });
+        }
+    }
+
+    /// Counts down the durations of each team's side conditions, removing any that have run out.
+    pub(crate) fn tick_side_conditions(&mut self) {
+        for team_id in [TeamID::Allies, TeamID::Opponents] {
+            let side_conditions = &mut self.battle.team_mut(team_id).side_conditions;
+            let mut expired_conditions = Vec::new();
+            for side_condition in side_conditions.iter_mut() {
+                if let Some(turns_remaining) = side_condition.turns_remaining.as_mut() {
+                    *turns_remaining = turns_remaining.saturating_sub(1);
+                    if *turns_remaining == 0 {
+                        expired_conditions.push(side_condition.species());
+                    }
+                }
+            }
+            side_conditions.retain(|side_condition| side_condition.turns_remaining != Some(0));
+            for species in expired_conditions {
+                self.log(BattleLogEntry::SideConditionRemoved { team_id, species });
+            }
+        }
+    }
+
+    /// Counts down the durations of suppressions that last a number of turns, removing any that have run out.
+    pub(crate) fn tick_suppressions(&mut self) {
+        for suppression in self.battle.suppressions.iter_mut() {
+            if let Some(turns_remaining) = suppression.turns_remaining.as_mut() {
+                *turns_remaining = turns_remaining.saturating_sub(1);
+            }
+        }
+        self.battle.suppressions.retain(|suppression| suppression.turns_remaining != Some(0));
+    }
+
     fn trigger_try_event<C: Copy, E: Event<EventReturnType = Outcome, ContextType = C>>(
         &mut self, 
         event: E, 
@@ -161,14 +404,181 @@ impl BattleSimulator { // simulation
     ) -> R {
         EventDispatcher::dispatch_event(self, event, broadcaster_id, event_context, default, short_circuit)
     }
+
+    fn trigger_modify_event<V: Copy, C: Copy, E: Event<EventReturnType = V, ContextType = (C, V)>>(
+        &mut self,
+        event: E,
+        broadcaster_id: MonsterID,
+        event_context: C,
+        initial_value: V,
+    ) -> V {
+        EventDispatcher::dispatch_modify_event(self, event, broadcaster_id, event_context, initial_value)
+    }
 }
 
 impl BattleSimulator { // public
+
+    /// The chain of events currently being dispatched.
+    #[inline(always)]
+    pub fn event_stack(&self) -> &EventStack {
+        &self.event_stack
+    }
+
+    /// The decisions the simulator is waiting on, each with the options available for it. The battle goes on
+    /// once all of them have been answered through `submit`, and there are none once the battle is finished.
+    /// If there are none while the battle is not finished, a step of the turn failed and the turn goes on with `resume`.
+    pub fn pending_requests(&self) -> Vec<DecisionRequest> {
+        let monsters_already_chosen_for_switch = self.monsters_already_chosen_for_switch();
+        self.battle.pending_decisions.iter()
+            .map(|pending_decision| match *pending_decision {
+                PendingDecision::TurnAction(monster_id) => DecisionRequest::TurnAction {
+                    monster_id,
+                    available_choices: self.battle.available_choices_for(self.battle.monster(monster_id), &monsters_already_chosen_for_switch),
+                },
+                PendingDecision::ForcedSwitch(field_position) => DecisionRequest::ForcedSwitch {
+                    field_position,
+                    switchable_benched_monster_ids: self.battle.switchable_benched_monster_ids(field_position.side(), &[]),
+                },
+                PendingDecision::MidMoveSwitch(active_monster_id) => DecisionRequest::MidMoveSwitch {
+                    active_monster_id,
+                    switchable_benched_monster_ids: self.battle.switchable_benched_monster_ids(active_monster_id.team_id, &monsters_already_chosen_for_switch),
+                },
+            })
+            .collect()
+    }
+
+    /// Answers one of the `pending_requests`. Once the last turn action has been submitted the turn is
+    /// simulated, and once a mid-move switch has been submitted the rest of the turn is.
+    pub fn submit(&mut self, decision: Decision) -> SimResult {
+        let Some(pending_decision_index) = self.battle.pending_decisions.iter().position(|pending_decision| decision.answers(*pending_decision)) else {
+            return Err(SimError::InvalidDecision(decision));
+        };
+        let pending_request = self.pending_requests()[pending_decision_index];
+        let is_offered = match (decision, pending_request) {
+            // Turn actions are checked together with the ones submitted before them, so that problems are reported in full.
+            (Decision::TurnAction(action_choice), DecisionRequest::TurnAction { .. }) => {
+                let mut action_choices = self.battle.submitted_action_choices.clone();
+                action_choices.push(action_choice);
+                self.battle.validate_choices(&action_choices)?;
+                true
+            },
+            (Decision::ForcedSwitch { benched_monster_id, .. }, DecisionRequest::ForcedSwitch { switchable_benched_monster_ids, .. })
+            | (Decision::MidMoveSwitch { benched_monster_id, .. }, DecisionRequest::MidMoveSwitch { switchable_benched_monster_ids, .. }) => {
+                switchable_benched_monster_ids.iter().any(|monster_id| *monster_id == benched_monster_id)
+            },
+            _ => unreachable!("The pending request was found using the decision, so they are of the same kind."),
+        };
+        if not!(is_offered) {
+            return Err(SimError::InvalidDecision(decision));
+        }
+        let battle_before_decision = self.battle.clone();
+
+        match decision {
+            Decision::TurnAction(action_choice) => {
+                let is_waiting_on_other_turn_actions = self.battle.pending_decisions
+                    .iter()
+                    .enumerate()
+                    .any(|(index, pending_decision)| index != pending_decision_index && matches!(pending_decision, PendingDecision::TurnAction(_)));
+                if is_waiting_on_other_turn_actions {
+                    self.battle.pending_decisions.remove(pending_decision_index);
+                    self.battle.submitted_action_choices.push(action_choice);
+                    return Ok(NOTHING);
+                }
+                // The last request stays pending until the turn has started, so that it can be answered again if starting the turn fails.
+                let mut action_choices = self.battle.submitted_action_choices.clone();
+                action_choices.push(action_choice);
+                self.simulate_turn(action_choices)
+            },
+            Decision::ForcedSwitch { field_position, benched_monster_id } => {
+                self.battle.pending_decisions.remove(pending_decision_index);
+                ReplaceFaintedMonster(self, benched_monster_id, (benched_monster_id, field_position));
+                self.roll_back_on_error(battle_before_decision)?;
+                // The Monster switched in may have fainted straight away to entry hazards, and the other empty
+                // positions may have no Monsters left to fill them, so the requests are worked out again.
+                self.announce_battle_end_if_finished();
+                self.queue_next_decisions();
+                Ok(NOTHING)
+            },
+            Decision::MidMoveSwitch { active_monster_id, benched_monster_id } => {
+                self.battle.pending_decisions.remove(pending_decision_index);
+                PerformSwitchOut(self, active_monster_id, SwitchContext::new(active_monster_id, benched_monster_id));
+                self.roll_back_on_error(battle_before_decision)?;
+                self.resume_turn()
+            },
+        }
+    }
+
+    /// Goes on with a turn one of whose steps failed and was rolled back, starting from that step. Does nothing
+    /// if no turn is in progress, and fails with `SimError::DecisionsPending` if the turn is waiting on a decision.
+    pub fn resume(&mut self) -> SimResult {
+        if not!(self.battle.is_turn_in_progress) {
+            return Ok(NOTHING);
+        }
+        if not!(self.battle.pending_decisions.is_empty()) {
+            return Err(SimError::DecisionsPending(self.pending_requests()));
+        }
+        self.resume_turn()
+    }
+
+    /// Runs `f` with the handlers from `source` disabled, as Mold Breaker does to its target's ability for the duration of a move.
+    pub fn with_suppressed<R>(&mut self, source: EventHandlerSource, f: impl FnOnce(&mut BattleSimulator) -> R) -> R {
+        self.battle.suppressions.push(Suppression { source, turns_remaining: None });
+        let result = f(self);
+        let scoped_suppression_index = self.battle.suppressions
+            .iter()
+            .rposition(|suppression| suppression.source == source && suppression.turns_remaining.is_none())
+            .expect("The scoped suppression should only be removed at the end of its scope.");
+        self.battle.suppressions.remove(scoped_suppression_index);
+        result
+    }
+
+    /// Disables the handlers from `source` until the end of the `number_of_turns`th turn from now, counting the current one.
+    pub fn suppress_for_turns(&mut self, source: EventHandlerSource, number_of_turns: u8) {
+        self.battle.suppressions.push(Suppression { source, turns_remaining: Some(number_of_turns) });
+    }
     
     pub fn push_message(&mut self, message: impl ToString) {
         self.battle.message_log.push(message);
     }
 
+    /// Records `entry` in the battle log and pushes its rendering to the message log.
+    pub fn log(&mut self, entry: BattleLogEntry) {
+        self.battle.battle_log.push(entry);
+        for message in self.log_renderer.render_styled(&self.battle, entry) {
+            self.battle.message_log.push_styled(message);
+        }
+    }
+
+    /// Logs a message written by `species` about the Monster, `english_template` being the template of its `SpeciesText`.
+    pub fn log_species_message<T: DexSpecies>(&mut self, species: &T, key: &'static str, english_template: &'static str, monster_id: MonsterID) {
+        self.log(BattleLogEntry::SpeciesMessage { text: SpeciesText::new(species, key, english_template), monster_id });
+    }
+
+    /// Sets the renderer used for the messages of entries logged from now on. The simulator renders
+    /// in English by default.
+    pub fn set_log_renderer(&mut self, renderer: &'static dyn BattleLogRenderer) {
+        self.log_renderer = renderer;
+    }
+
+    /// Returns the value of `stat` for the Monster with id `monster_id` after applying its stat stages,
+    /// and then any modifications to the stat by `OnModifyStat` handlers.
+    pub fn effective_stat(&mut self, monster_id: MonsterID, stat: Stat) -> u16 {
+        let stage = self.battle.monster(monster_id).stat_modifier(stat);
+        self.effective_stat_at_stage(monster_id, stat, stage)
+    }
+
+    /// Like `effective_stat`, but as if the Monster's stat stage for `stat` was `stage`.
+    pub fn effective_stat_at_stage(&mut self, monster_id: MonsterID, stat: Stat, stage: i8) -> u16 {
+        let staged_stat = self.battle.monster(monster_id).stat_at_stage(stat, stage);
+        self.trigger_modify_event(OnModifyStat, monster_id, StatContext::new(monster_id, stat), staged_stat)
+    }
+
+    /// The battle's random number generator, for effects that need to roll their own chances.
+    #[inline(always)]
+    pub fn prng(&mut self) -> &mut Prng {
+        &mut self.battle.prng
+    }
+
     fn generate_random_number_in_range_inclusive(&mut self, range: RangeInclusive<u16>) -> u16 {
         self.battle.prng.generate_random_u16_in_range(range)
     }
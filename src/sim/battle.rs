@@ -1,37 +1,67 @@
 mod message_log;
+mod rich_text;
 pub(super) mod builders;
 
-use std::fmt::Display;
+use std::{collections::VecDeque, fmt::Display};
 use monsim_utils::{not, Ally, MaxSizedVec, Opponent};
-use crate::{sim::{Ability, ActivationOrder, AvailableChoices, Monster, MonsterID, MonsterTeam, Move, MoveID, Stat}, AbilityID, Event, OwnedEventHandler, PartiallySpecifiedActionChoice, TargetFlags};
+use crate::{sim::{Ability, ActivationOrder, AvailableChoices, BattleLogEntry, BattleLogRenderer, ChoiceError, EventFilteringOptions, FieldState, Item, ItemID, Monster, MonsterID, MonsterTeam, Move, MoveID, Stat}, AbilityID, Event, EventHandlerOwner, EventHandlerSource, OwnedEventHandler, Suppression, FullySpecifiedActionChoice, PartiallySpecifiedActionChoice, SimError, TargetFlags};
 
 use self::builders::BattleFormat;
 
-use super::{prng::Prng, targetting::{BoardPosition, FieldPosition}, PerTeam, TeamID};
+use super::{decision::PendingDecision, prng::Prng, targetting::{BoardPosition, FieldPosition}, PerTeam, TeamID};
 use message_log::MessageLog;
+pub use rich_text::{Colour, StyledSpan, StyledText, TextStyle};
 
 /// The main data struct that contains all the information one could want to know about the current battle. This is meant to be passed around as a unit and queried for battle-related information.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BattleState {
 
     pub(crate) prng: Prng,
     pub(crate) turn_number: u16,
     pub(crate) format: BattleFormat,
-    // TODO: Special text format for storing metadata with text (colour and modifiers like italic and bold).
     pub message_log: MessageLog,
+    /// Everything that has happened in the battle so far, in order. The `message_log` is rendered from these.
+    pub(crate) battle_log: Vec<BattleLogEntry>,
+    pub(crate) field_state: FieldState,
+    /// Event handler sources whose handlers are currently disabled.
+    pub(crate) suppressions: Vec<Suppression>,
+    /// The decisions the battle is waiting on before it can go on.
+    pub(crate) pending_decisions: Vec<PendingDecision>,
+    /// Turn actions submitted so far for the coming turn.
+    pub(crate) submitted_action_choices: Vec<FullySpecifiedActionChoice>,
+    /// Actions of the current turn still to be resolved, kept while the turn waits on a mid-turn decision.
+    pub(crate) action_queue: VecDeque<FullySpecifiedActionChoice>,
+    /// Whether the current turn has started and its end has not been resolved yet.
+    pub(crate) is_turn_in_progress: bool,
     
     teams: PerTeam<MonsterTeam>,
 }
 
+#[cfg(feature = "serde")]
 impl BattleState {
+    /// Deserializes a battle saved with serde, looking up the species it references in `registry`.
+    pub fn deserialize_with_registry<'de, D: serde::Deserializer<'de>>(deserializer: D, registry: &'static dyn crate::sim::SpeciesRegistry) -> Result<Self, D::Error> {
+        crate::sim::species_registry::with_species_registry(registry, || <Self as serde::Deserialize>::deserialize(deserializer))
+    }
+}
 
-    pub(crate) fn new(ally_team: Ally<MonsterTeam>, opponent_team: Opponent<MonsterTeam>, format: BattleFormat) -> Self {
+impl BattleState {
+
+    pub(crate) fn new(ally_team: Ally<MonsterTeam>, opponent_team: Opponent<MonsterTeam>, format: BattleFormat, prng: Prng) -> Self {
         let teams = PerTeam::new(ally_team, opponent_team);
         Self {
-            prng: Prng::from_current_time(),
+            prng,
             turn_number: 0,
             teams,
             message_log: MessageLog::new(),
+            battle_log: Vec::new(),
+            field_state: FieldState::new(),
+            suppressions: Vec::new(),
+            pending_decisions: Vec::new(),
+            submitted_action_choices: Vec::new(),
+            action_queue: VecDeque::new(),
+            is_turn_in_progress: false,
             format,
         }
     }
@@ -40,8 +70,27 @@ impl BattleState {
     pub fn format(&self) -> BattleFormat {
         self.format
     }
+
+    /// The seed used for all randomness in this battle. Building a battle with `BattleBuilder::with_seed`
+    /// using this seed and simulating the same sequence of choices reproduces the battle exactly.
+    #[inline(always)]
+    pub fn start_seed(&self) -> u64 {
+        self.prng.start_seed()
+    }
     
 
+    #[inline(always)]
+    pub fn battle_log(&self) -> &[BattleLogEntry] {
+        &self.battle_log
+    }
+
+    /// Renders the whole battle log with `renderer`, which can differ from the one that produced the `message_log`.
+    pub fn render_battle_log(&self, renderer: &dyn BattleLogRenderer) -> Vec<String> {
+        self.battle_log.iter()
+            .flat_map(|entry| renderer.render(self, *entry))
+            .collect()
+    }
+
     pub fn is_finished(&self) -> bool {
         self.ally_team().monsters().all(|monster| {
             monster.is_fainted()
@@ -98,7 +147,7 @@ impl BattleState {
     /// A monster is not considered its own ally.
     pub fn are_allies(&self, monster_1_id: MonsterID, monster_2_id: MonsterID) -> bool {
         if monster_1_id == monster_2_id {
-            return false;
+            false
         } else {
             monster_1_id.team_id == monster_2_id.team_id
         }
@@ -108,9 +157,50 @@ impl BattleState {
         let mut out = Vec::new();
         out.append(&mut self.ally_team().event_handlers_for(event));
         out.append(&mut self.opponent_team().event_handlers_for(event));
+        out.append(&mut self.field_event_handlers_for(event));
+        out.retain(|owned_event_handler| not!(self.is_suppressed(owned_event_handler.source)));
         out
     }
 
+    pub fn is_suppressed(&self, source: EventHandlerSource) -> bool {
+        self.suppressions.iter().any(|suppression| suppression.source == source)
+    }
+
+    #[inline(always)]
+    pub fn suppressions(&self) -> &[Suppression] {
+        &self.suppressions
+    }
+
+    fn field_event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
+        self.field_state
+            .field_effects()
+            .flat_map(|field_effect| {
+                event.corresponding_handlers(&field_effect.event_handlers())
+                    .into_iter()
+                    .map(|event_handler| {
+                        event_handler.owned_by(
+                            EventHandlerOwner::Field,
+                            EventHandlerSource::FieldEffect(field_effect.species()),
+                            ActivationOrder {
+                                priority: 0,
+                                speed: 0,
+                                order: field_effect.species().order(),
+                            },
+                            // Field handlers are not filtered, they respond to every broadcaster.
+                            EventFilteringOptions::default(),
+                        )
+                    })
+            })
+            .collect::<Vec<_>>()
+    }
+
+    // Field -----------------
+
+    #[inline(always)]
+    pub fn field_state(&self) -> &FieldState {
+        &self.field_state
+    }
+
     // Monsters -----------------
 
     pub fn monsters(&self) -> impl Iterator<Item = &Monster> {
@@ -159,6 +249,15 @@ impl BattleState {
             .ability
     }
 
+    // Items -----------------
+
+    /// Returns the item with id `item_id`, or `None` if its holder is no longer holding an item.
+    pub fn item(&self, item_id: ItemID) -> Option<&Item> {
+        self.monster(item_id.owner_id)
+            .held_item
+            .as_ref()
+    }
+
     // Moves -----------------
 
     pub fn move_(&self, move_id: MoveID) -> &Move {
@@ -173,7 +272,7 @@ impl BattleState {
 
     // Choice -------------------------------------
 
-    pub(crate) fn available_choices_for(&self, monster: &Monster, monsters_already_chosen_for_switch: &Vec<MonsterID>) -> AvailableChoices {
+    pub(crate) fn available_choices_for(&self, monster: &Monster, monsters_already_chosen_for_switch: &[MonsterID]) -> AvailableChoices {
         
         // Move choices
         let mut move_actions = Vec::with_capacity(4);
@@ -222,8 +321,92 @@ impl BattleState {
         )
     }
 
+    /// Checks the action choices for a turn against the choices available to each Monster, and returns
+    /// `SimError::InvalidChoices` listing every problem found.
+    pub fn validate_choices(&self, action_choices: &[FullySpecifiedActionChoice]) -> Result<(), SimError> {
+        let mut choice_errors = Vec::new();
+        let mut actor_ids = Vec::with_capacity(action_choices.len());
+        let mut monsters_already_chosen_for_switch = Vec::new();
+
+        for action_choice in action_choices {
+            let actor_id = action_choice.actor_id();
+            if actor_ids.contains(&actor_id) {
+                choice_errors.push(ChoiceError::DuplicateAction(actor_id));
+                continue;
+            }
+            actor_ids.push(actor_id);
+
+            let actor = self.monster(actor_id);
+            let Some(targetter_position) = actor.field_position() else {
+                choice_errors.push(ChoiceError::ActorNotActive(actor_id));
+                continue;
+            };
+            let available_choices = self.available_choices_for(actor, &monsters_already_chosen_for_switch);
+
+            match *action_choice {
+                FullySpecifiedActionChoice::Move { move_id, target_positions, activation_order } => {
+                    let maybe_available_move_choice = available_choices.move_choices().find_map(|choice| match *choice {
+                        PartiallySpecifiedActionChoice::Move { move_id: available_move_id, possible_target_positions, activation_order, .. } if available_move_id == move_id => {
+                            Some((possible_target_positions, activation_order))
+                        },
+                        _ => None,
+                    });
+                    let Some((possible_target_positions, available_activation_order)) = maybe_available_move_choice else {
+                        choice_errors.push(ChoiceError::MoveNotAvailable(move_id));
+                        continue;
+                    };
+                    if activation_order.priority != available_activation_order.priority {
+                        choice_errors.push(ChoiceError::WrongPriority { actor_id, priority: activation_order.priority, expected_priority: available_activation_order.priority });
+                    }
+                    let allowed_target_flags = self.move_(move_id).allowed_target_flags();
+                    for target_position in target_positions.iter().copied() {
+                        let is_valid_target_position = self.is_valid_target_position(targetter_position, allowed_target_flags, target_position)
+                            && possible_target_positions.iter().any(|position| *position == target_position);
+                        if not!(is_valid_target_position) {
+                            choice_errors.push(ChoiceError::InvalidTargetPosition { move_id, target_position });
+                        }
+                    }
+                    // `ANY` has no bits of its own, so single target moves are the ones without `ALL`.
+                    if not!(allowed_target_flags.contains(TargetFlags::ALL)) {
+                        if target_positions.count() != 1 {
+                            choice_errors.push(ChoiceError::WrongNumberOfTargets { move_id, number_of_targets: target_positions.count() });
+                        }
+                    } else if target_positions.is_empty() && not!(possible_target_positions.is_empty()) {
+                        choice_errors.push(ChoiceError::NoTargets(move_id));
+                    }
+                },
+                FullySpecifiedActionChoice::SwitchOut { active_monster_id, benched_monster_id, activation_order } => {
+                    let maybe_available_switch = available_choices.switch_out_choice().and_then(|choice| match *choice {
+                        PartiallySpecifiedActionChoice::SwitchOut { switchable_benched_monster_ids, activation_order, .. } => {
+                            Some((switchable_benched_monster_ids, activation_order))
+                        },
+                        PartiallySpecifiedActionChoice::Move { .. } => None,
+                    });
+                    let is_switchable = maybe_available_switch.is_some_and(|(switchable_benched_monster_ids, _)| {
+                        switchable_benched_monster_ids.iter().any(|monster_id| *monster_id == benched_monster_id)
+                    });
+                    if not!(is_switchable) {
+                        choice_errors.push(ChoiceError::InvalidSwitchIn { active_monster_id, benched_monster_id });
+                    }
+                    if let Some((_, available_activation_order)) = maybe_available_switch {
+                        if activation_order.priority != available_activation_order.priority {
+                            choice_errors.push(ChoiceError::WrongPriority { actor_id, priority: activation_order.priority, expected_priority: available_activation_order.priority });
+                        }
+                    }
+                    monsters_already_chosen_for_switch.push(benched_monster_id);
+                },
+            }
+        }
+
+        if choice_errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SimError::InvalidChoices(choice_errors))
+        }
+    }
+
     /// Returns an array of options where all the `Some` variants are at the beginning.
-    pub(crate) fn switchable_benched_monster_ids(&self, team_id: TeamID, monsters_already_chosen_for_switch: &Vec<MonsterID>) -> MaxSizedVec<MonsterID, 5> {
+    pub(crate) fn switchable_benched_monster_ids(&self, team_id: TeamID, monsters_already_chosen_for_switch: &[MonsterID]) -> MaxSizedVec<MonsterID, 5> {
         let mut number_of_switchees = 0;
         let mut switchable_benched_monsters = Vec::with_capacity(5);
         for monster in self.team(team_id).monsters() {
@@ -1,7 +1,7 @@
 use monsim_utils::{Ally, MaxSizedVec, Opponent};
 use tap::Pipe;
 
-use crate::{sim::{game_mechanics::{Ability, AbilitySpecies, MonsterNature, MonsterSpecies, MoveSpecies, StatModifierSet, StatSet}, targetting::{BoardPosition, FieldPosition}}, AbilityID, BattleState, DealDefaultDamage, Monster, MonsterID, MonsterTeam, Move, MoveCategory, MoveID, Stat, TeamID, ALLY_1, ALLY_2, ALLY_3, ALLY_4, ALLY_5, ALLY_6, OPPONENT_1, OPPONENT_2, OPPONENT_3, OPPONENT_4, OPPONENT_5, OPPONENT_6};
+use crate::{sim::{prng::Prng, game_mechanics::{Ability, AbilitySpecies, Item, ItemSpecies, MonsterNature, MonsterSpecies, MoveSpecies, StatModifierSet, StatSet}, targetting::{BoardPosition, FieldPosition}}, AbilityID, BattleState, BuiltInEffect, EffectDescription, ItemID, Monster, MonsterID, MonsterTeam, Move, MoveCategory, MoveID, Stat, TeamID, ALLY_1, ALLY_2, ALLY_3, ALLY_4, ALLY_5, ALLY_6, OPPONENT_1, OPPONENT_2, OPPONENT_3, OPPONENT_4, OPPONENT_5, OPPONENT_6};
 
 /*  
     FEATURE: Better Validation -> Some basic state validation will be done 
@@ -15,22 +15,42 @@ use crate::{sim::{game_mechanics::{Ability, AbilitySpecies, MonsterNature, Monst
 pub struct BattleBuilder {
     maybe_ally_team: Option<Ally<MonsterTeamBuilder>>,
     maybe_opponent_team: Option<Opponent<MonsterTeamBuilder>>,
-    format: BattleFormat
+    format: BattleFormat,
+    maybe_seed: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BattleFormat {
     Single,
     Double,
     Triple,
 }
 
+impl BattleFormat {
+    /// The positions on the field that are occupied in this format.
+    pub fn field_positions(self) -> Vec<FieldPosition> {
+        match self {
+            BattleFormat::Single => {
+                vec![FieldPosition::AllySideCentre, FieldPosition::OpponentSideCentre]
+            },
+            BattleFormat::Double => {
+                vec![FieldPosition::AllySideCentre, FieldPosition::AllySideRight, FieldPosition::OpponentSideCentre, FieldPosition::OpponentSideRight]
+            },
+            BattleFormat::Triple => {
+                vec![FieldPosition::AllySideLeft, FieldPosition::AllySideCentre, FieldPosition::AllySideRight, FieldPosition::OpponentSideLeft, FieldPosition::OpponentSideCentre, FieldPosition::OpponentSideRight]
+            },
+        }
+    }
+}
+
 impl BattleState {
     pub fn spawn() -> BattleBuilder {
         BattleBuilder { 
             maybe_ally_team: None, 
             maybe_opponent_team: None, 
-            format: BattleFormat::Single
+            format: BattleFormat::Single,
+            maybe_seed: None,
         }
     }
 }
@@ -53,6 +73,13 @@ impl BattleBuilder {
         self
     }
 
+    /// Seeds the battle's random number generator, the current time is used if no seed is given. The
+    /// same seed and the same sequence of `FullySpecifiedActionChoice`s always produce the same battle.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.maybe_seed = Some(seed);
+        self
+    }
+
     pub fn build(self) -> BattleState {
         
         let ally_board_positions = match self.format {
@@ -151,7 +178,9 @@ impl BattleBuilder {
                 opponent_team_builder.build(OPPONENT_IDS, opponent_board_positions, TeamID::Opponents)                
             });
 
-        BattleState::new(ally_team, opponent_team, self.format)
+        let prng = self.maybe_seed.map_or_else(Prng::from_current_time, Prng::new);
+
+        BattleState::new(ally_team, opponent_team, self.format, prng)
     }    
 }
 
@@ -182,12 +211,10 @@ impl MonsterTeamBuilder {
 
     fn build(self, monster_ids: [MonsterID; 6], board_positions: [BoardPosition; 6], team_id: TeamID) -> MonsterTeam {
         self.maybe_monsters
-            .expect(
-                format!["Expected {team_id} to have at least one monster, but none were given"].as_str()
-            )
+            .unwrap_or_else(|| panic!("Expected {team_id} to have at least one monster, but none were given"))
             .into_iter()
-            .zip(monster_ids.into_iter())
-            .zip(board_positions.into_iter())
+            .zip(monster_ids)
+            .zip(board_positions)
             .map(|((monster_builder, monster_id), board_position)| {
                 monster_builder.build(monster_id, board_position)
             })
@@ -201,6 +228,7 @@ pub struct MonsterBuilder {
     species: &'static MonsterSpecies,
     moves: MaxSizedVec<MoveBuilder, 4>,
     ability: AbilityBuilder,
+    item: Option<ItemBuilder>,
     nickname: Option<&'static str>,
     _level: Option<u16>,
     _nature: Option<MonsterNature>,
@@ -214,7 +242,7 @@ pub trait MonsterBuilderExt {
 
 impl MonsterBuilderExt for MonsterSpecies {
     fn spawn(&'static self, moves: (MoveBuilder, Option<MoveBuilder>, Option<MoveBuilder>, Option<MoveBuilder>), ability: AbilityBuilder) -> MonsterBuilder {
-        Monster::with(&self, moves, ability)
+        Monster::with(self, moves, ability)
     }
 }
 
@@ -253,6 +281,7 @@ impl Monster {
             species,
             moves,
             ability,
+            item: None,
             nickname: None,
             _level: None,
             _nature: None,
@@ -268,6 +297,11 @@ impl MonsterBuilder {
         self
     } 
 
+    pub fn with_item(mut self, item: ItemBuilder) -> Self {
+        self.item = Some(item);
+        self
+    }
+
     pub fn build(self, monster_id: MonsterID, board_position: BoardPosition) -> Monster {
         
         let nickname = self.nickname;
@@ -281,7 +315,7 @@ impl MonsterBuilder {
 
         let moveset = self.moves
             .into_iter()
-            .zip(move_ids.into_iter()).map(|(move_builder, move_id)| {
+            .zip(move_ids).map(|(move_builder, move_id)| {
                 move_builder.build(move_id)
             })
             .collect::<Vec<_>>()
@@ -289,6 +323,9 @@ impl MonsterBuilder {
         
         let ability = self.ability
             .build(AbilityID { owner_id: monster_id});
+
+        let held_item = self.item
+            .map(|item_builder| item_builder.build(ItemID::from_owner(monster_id)));
         
         let level = 50;
         // FEATURE: EVs and IVs should be settable through the builder.
@@ -305,10 +342,15 @@ impl MonsterBuilder {
             individual_values: IVS,
             level,
             nature,
-            stat_modifiers: StatModifierSet::new(0, 0, 0, 0, 0),
+            stat_modifiers: StatModifierSet::new(0, 0, 0, 0, 0, 0, 0),
+            status: None,
+            status_turn_counter: 0,
+            volatile_statuses: Vec::new(),
+            crit_stage: 0,
             species: self.species,
             moveset,
             ability,
+            held_item,
             board_position,
         } 
     }
@@ -354,8 +396,7 @@ impl MoveBuilder {
     fn build(self, move_id: MoveID) -> Move {
         let species = self.species;
         // FEATURE: When the engine is more mature, we'd like to make warnings like this toggleable.
-        if species.category() == MoveCategory::Status && species.
-        on_hit_effect() == DealDefaultDamage {
+        if species.category() == MoveCategory::Status && species.on_hit_effect() == EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage) {
             println!("\n Warning: The user created move {} has been given the category \"Status\" but deals damage only. Consider changing its category to Physical or Special. If this is intentional, ignore this message.", species.name())
         }
         Move {
@@ -400,4 +441,37 @@ impl AbilityBuilder {
             species: self.species,
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Clone)]
+pub struct ItemBuilder {
+    pub species: & 'static ItemSpecies,
+}
+
+pub trait ItemBuilderExt {
+    fn spawn(&'static self) -> ItemBuilder;
+}
+
+impl ItemBuilderExt for ItemSpecies {
+    fn spawn(&'static self) -> ItemBuilder {
+        Item::builder(self)
+    }
+}
+
+impl Item {
+    /// Starting point for building an Item.
+    pub fn builder(species: &'static ItemSpecies) -> ItemBuilder {
+        ItemBuilder {
+            species,
+        }
+    }
+}
+
+impl ItemBuilder {
+    fn build(self, id: ItemID) -> Item {
+        Item {
+            id,
+            species: self.species,
+        }
+    }
+}
@@ -1,14 +1,45 @@
+use super::rich_text::StyledText;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "serialization::SerializedMessageLog"))]
 pub struct MessageLog {
     messages: Vec<String>,
+    /// The same messages with their styles, for consumers that can show them.
+    styled_messages: Vec<StyledText>,
     last_turn_cursor: usize,
 }
 
+#[cfg(feature = "serde")]
+mod serialization {
+    use super::{MessageLog, StyledText};
+
+    /// A `MessageLog` as saved, which may be from before messages had styles.
+    #[derive(serde::Deserialize)]
+    pub(super) struct SerializedMessageLog {
+        messages: Vec<String>,
+        #[serde(default)]
+        styled_messages: Vec<StyledText>,
+        last_turn_cursor: usize,
+    }
+
+    impl From<SerializedMessageLog> for MessageLog {
+        fn from(SerializedMessageLog { messages, mut styled_messages, last_turn_cursor }: SerializedMessageLog) -> Self {
+            // Logs saved without styles have them rebuilt as plain text, so that the two lists of messages line up.
+            if styled_messages.len() != messages.len() {
+                styled_messages = messages.iter().map(StyledText::plain).collect();
+            }
+            MessageLog { messages, styled_messages, last_turn_cursor }
+        }
+    }
+}
+
 const INITIAL_MESSAGE_LOG_CAPACITY: usize = 200;
 impl MessageLog {
     pub fn new() -> Self {
         Self {
             messages: Vec::with_capacity(INITIAL_MESSAGE_LOG_CAPACITY),
+            styled_messages: Vec::with_capacity(INITIAL_MESSAGE_LOG_CAPACITY),
             last_turn_cursor: 0,
         }
     }
@@ -21,6 +52,10 @@ impl MessageLog {
         &self.messages
     } 
 
+    pub fn styled_messages(&self) -> &[StyledText] {
+        &self.styled_messages
+    }
+
     pub fn show_all_messages(&self) {
         self.messages.iter().for_each(|message| {
             println!("{}", message);
@@ -33,21 +68,42 @@ impl MessageLog {
         })
     }
     
+    /// Like `show_last_turn_messages`, but with the styles of the messages written as ANSI escape codes.
+    pub fn show_last_turn_styled_messages(&self) {
+        self.styled_messages[self.last_turn_cursor..].iter().for_each(|message| {
+            println!("{}", message.to_ansi());
+        })
+    }
+
+    /// The whole log as HTML, one paragraph per message.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<div class=\"message-log\">\n");
+        for message in &self.styled_messages {
+            html.push_str("<p>");
+            html.push_str(&message.to_html());
+            html.push_str("</p>\n");
+        }
+        html.push_str("</div>\n");
+        html
+    }
+    
     pub fn snap_last_turn_cursor_to_end(&mut self) {
         self.last_turn_cursor = self.len()
     }
 
     pub fn push(&mut self, message: impl ToString) {
-        self.messages.push(message.to_string());
+        self.push_styled(StyledText::plain(message));
+    }
+
+    /// Pushes `message` with its styles, plain-text consumers of the log see it without them.
+    pub fn push_styled(&mut self, message: StyledText) {
+        self.messages.push(message.plain_text());
+        self.styled_messages.push(message);
     }
 
     pub fn extend(&mut self, messages: &[&str]) {
         for message in messages {
-            self.messages.push(message.to_string());
+            self.push(message);
         }
     }
-    
-    pub(crate) fn show_last_message(&self) {
-        println!("{}", self.messages[self.len()-1]);
-    }
 }
\ No newline at end of file
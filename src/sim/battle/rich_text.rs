@@ -0,0 +1,223 @@
+use std::fmt::{Display, Write};
+
+use crate::sim::{TeamID, Type};
+
+/// A colour for styled text, given as its red, green and blue components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const ALLY_TEAM: Colour = Colour::rgb(0x4A, 0x90, 0xE2);
+    pub const OPPONENT_TEAM: Colour = Colour::rgb(0xE2, 0x4A, 0x4A);
+    pub const TURN_HEADER: Colour = Colour::rgb(0x3F, 0xC1, 0xC9);
+
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    pub fn of_team(team_id: TeamID) -> Self {
+        match team_id {
+            TeamID::Allies => Colour::ALLY_TEAM,
+            TeamID::Opponents => Colour::OPPONENT_TEAM,
+        }
+    }
+
+    pub fn of_type(type_: Type) -> Self {
+        match type_ {
+            Type::Bug => Colour::rgb(0xA6, 0xB9, 0x1A),
+            Type::Dark => Colour::rgb(0x70, 0x57, 0x46),
+            Type::Dragon => Colour::rgb(0x6F, 0x35, 0xFC),
+            Type::Electric => Colour::rgb(0xF7, 0xD0, 0x2C),
+            Type::Fairy => Colour::rgb(0xD6, 0x85, 0xAD),
+            Type::Fighting => Colour::rgb(0xC2, 0x2E, 0x28),
+            Type::Fire => Colour::rgb(0xEE, 0x81, 0x30),
+            Type::Flying => Colour::rgb(0xA9, 0x8F, 0xF3),
+            Type::Ghost => Colour::rgb(0x73, 0x57, 0x97),
+            Type::Grass => Colour::rgb(0x7A, 0xC7, 0x4C),
+            Type::Ground => Colour::rgb(0xE2, 0xBF, 0x65),
+            Type::Ice => Colour::rgb(0x96, 0xD9, 0xD6),
+            Type::Normal => Colour::rgb(0xA8, 0xA7, 0x7A),
+            Type::Poison => Colour::rgb(0xA3, 0x3E, 0xA1),
+            Type::Psychic => Colour::rgb(0xF9, 0x55, 0x87),
+            Type::Rock => Colour::rgb(0xB6, 0xA1, 0x36),
+            Type::Steel => Colour::rgb(0xB7, 0xB7, 0xCE),
+            Type::Water => Colour::rgb(0x63, 0x90, 0xF0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextStyle {
+    pub colour: Option<Colour>,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl TextStyle {
+    pub const PLAIN: TextStyle = TextStyle { colour: None, bold: false, italic: false };
+
+    pub const fn coloured(colour: Colour) -> Self {
+        TextStyle { colour: Some(colour), ..TextStyle::PLAIN }
+    }
+
+    pub const fn bold(self) -> Self {
+        TextStyle { bold: true, ..self }
+    }
+
+    pub const fn italic(self) -> Self {
+        TextStyle { italic: true, ..self }
+    }
+
+    /// `other` laid over this style, keeping this style's colour unless `other` has its own.
+    pub fn merged_with(self, other: TextStyle) -> Self {
+        TextStyle {
+            colour: other.colour.or(self.colour),
+            bold: self.bold || other.bold,
+            italic: self.italic || other.italic,
+        }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        *self == TextStyle::PLAIN
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StyledSpan {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+/// A message made of spans of text that each have their own style. It displays as its plain text, and
+/// can be written out for terminals, the TUI or HTML.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StyledText {
+    spans: Vec<StyledSpan>,
+}
+
+impl StyledText {
+    pub fn new() -> Self {
+        Self { spans: Vec::new() }
+    }
+
+    pub fn plain(text: impl ToString) -> Self {
+        let mut styled_text = StyledText::new();
+        styled_text.push(text.to_string(), TextStyle::PLAIN);
+        styled_text
+    }
+
+    /// Appends `text` in `style`, joining it to the last span if that has the same style.
+    pub fn push(&mut self, text: impl Into<String>, style: TextStyle) {
+        let text = text.into();
+        if text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(last_span) if last_span.style == style => last_span.text.push_str(&text),
+            _ => self.spans.push(StyledSpan { text, style }),
+        }
+    }
+
+    #[inline(always)]
+    pub fn spans(&self) -> &[StyledSpan] {
+        &self.spans
+    }
+
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// The text with ANSI escape codes for its styles, using 24-bit colours.
+    pub fn to_ansi(&self) -> String {
+        let mut ansi_text = String::new();
+        for StyledSpan { text, style } in &self.spans {
+            if style.is_plain() {
+                ansi_text.push_str(text);
+                continue;
+            }
+            let mut codes = Vec::new();
+            if style.bold {
+                codes.push("1".to_owned());
+            }
+            if style.italic {
+                codes.push("3".to_owned());
+            }
+            if let Some(Colour { red, green, blue }) = style.colour {
+                codes.push(format!["38;2;{red};{green};{blue}"]);
+            }
+            _ = write!(ansi_text, "\x1b[{}m{text}\x1b[0m", codes.join(";"));
+        }
+        ansi_text
+    }
+
+    /// The text as HTML, with styled spans in `<span>`s with inline styles.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        for StyledSpan { text, style } in &self.spans {
+            if style.is_plain() {
+                html.push_str(&escape_html(text));
+                continue;
+            }
+            let mut declarations = Vec::new();
+            if let Some(Colour { red, green, blue }) = style.colour {
+                declarations.push(format!["color: #{red:02x}{green:02x}{blue:02x}"]);
+            }
+            if style.bold {
+                declarations.push("font-weight: bold".to_owned());
+            }
+            if style.italic {
+                declarations.push("font-style: italic".to_owned());
+            }
+            _ = write!(html, "<span style=\"{}\">{}</span>", declarations.join("; "), escape_html(text));
+        }
+        html
+    }
+
+    #[cfg(feature = "monsim_tui")]
+    pub fn to_tui_spans(&self) -> tui::text::Spans<'_> {
+        use tui::{style::{Color, Modifier, Style}, text::{Span, Spans}};
+
+        Spans::from(self.spans.iter().map(|StyledSpan { text, style: text_style }| {
+            let mut style = Style::default();
+            if let Some(Colour { red, green, blue }) = text_style.colour {
+                style = style.fg(Color::Rgb(red, green, blue));
+            }
+            if text_style.bold {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            if text_style.italic {
+                style = style.add_modifier(Modifier::ITALIC);
+            }
+            Span::styled(text.as_str(), style)
+        }).collect::<Vec<_>>())
+    }
+}
+
+impl Display for StyledText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.spans.iter().try_for_each(|span| write!(f, "{}", span.text))
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped_text = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped_text.push_str("&amp;"),
+            '<' => escaped_text.push_str("&lt;"),
+            '>' => escaped_text.push_str("&gt;"),
+            '"' => escaped_text.push_str("&quot;"),
+            '\'' => escaped_text.push_str("&#39;"),
+            _ => escaped_text.push(character),
+        }
+    }
+    escaped_text
+}
@@ -0,0 +1,370 @@
+use std::{cmp::Ordering, fmt::Debug};
+
+use monsim_utils::Percent;
+
+use crate::sim::{
+    localisation::{Message, MessageArgument, SpeciesText}, AbilityID, BattleState, StyledText, FieldEffectSpecies, FieldPosition, ItemID, ItemSpecies, MajorStatus,
+    MonsterID, MoveID, SideConditionSpecies, Stat, TeamID, VolatileStatusSpecies,
+};
+
+/// A record of something that happened in the battle, kept in `BattleState::battle_log` alongside the text
+/// of the `MessageLog`. Entries refer to Monsters, moves and so on by ID, so they can be looked up in the
+/// battle they were recorded in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BattleLogEntry {
+    TurnStarted {
+        turn_number: u16,
+    },
+    /// The Monster fainted before its action came up.
+    UnableToAct {
+        monster_id: MonsterID,
+    },
+    PreventedFromMoving {
+        monster_id: MonsterID,
+        status: MajorStatus,
+    },
+    MoveUsed {
+        move_id: MoveID,
+    },
+    /// Every target of the move fainted before it was used.
+    MoveHadNoTargets {
+        move_id: MoveID,
+    },
+    /// An `OnTryMove` handler stopped the move.
+    MoveFailed {
+        move_id: MoveID,
+    },
+    MoveMissed {
+        move_id: MoveID,
+        target_id: MonsterID,
+    },
+    /// An `OnTryMoveHit` handler stopped the move from hitting the target.
+    MoveFailedToHit {
+        move_id: MoveID,
+        target_id: MonsterID,
+    },
+    MoveHitMultipleTimes {
+        move_id: MoveID,
+        number_of_hits: u8,
+    },
+    /// The target's types make it immune to the move.
+    TargetImmune {
+        move_id: MoveID,
+        target_id: MonsterID,
+    },
+    /// `move_used_id`, `effectiveness` and `is_critical_hit` are only filled in for damage dealt by a move.
+    DamageDealt {
+        source_id: MonsterID,
+        target_id: MonsterID,
+        amount: u16,
+        remaining_health: u16,
+        move_used_id: Option<MoveID>,
+        effectiveness: Option<Effectiveness>,
+        is_critical_hit: bool,
+    },
+    HealthRestored {
+        monster_id: MonsterID,
+        amount: u16,
+    },
+    Fainted {
+        monster_id: MonsterID,
+    },
+    /// `replaced_monster_id` is the Monster that switched out, if the switch wasn't filling an empty position.
+    SwitchedIn {
+        monster_id: MonsterID,
+        replaced_monster_id: Option<MonsterID>,
+    },
+    AbilityActivated {
+        ability_id: AbilityID,
+    },
+    ItemUsed {
+        item_id: ItemID,
+    },
+    ItemRemoved {
+        monster_id: MonsterID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        item: &'static ItemSpecies,
+    },
+    ItemTransferred {
+        giver_id: MonsterID,
+        receiver_id: MonsterID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        item: &'static ItemSpecies,
+    },
+    ItemsSwapped {
+        first_monster_id: MonsterID,
+        second_monster_id: MonsterID,
+    },
+    CritStageRaised {
+        monster_id: MonsterID,
+    },
+    StatChanged {
+        monster_id: MonsterID,
+        stat: Stat,
+        direction: StatChangeDirection,
+        stages: u8,
+    },
+    StatChangeFailed {
+        monster_id: MonsterID,
+        stat: Stat,
+        direction: StatChangeDirection,
+    },
+    StatusInflicted {
+        monster_id: MonsterID,
+        status: MajorStatus,
+    },
+    AlreadyHasStatus {
+        monster_id: MonsterID,
+        status: MajorStatus,
+    },
+    /// The Monster's types make it immune to the effect.
+    NotAffected {
+        monster_id: MonsterID,
+    },
+    HurtByStatus {
+        monster_id: MonsterID,
+        status: MajorStatus,
+    },
+    StatusCured {
+        monster_id: MonsterID,
+        status: MajorStatus,
+    },
+    VolatileStatusAdded {
+        monster_id: MonsterID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        species: &'static VolatileStatusSpecies,
+    },
+    VolatileStatusRemoved {
+        monster_id: MonsterID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        species: &'static VolatileStatusSpecies,
+    },
+    /// Weather or terrain started.
+    FieldEffectStarted {
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        species: &'static FieldEffectSpecies,
+    },
+    FieldEffectEnded {
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        species: &'static FieldEffectSpecies,
+    },
+    SideConditionAdded {
+        team_id: TeamID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        species: &'static SideConditionSpecies,
+    },
+    SideConditionRemoved {
+        team_id: TeamID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        species: &'static SideConditionSpecies,
+    },
+    /// An effect failed without anything more specific to say about it.
+    EffectFailed,
+    /// Something a species says about the Monster, as when a Sitrus Berry restores its holder's health.
+    SpeciesMessage {
+        text: SpeciesText,
+        monster_id: MonsterID,
+    },
+    /// The position is empty and its team has no Monsters left to fill it with.
+    PositionLeftEmpty {
+        field_position: FieldPosition,
+    },
+    /// `winner` is `None` if both teams ran out of Monsters at once.
+    BattleEnded {
+        winner: Option<TeamID>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StatChangeDirection {
+    Raised,
+    Lowered,
+}
+
+/// How effective a damaging move was against its target's types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Effectiveness {
+    NotVeryEffective,
+    Effective,
+    SuperEffective,
+}
+
+impl Effectiveness {
+    /// The effectiveness of a type matchup multiplier other than an immunity. Handlers can modify the
+    /// multiplier to any value, so it is classified by whether it is below, at or above 100%.
+    pub fn from_multiplier(type_matchup_multiplier: Percent) -> Self {
+        match type_matchup_multiplier.0.cmp(&100) {
+            Ordering::Less => Effectiveness::NotVeryEffective,
+            Ordering::Equal => Effectiveness::Effective,
+            Ordering::Greater => Effectiveness::SuperEffective,
+        }
+    }
+}
+
+/// Turns `BattleLogEntry`s into the lines of text pushed to the `MessageLog`. `MessageCatalog` renders
+/// entries through their `messages`, in whichever language the catalog is written in.
+pub trait BattleLogRenderer: Debug {
+    fn render(&self, battle: &BattleState, entry: BattleLogEntry) -> Vec<String>;
+
+    /// The entry's lines with styles, which are left plain unless the renderer has styles of its own.
+    fn render_styled(&self, battle: &BattleState, entry: BattleLogEntry) -> Vec<StyledText> {
+        self.render(battle, entry).into_iter().map(StyledText::plain).collect()
+    }
+}
+
+impl BattleLogEntry {
+    /// The messages the entry is written as. An entry may have no messages at all, for example when the
+    /// effect that recorded it lets the species involved describe it.
+    pub fn messages(&self) -> Vec<Message> {
+        use MessageArgument::*;
+        match *self {
+            BattleLogEntry::TurnStarted { turn_number } => vec![
+                Message::new("divider"),
+                Message::new("blank_line"),
+                Message::new("turn_started").with("turn_number", Number(turn_number)),
+                Message::new("blank_line"),
+            ],
+            BattleLogEntry::UnableToAct { monster_id } => vec![Message::new("unable_to_act").with("monster", Monster(monster_id))],
+            BattleLogEntry::PreventedFromMoving { monster_id, status } => {
+                let key = match status {
+                    MajorStatus::Sleep => "fast_asleep",
+                    MajorStatus::Freeze => "frozen_solid",
+                    MajorStatus::Paralysis => "fully_paralysed",
+                    _ => "cannot_move",
+                };
+                vec![Message::new(key).with("monster", Monster(monster_id))]
+            },
+            BattleLogEntry::MoveUsed { move_id } => vec![
+                Message::new("move_used").with("monster", Monster(move_id.owner_id)).with("move", Move(move_id))
+            ],
+            BattleLogEntry::MoveHadNoTargets { move_id } => vec![
+                Message::new("move_had_no_targets").with("monster", Monster(move_id.owner_id)).with("move", Move(move_id))
+            ],
+            BattleLogEntry::MoveFailed { move_id } => vec![Message::new("move_failed").with("move", Move(move_id))],
+            BattleLogEntry::MoveMissed { move_id, target_id } => vec![
+                Message::new("move_missed").with("monster", Monster(move_id.owner_id)).with("move", Move(move_id)).with("target", Monster(target_id))
+            ],
+            BattleLogEntry::MoveFailedToHit { move_id, target_id } => vec![
+                Message::new("move_failed_to_hit").with("move", Move(move_id)).with("target", Monster(target_id))
+            ],
+            BattleLogEntry::MoveHitMultipleTimes { move_id, number_of_hits } => vec![
+                Message::new("move_hit_multiple_times").with("move", Move(move_id)).with("number_of_hits", Number(number_of_hits as u16))
+            ],
+            BattleLogEntry::TargetImmune { move_id, target_id } => vec![
+                Message::new("target_immune").with("move", Move(move_id)).with("target", Monster(target_id))
+            ],
+            BattleLogEntry::DamageDealt { target_id, amount, remaining_health, effectiveness, is_critical_hit, .. } => {
+                // Damage that isn't dealt by a move is described by whatever dealt it.
+                let Some(effectiveness) = effectiveness else {
+                    return Vec::new();
+                };
+                let mut messages = Vec::with_capacity(4);
+                if is_critical_hit {
+                    messages.push(Message::new("critical_hit"));
+                }
+                messages.push(Message::new(match effectiveness {
+                    Effectiveness::NotVeryEffective => "not_very_effective",
+                    Effectiveness::Effective => "effective",
+                    Effectiveness::SuperEffective => "super_effective",
+                }));
+                messages.push(Message::new("damage_taken").with("monster", Monster(target_id)).with("amount", Number(amount)));
+                messages.push(Message::new("health_left").with("monster", Monster(target_id)).with("remaining_health", Number(remaining_health)));
+                messages
+            },
+            BattleLogEntry::HealthRestored { .. } => Vec::new(),
+            BattleLogEntry::Fainted { monster_id } => vec![Message::new("fainted").with("monster", Monster(monster_id))],
+            BattleLogEntry::SwitchedIn { monster_id, replaced_monster_id } => match replaced_monster_id {
+                Some(replaced_monster_id) => vec![
+                    Message::new("switched_out").with("monster", Monster(monster_id)).with("replaced_monster", Monster(replaced_monster_id))
+                ],
+                None => vec![Message::new("sent_in").with("monster", Monster(monster_id))],
+            },
+            // Abilities and items describe their own effects.
+            BattleLogEntry::AbilityActivated { .. } | BattleLogEntry::ItemUsed { .. } => Vec::new(),
+            BattleLogEntry::ItemRemoved { monster_id, item } => vec![
+                Message::new("item_removed").with("monster", Monster(monster_id)).with("item", Item(item))
+            ],
+            BattleLogEntry::ItemTransferred { giver_id, receiver_id, item } => vec![
+                Message::new("item_transferred").with("giver", Monster(giver_id)).with("receiver", Monster(receiver_id)).with("item", Item(item))
+            ],
+            BattleLogEntry::ItemsSwapped { first_monster_id, second_monster_id } => vec![
+                Message::new("items_swapped").with("monster", Monster(first_monster_id)).with("other_monster", Monster(second_monster_id))
+            ],
+            BattleLogEntry::CritStageRaised { monster_id } => vec![Message::new("crit_stage_raised").with("monster", Monster(monster_id))],
+            BattleLogEntry::StatChanged { monster_id, stat, direction, stages } => {
+                let key = match direction {
+                    StatChangeDirection::Raised => "stat_raised",
+                    StatChangeDirection::Lowered => "stat_lowered",
+                };
+                vec![Message::new(key).with("monster", Monster(monster_id)).with("stat", Stat(stat)).with("stages", Number(stages as u16))]
+            },
+            BattleLogEntry::StatChangeFailed { monster_id, stat, direction } => {
+                let key = match direction {
+                    StatChangeDirection::Raised => "stat_raise_failed",
+                    StatChangeDirection::Lowered => "stat_lower_failed",
+                };
+                vec![Message::new(key).with("monster", Monster(monster_id)).with("stat", Stat(stat))]
+            },
+            BattleLogEntry::StatusInflicted { monster_id, status } => vec![
+                Message::new("status_inflicted").with("monster", Monster(monster_id)).with("status", Status(status))
+            ],
+            BattleLogEntry::AlreadyHasStatus { monster_id, status } => vec![
+                Message::new("already_has_status").with("monster", Monster(monster_id)).with("status", Status(status))
+            ],
+            BattleLogEntry::NotAffected { monster_id } => vec![Message::new("not_affected").with("monster", Monster(monster_id))],
+            BattleLogEntry::HurtByStatus { monster_id, status } => {
+                let key = match status {
+                    MajorStatus::Burn => "hurt_by_burn",
+                    _ => "hurt_by_poison",
+                };
+                vec![Message::new(key).with("monster", Monster(monster_id)).with("status", Status(status))]
+            },
+            BattleLogEntry::StatusCured { monster_id, status } => {
+                let key = match status {
+                    MajorStatus::Sleep => "woke_up",
+                    MajorStatus::Freeze => "thawed_out",
+                    MajorStatus::Burn => "burn_healed",
+                    MajorStatus::Paralysis => "paralysis_cured",
+                    MajorStatus::Poison | MajorStatus::BadPoison => "poisoning_cured",
+                };
+                vec![Message::new(key).with("monster", Monster(monster_id)).with("status", Status(status))]
+            },
+            // Volatile statuses describe themselves when they start and end.
+            BattleLogEntry::VolatileStatusAdded { .. } | BattleLogEntry::VolatileStatusRemoved { .. } => Vec::new(),
+            BattleLogEntry::FieldEffectStarted { species } => vec![
+                Message::from_species_text(species.start_message()).with("field_effect", FieldEffect(species))
+            ],
+            BattleLogEntry::FieldEffectEnded { species } => vec![
+                Message::from_species_text(species.end_message()).with("field_effect", FieldEffect(species))
+            ],
+            BattleLogEntry::SideConditionAdded { team_id, species } => vec![
+                Message::from_species_text(species.start_message()).with("team", Team(team_id)).with("side_condition", SideCondition(species))
+            ],
+            BattleLogEntry::SideConditionRemoved { team_id, species } => vec![
+                Message::from_species_text(species.end_message()).with("team", Team(team_id)).with("side_condition", SideCondition(species))
+            ],
+            BattleLogEntry::EffectFailed => vec![Message::new("effect_failed")],
+            BattleLogEntry::SpeciesMessage { text, monster_id } => vec![Message::from_species_text(text).with("monster", Monster(monster_id))],
+            BattleLogEntry::PositionLeftEmpty { field_position } => vec![
+                Message::new("position_left_empty").with("position", Position(field_position)).with("team", Team(field_position.side()))
+            ],
+            BattleLogEntry::BattleEnded { winner } => {
+                let key = match winner {
+                    None => "battle_tied",
+                    Some(TeamID::Allies) => "ally_team_won",
+                    Some(TeamID::Opponents) => "opponent_team_won",
+                };
+                vec![
+                    Message::new(key),
+                    Message::new("blank_line"),
+                    Message::new("battle_ended"),
+                    Message::new("divider"),
+                    Message::new("blank_line"),
+                ]
+            },
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::{IndexMut, Index};
+use std::{fmt::Display, ops::{IndexMut, Index}};
 
 use monsim_utils::MaxSizedVec;
 
@@ -27,6 +27,7 @@ pub enum PartiallySpecifiedActionChoice {
 
 /// An action whose details have been fully specified.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FullySpecifiedActionChoice {
     Move { 
         move_id: MoveID,
@@ -48,6 +49,13 @@ impl FullySpecifiedActionChoice {
         }
     }
     
+    pub(crate) fn activation_order_mut(&mut self) -> &mut ActivationOrder {
+        match self {
+            FullySpecifiedActionChoice::Move { activation_order, .. } => activation_order,
+            FullySpecifiedActionChoice::SwitchOut { activation_order, .. } => activation_order,
+        }
+    }
+    
     pub(crate) fn actor_id(&self) -> MonsterID {
         match *self {
             FullySpecifiedActionChoice::Move { move_id, .. } => move_id.owner_id,
@@ -56,6 +64,62 @@ impl FullySpecifiedActionChoice {
     }
 }
 
+/// A problem with an action choice given to the simulator, found by `BattleState::validate_choices`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceError {
+    /// More than one action was chosen for the Monster.
+    DuplicateAction(MonsterID),
+    /// The Monster is not on the field, so it cannot act.
+    ActorNotActive(MonsterID),
+    /// The move is out of power points, or its owner doesn't have a move in that slot.
+    MoveNotAvailable(MoveID),
+    InvalidTargetPosition {
+        move_id: MoveID,
+        target_position: FieldPosition,
+    },
+    /// Moves that target `ANY` position need exactly one target.
+    WrongNumberOfTargets {
+        move_id: MoveID,
+        number_of_targets: usize,
+    },
+    /// The move needs at least one target, but none were chosen.
+    NoTargets(MoveID),
+    /// The priority the action was given is not the priority of the move or switch chosen.
+    WrongPriority {
+        actor_id: MonsterID,
+        priority: i8,
+        expected_priority: i8,
+    },
+    /// The benched Monster is fainted, already active, on the other team or already chosen to switch in.
+    InvalidSwitchIn {
+        active_monster_id: MonsterID,
+        benched_monster_id: MonsterID,
+    },
+}
+
+impl Display for ChoiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChoiceError::DuplicateAction(monster_id) => write!(f, "{} was given more than one action.", monster_id),
+            ChoiceError::ActorNotActive(monster_id) => write!(f, "{} is not on the field.", monster_id),
+            ChoiceError::MoveNotAvailable(move_id) => write!(f, "{}'s move {:?} cannot be used.", move_id.owner_id, move_id.move_number),
+            ChoiceError::InvalidTargetPosition { move_id, target_position } => write!(
+                f, "{}'s move {:?} cannot target {}.", move_id.owner_id, move_id.move_number, target_position
+            ),
+            ChoiceError::WrongNumberOfTargets { move_id, number_of_targets } => write!(
+                f, "{}'s move {:?} needs exactly one target, found {}.", move_id.owner_id, move_id.move_number, number_of_targets
+            ),
+            ChoiceError::NoTargets(move_id) => write!(f, "{}'s move {:?} needs at least one target.", move_id.owner_id, move_id.move_number),
+            ChoiceError::WrongPriority { actor_id, priority, expected_priority } => write!(
+                f, "{}'s action was given priority {}, but has priority {}.", actor_id, priority, expected_priority
+            ),
+            ChoiceError::InvalidSwitchIn { active_monster_id, benched_monster_id } => write!(
+                f, "{} cannot switch in for {}.", benched_monster_id, active_monster_id
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AvailableChoices {
     choices: MaxSizedVec<PartiallySpecifiedActionChoice, 5>,
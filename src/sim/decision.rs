@@ -0,0 +1,61 @@
+use monsim_utils::MaxSizedVec;
+
+use crate::MonsterID;
+
+use super::{choice::{AvailableChoices, FullySpecifiedActionChoice}, targetting::FieldPosition};
+
+/// Something the simulator is waiting on a player to decide before the battle can go on.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecisionRequest {
+    /// An action for `monster_id` to take in the coming turn.
+    TurnAction {
+        monster_id: MonsterID,
+        available_choices: AvailableChoices,
+    },
+    /// A benched Monster to fill `field_position`, which was left empty after the Monster there fainted.
+    ForcedSwitch {
+        field_position: FieldPosition,
+        switchable_benched_monster_ids: MaxSizedVec<MonsterID, 5>,
+    },
+    /// A benched Monster to switch in for `active_monster_id` in the middle of the turn, as with U-turn.
+    MidMoveSwitch {
+        active_monster_id: MonsterID,
+        switchable_benched_monster_ids: MaxSizedVec<MonsterID, 5>,
+    },
+}
+
+/// The answer to a `DecisionRequest`, given to `BattleSimulator::submit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    TurnAction(FullySpecifiedActionChoice),
+    ForcedSwitch {
+        field_position: FieldPosition,
+        benched_monster_id: MonsterID,
+    },
+    MidMoveSwitch {
+        active_monster_id: MonsterID,
+        benched_monster_id: MonsterID,
+    },
+}
+
+/// The simulator keeps only what identifies each request, the options offered are worked out when the
+/// requests are asked for, so that they take into account the decisions submitted in the meantime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum PendingDecision {
+    TurnAction(MonsterID),
+    ForcedSwitch(FieldPosition),
+    MidMoveSwitch(MonsterID),
+}
+
+impl Decision {
+    pub(crate) fn answers(&self, pending_decision: PendingDecision) -> bool {
+        match (*self, pending_decision) {
+            (Decision::TurnAction(action_choice), PendingDecision::TurnAction(monster_id)) => action_choice.actor_id() == monster_id,
+            (Decision::ForcedSwitch { field_position, .. }, PendingDecision::ForcedSwitch(pending_field_position)) => field_position == pending_field_position,
+            (Decision::MidMoveSwitch { active_monster_id, .. }, PendingDecision::MidMoveSwitch(monster_id)) => active_monster_id == monster_id,
+            _ => false,
+        }
+    }
+}
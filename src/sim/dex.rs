@@ -0,0 +1,279 @@
+use std::{error::Error, fmt::Display};
+
+use crate::sim::{
+    AbilitySpecies, EventHandlerDeck, FieldEffectSpecies, ItemSpecies, MonsterSpecies, MoveSpecies, RegisteredSpecies, SideConditionSpecies, SpeciesRegistry, VolatileStatusSpecies,
+};
+
+/**
+A runtime registry of every species known to a game, looked up by dex number or by name. Species
+are still defined as `const`s (or leaked from data files), the `Dex` only indexes them.
+
+```ignore
+let mut dex = Dex::new();
+dex.register(&Dandyleo)?;
+dex.register(&Tackle)?;
+assert_eq!(dex.monsters().find_by_name("dandyleo"), Some(&Dandyleo));
+```
+*/
+#[derive(Debug, Default, Clone)]
+pub struct Dex {
+    monsters: DexSection<MonsterSpecies>,
+    moves: DexSection<MoveSpecies>,
+    abilities: DexSection<AbilitySpecies>,
+    items: DexSection<ItemSpecies>,
+    volatile_statuses: DexSection<VolatileStatusSpecies>,
+    field_effects: DexSection<FieldEffectSpecies>,
+    side_conditions: DexSection<SideConditionSpecies>,
+}
+
+impl Dex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `species` to the section of the Dex for its kind. Fails if a species of the same kind
+    /// with the same dex number or name is already registered.
+    pub fn register<T: DexSpecies>(&mut self, species: &'static T) -> Result<(), DexError> {
+        T::section_mut(self).register(species)
+    }
+
+    /// Registers every species in `species`, stopping at the first failure.
+    pub fn register_all<T: DexSpecies>(&mut self, species: &[&'static T]) -> Result<(), DexError> {
+        species.iter().try_for_each(|species| self.register(*species))
+    }
+
+    #[inline(always)]
+    pub fn monsters(&self) -> &DexSection<MonsterSpecies> {
+        &self.monsters
+    }
+
+    #[inline(always)]
+    pub fn moves(&self) -> &DexSection<MoveSpecies> {
+        &self.moves
+    }
+
+    #[inline(always)]
+    pub fn abilities(&self) -> &DexSection<AbilitySpecies> {
+        &self.abilities
+    }
+
+    #[inline(always)]
+    pub fn items(&self) -> &DexSection<ItemSpecies> {
+        &self.items
+    }
+
+    #[inline(always)]
+    pub fn volatile_statuses(&self) -> &DexSection<VolatileStatusSpecies> {
+        &self.volatile_statuses
+    }
+
+    #[inline(always)]
+    pub fn field_effects(&self) -> &DexSection<FieldEffectSpecies> {
+        &self.field_effects
+    }
+
+    #[inline(always)]
+    pub fn side_conditions(&self) -> &DexSection<SideConditionSpecies> {
+        &self.side_conditions
+    }
+}
+
+impl SpeciesRegistry for Dex {
+    fn monster_species(&self, dex_number: u16) -> Option<&'static MonsterSpecies> {
+        self.monsters.find_by_dex_number(dex_number)
+    }
+
+    fn move_species(&self, dex_number: u16) -> Option<&'static MoveSpecies> {
+        self.moves.find_by_dex_number(dex_number)
+    }
+
+    fn ability_species(&self, dex_number: u16) -> Option<&'static AbilitySpecies> {
+        self.abilities.find_by_dex_number(dex_number)
+    }
+
+    fn item_species(&self, dex_number: u16) -> Option<&'static ItemSpecies> {
+        self.items.find_by_dex_number(dex_number)
+    }
+
+    fn volatile_status_species(&self, dex_number: u16) -> Option<&'static VolatileStatusSpecies> {
+        self.volatile_statuses.find_by_dex_number(dex_number)
+    }
+
+    fn field_effect_species(&self, dex_number: u16) -> Option<&'static FieldEffectSpecies> {
+        self.field_effects.find_by_dex_number(dex_number)
+    }
+
+    fn side_condition_species(&self, dex_number: u16) -> Option<&'static SideConditionSpecies> {
+        self.side_conditions.find_by_dex_number(dex_number)
+    }
+}
+
+/// A species that can be registered in a `Dex`.
+pub trait DexSpecies: RegisteredSpecies + Sized {
+    fn name(&self) -> &'static str;
+
+    fn event_handlers(&self) -> EventHandlerDeck;
+
+    fn section_mut(dex: &mut Dex) -> &mut DexSection<Self>;
+}
+
+macro_rules! impl_dex_species {
+    ($($species: ty, $section: ident;)*) => {
+        $(
+            impl DexSpecies for $species {
+                fn name(&self) -> &'static str {
+                    <$species>::name(self)
+                }
+
+                fn event_handlers(&self) -> EventHandlerDeck {
+                    <$species>::event_handlers(self)
+                }
+
+                fn section_mut(dex: &mut Dex) -> &mut DexSection<Self> {
+                    &mut dex.$section
+                }
+            }
+        )*
+    };
+}
+
+impl_dex_species!(
+    MonsterSpecies, monsters;
+    MoveSpecies, moves;
+    AbilitySpecies, abilities;
+    ItemSpecies, items;
+    VolatileStatusSpecies, volatile_statuses;
+    FieldEffectSpecies, field_effects;
+    SideConditionSpecies, side_conditions;
+);
+
+/// All the registered species of one kind, kept sorted by dex number.
+#[derive(Debug)]
+pub struct DexSection<T: 'static> {
+    species: Vec<&'static T>,
+}
+
+impl<T> Default for DexSection<T> {
+    fn default() -> Self {
+        Self { species: Vec::new() }
+    }
+}
+
+impl<T> Clone for DexSection<T> {
+    fn clone(&self) -> Self {
+        Self { species: self.species.clone() }
+    }
+}
+
+impl<T: DexSpecies> DexSection<T> {
+    fn register(&mut self, new_species: &'static T) -> Result<(), DexError> {
+        let insertion_index = match self.species.binary_search_by_key(&new_species.dex_number(), |species| species.dex_number()) {
+            Ok(index) => {
+                return Err(DexError::DuplicateDexNumber {
+                    kind: T::KIND,
+                    dex_number: new_species.dex_number(),
+                    registered_name: self.species[index].name(),
+                    new_name: new_species.name(),
+                });
+            }
+            Err(index) => index,
+        };
+        if let Some(registered_species) = self.find_by_name(new_species.name()) {
+            return Err(DexError::DuplicateName {
+                kind: T::KIND,
+                name: new_species.name(),
+                registered_dex_number: registered_species.dex_number(),
+                new_dex_number: new_species.dex_number(),
+            });
+        }
+        self.species.insert(insertion_index, new_species);
+        Ok(())
+    }
+
+    pub fn find_by_dex_number(&self, dex_number: u16) -> Option<&'static T> {
+        self.species
+            .binary_search_by_key(&dex_number, |species| species.dex_number())
+            .ok()
+            .map(|index| self.species[index])
+    }
+
+    /// Ignores case, spaces and punctuation, so "Flash Fire", "flashfire" and "FLASH-FIRE" are all the same name.
+    pub fn find_by_name(&self, name: &str) -> Option<&'static T> {
+        let name = normalise_name(name);
+        self.species.iter().copied().find(|species| normalise_name(species.name()) == name)
+    }
+
+    /// Returns the species whose name is closest to `name`, allowing roughly one typo per three letters.
+    /// Useful for resolving names typed by a user.
+    pub fn find_closest_by_name(&self, name: &str) -> Option<&'static T> {
+        let name = normalise_name(name);
+        let max_distance = (name.chars().count() / 3).max(1);
+        self.species
+            .iter()
+            .copied()
+            .map(|species| (species, edit_distance(&normalise_name(species.name()), &name)))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(species, _)| species)
+    }
+
+    /// Iterates over the species in order of dex number.
+    pub fn iter(&self) -> impl Iterator<Item = &'static T> + '_ {
+        self.species.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.species.is_empty()
+    }
+}
+
+fn normalise_name(name: &str) -> String {
+    name.chars()
+        .filter(|character| character.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DexError {
+    DuplicateDexNumber { kind: &'static str, dex_number: u16, registered_name: &'static str, new_name: &'static str },
+    DuplicateName { kind: &'static str, name: &'static str, registered_dex_number: u16, new_dex_number: u16 },
+}
+
+impl Error for DexError {}
+
+impl Display for DexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DexError::DuplicateDexNumber { kind, dex_number, registered_name, new_name } => write!(
+                f,
+                "{kind} #{dex_number:03} is already registered as {registered_name}, so {new_name} cannot use the same dex number."
+            ),
+            DexError::DuplicateName { kind, name, registered_dex_number, new_dex_number } => write!(
+                f,
+                "A {kind} named {name} is already registered as #{registered_dex_number:03}, so #{new_dex_number:03} cannot use the same name."
+            ),
+        }
+    }
+}
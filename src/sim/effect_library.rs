@@ -0,0 +1,219 @@
+use crate::sim::{not, BattleSimulator, Effect, MajorStatus, MonsterID, MoveHitContext, Outcome, Rng, Stat};
+use crate::sim::{CureStatus, DealDefaultDamage, InflictStatus, LowerStat, RaiseCritStage, RaiseStat, RestoreHealth};
+
+/**
+A composable description of what a move does when it hits, built from `BuiltInEffect`s. It is
+interpreted by the simulator, can be written as a `const`, and can be (de)serialized, so moves defined
+in data files get the same behaviour as moves written in Rust. Moves written in Rust can also resolve
+their own `Effect` with `Custom`, which data files cannot express.
+
+In data files a sequence is an array, a built-in effect is a table with an `effect` key, and the
+other forms are tables with `chance` or `if` keys:
+```toml
+effects = [
+    { effect = "DealDefaultDamage" },
+    { chance = 30, then = { effect = "LowerStat", target = "Target", stat = "Speed", stages = 1 } },
+    { effect = "RestoreHealthFromDamageDealt", target = "User", percent = 50 },
+]
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(untagged))]
+pub enum EffectDescription {
+    BuiltIn(BuiltInEffect),
+    /// Resolves each effect in order. Succeeds if any of them succeeded.
+    Sequence(
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "leaked::deserialize_slice"))]
+        &'static [EffectDescription]
+    ),
+    /// Resolves `then` with a `chance` percent probability, and fails otherwise.
+    Chance {
+        chance: u16,
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "leaked::deserialize_ref"))]
+        then: &'static EffectDescription,
+    },
+    /// Resolves `if`, then `then` if it succeeded or `else` if it failed.
+    Conditional {
+        #[cfg_attr(feature = "serde", serde(rename = "if", deserialize_with = "leaked::deserialize_ref"))]
+        condition: &'static EffectDescription,
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "leaked::deserialize_ref"))]
+        then: &'static EffectDescription,
+        #[cfg_attr(feature = "serde", serde(rename = "else", default, deserialize_with = "leaked::deserialize_option_ref"))]
+        otherwise: Option<&'static EffectDescription>,
+    },
+    /// Resolves an effect written in Rust.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Effect<Outcome, MoveHitContext>),
+}
+
+impl EffectDescription {
+    /// Resolves the description as part of a move hitting `context.target_id`.
+    pub fn resolve(&self, sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveHitContext) -> Outcome {
+        self.resolve_with_state(sim, effector_id, context, &mut ResolutionState::default())
+    }
+
+    /// Whether `BuiltInEffect::DealDefaultDamage` appears anywhere in the description.
+    pub fn deals_default_damage(&self) -> bool {
+        match *self {
+            EffectDescription::BuiltIn(effect) => effect == BuiltInEffect::DealDefaultDamage,
+            EffectDescription::Sequence(effects) => effects.iter().any(EffectDescription::deals_default_damage),
+            EffectDescription::Chance { then, .. } => then.deals_default_damage(),
+            EffectDescription::Conditional { condition, then, otherwise } => {
+                condition.deals_default_damage()
+                    || then.deals_default_damage()
+                    || otherwise.is_some_and(EffectDescription::deals_default_damage)
+            },
+            // There is no way to tell what a custom effect does.
+            EffectDescription::Custom(_) => false,
+        }
+    }
+
+    fn resolve_with_state(&self, sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveHitContext, state: &mut ResolutionState) -> Outcome {
+        match *self {
+            EffectDescription::BuiltIn(effect) => effect.resolve_with_state(sim, effector_id, context, state),
+            EffectDescription::Sequence(effects) => {
+                effects.iter().fold(Outcome::Failure, |outcome, effect| {
+                    let effect_outcome = effect.resolve_with_state(sim, effector_id, context, state);
+                    Outcome::from(outcome.succeeded() || effect_outcome.succeeded())
+                })
+            },
+            EffectDescription::Chance { chance, then } => {
+                if sim.prng().chance(chance, 100) {
+                    then.resolve_with_state(sim, effector_id, context, state)
+                } else {
+                    Outcome::Failure
+                }
+            },
+            EffectDescription::Conditional { condition, then, otherwise } => {
+                if condition.resolve_with_state(sim, effector_id, context, state).succeeded() {
+                    then.resolve_with_state(sim, effector_id, context, state)
+                } else {
+                    otherwise.map_or(Outcome::Failure, |otherwise| otherwise.resolve_with_state(sim, effector_id, context, state))
+                }
+            },
+            EffectDescription::Custom(effect) => effect(sim, effector_id, context),
+        }
+    }
+}
+
+/// What an `EffectDescription` remembers while it is being resolved.
+#[derive(Debug, Default)]
+struct ResolutionState {
+    damage_dealt: u16,
+}
+
+/// The library of named, parameterised effects that `EffectDescription`s are built from. Each one
+/// wraps a public `Effect` from `effects.rs`, and is resolved once for each Monster its target selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "effect"))]
+pub enum BuiltInEffect {
+    DealDefaultDamage,
+    RaiseStat { target: EffectTarget, stat: Stat, stages: u8 },
+    LowerStat { target: EffectTarget, stat: Stat, stages: u8 },
+    RaiseCritStage { target: EffectTarget, stages: u8 },
+    InflictStatus { target: EffectTarget, status: MajorStatus },
+    CureStatus { target: EffectTarget },
+    /// Restores `percent` of the affected Monster's max HP.
+    RestoreHealth { target: EffectTarget, percent: u16 },
+    /// Restores `percent` of the damage dealt earlier in the same `EffectDescription`, as with draining moves.
+    RestoreHealthFromDamageDealt { target: EffectTarget, percent: u16 },
+}
+
+/// Which Monsters a `BuiltInEffect` used by a move affects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EffectTarget {
+    /// The user of the move.
+    User,
+    /// The Monster being hit by the move.
+    Target,
+    /// The user's active allies that haven't fainted, not including the user.
+    UserAllies,
+}
+
+impl EffectTarget {
+    pub fn monster_ids(&self, sim: &BattleSimulator, context: MoveHitContext) -> Vec<MonsterID> {
+        match self {
+            EffectTarget::User => vec![context.move_user_id],
+            EffectTarget::Target => vec![context.target_id],
+            EffectTarget::UserAllies => sim.battle
+                .active_monsters()
+                .filter(|monster| monster.id != context.move_user_id && sim.battle.are_allies(monster.id, context.move_user_id) && not!(monster.is_fainted()))
+                .map(|monster| monster.id)
+                .collect(),
+        }
+    }
+}
+
+impl BuiltInEffect {
+    /// Resolves the effect as part of a move hitting `context.target_id`. Succeeds if it succeeded
+    /// for any of the affected Monsters. Dealing damage succeeds if the target was damaged.
+    pub fn resolve(&self, sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveHitContext) -> Outcome {
+        self.resolve_with_state(sim, effector_id, context, &mut ResolutionState::default())
+    }
+
+    fn resolve_with_state(&self, sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveHitContext, state: &mut ResolutionState) -> Outcome {
+        if let BuiltInEffect::DealDefaultDamage = self {
+            let health_before_hit = sim.battle.monster(context.target_id).current_health();
+            let outcome = DealDefaultDamage(sim, effector_id, context);
+            state.damage_dealt += health_before_hit.saturating_sub(sim.battle.monster(context.target_id).current_health());
+            return outcome;
+        }
+
+        let mut outcome = Outcome::Failure;
+        for affected_monster_id in self.target().map_or(Vec::new(), |target| target.monster_ids(sim, context)) {
+            let affected_monster_outcome = match *self {
+                BuiltInEffect::DealDefaultDamage => unreachable!("Damage is dealt before selecting targets."),
+                BuiltInEffect::RaiseStat { stat, stages, .. } => RaiseStat(sim, effector_id, (affected_monster_id, stat, stages)),
+                BuiltInEffect::LowerStat { stat, stages, .. } => LowerStat(sim, effector_id, (affected_monster_id, stat, stages)),
+                BuiltInEffect::RaiseCritStage { stages, .. } => RaiseCritStage(sim, effector_id, (affected_monster_id, stages)),
+                BuiltInEffect::InflictStatus { status, .. } => InflictStatus(sim, effector_id, (affected_monster_id, status)),
+                BuiltInEffect::CureStatus { .. } => CureStatus(sim, effector_id, affected_monster_id),
+                BuiltInEffect::RestoreHealth { percent, .. } => {
+                    let amount = (sim.battle.monster(affected_monster_id).max_health() as u32 * percent as u32 / 100) as u16;
+                    Outcome::from(RestoreHealth(sim, effector_id, (affected_monster_id, amount)) > 0)
+                },
+                BuiltInEffect::RestoreHealthFromDamageDealt { percent, .. } => {
+                    let amount = (state.damage_dealt as u32 * percent as u32 / 100) as u16;
+                    Outcome::from(RestoreHealth(sim, effector_id, (affected_monster_id, amount)) > 0)
+                },
+            };
+            outcome = Outcome::from(outcome.succeeded() || affected_monster_outcome.succeeded());
+        }
+        outcome
+    }
+
+    fn target(&self) -> Option<EffectTarget> {
+        match *self {
+            BuiltInEffect::DealDefaultDamage => None,
+            BuiltInEffect::RaiseStat { target, .. }
+            | BuiltInEffect::LowerStat { target, .. }
+            | BuiltInEffect::RaiseCritStage { target, .. }
+            | BuiltInEffect::InflictStatus { target, .. }
+            | BuiltInEffect::CureStatus { target }
+            | BuiltInEffect::RestoreHealth { target, .. }
+            | BuiltInEffect::RestoreHealthFromDamageDealt { target, .. } => Some(target),
+        }
+    }
+}
+
+/// Effect descriptions refer to each other with `&'static` references so that they can be written as
+/// `const`s. Descriptions loaded at runtime are leaked to match, like loaded species.
+#[cfg(feature = "serde")]
+mod leaked {
+    use serde::{Deserialize, Deserializer};
+
+    use super::EffectDescription;
+
+    pub(super) fn deserialize_slice<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static [EffectDescription], D::Error> {
+        Ok(Vec::deserialize(deserializer)?.leak())
+    }
+
+    pub(super) fn deserialize_ref<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static EffectDescription, D::Error> {
+        Ok(Box::leak(Box::new(EffectDescription::deserialize(deserializer)?)))
+    }
+
+    pub(super) fn deserialize_option_ref<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<&'static EffectDescription>, D::Error> {
+        Ok(Option::<EffectDescription>::deserialize(deserializer)?.map(|effect| &*Box::leak(Box::new(effect))))
+    }
+}
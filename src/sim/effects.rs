@@ -6,7 +6,7 @@ use monsim_macros::{abl, mon, mov};
 
 use crate::matchup;
 
-use self::targetting::BoardPosition;
+use self::{decision::PendingDecision, targetting::BoardPosition};
 
 use super::event_dex::*;
 use super::*;
@@ -19,9 +19,23 @@ pub(super) type EffectFunction<R,C> = fn(/* simulator */ &mut BattleSimulator, /
 /// `C`: Any information necessary for the resolution of the effect, provided 
 /// directly, such as the user of the move, the move used and the target 
 /// in case of a move's effect. 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy)]
 pub struct Effect<R, C>(EffectFunction<R, C>);
 
+impl<R, C> PartialEq for Effect<R, C> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::fn_addr_eq(self.0, other.0)
+    }
+}
+
+impl<R, C> Eq for Effect<R, C> {}
+
+impl<R, C> std::fmt::Debug for Effect<R, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Effect({:p})", self.0)
+    }
+}
+
 impl<R, C> Deref for Effect<R, C> {
     type Target = EffectFunction<R, C>;
 
@@ -49,23 +63,21 @@ pub(crate) const UseMove: Effect<Nothing, MoveUseContext> = Effect(use_move);
 fn use_move(sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveUseContext) {
     let MoveUseContext { move_user_id, move_used_id, target_ids } = context;
     assert!(mov![move_used_id].current_power_points > 0, "A move was used that had zero power points");
+
+    if is_prevented_from_moving_by_status(sim, move_user_id) {
+        return;
+    }
     
-    sim.push_message(format![
-        "{attacker} used {_move}",
-        attacker = mon![move_user_id].name(),
-        _move = mov![move_used_id].name()
-    ]);
+    sim.log(BattleLogEntry::MoveUsed { move_id: move_used_id });
     
     // There are no remaining targets for this move. They fainted before the move was used.
     if target_ids.is_empty() {
-        sim.push_message(
-            format!["{}'s {} has no targets...", mon!(move_user_id).name(), mov!(move_used_id).name()]
-        );
+        sim.log(BattleLogEntry::MoveHadNoTargets { move_id: move_used_id });
         return;
     }
     
     if sim.trigger_try_event(OnTryMove, move_user_id, context).failed() {
-        sim.push_message("The move failed!");
+        sim.log(BattleLogEntry::MoveFailed { move_id: move_used_id });
         return;
     }
 
@@ -78,6 +90,13 @@ fn use_move(sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveUseC
     */
     for target_id in target_ids {
         let subcontext = MoveHitContext { move_user_id, move_used_id, target_id };
+
+        if does_move_miss(sim, subcontext) {
+            sim.log(BattleLogEntry::MoveMissed { move_id: move_used_id, target_id });
+            sim.trigger_event(OnMoveMissed, move_user_id, subcontext, NOTHING, None);
+            continue;
+        }
+
         let mut actual_number_of_hits = 0;
         for _ in {
             match mov![move_used_id].hits_per_target() {
@@ -89,12 +108,16 @@ fn use_move(sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveUseC
                 },
             }
         } {
-            mov![move_used_id].on_hit_effect()(&mut *sim, effector_id, subcontext);
+            // A hit that was blocked, or that the target was immune to, doesn't connect and ends the move for this target.
+            if mov![move_used_id].on_hit_effect().resolve(sim, effector_id, subcontext).failed() {
+                break;
+            }
+            sim.trigger_event(OnHit, move_user_id, subcontext, NOTHING, None);
             actual_number_of_hits += 1;
         } 
 
         if actual_number_of_hits > 1 {
-            sim.push_message(format!["The move hit {} time(s)", actual_number_of_hits]);
+            sim.log(BattleLogEntry::MoveHitMultipleTimes { move_id: move_used_id, number_of_hits: actual_number_of_hits });
         }
     }
     
@@ -111,30 +134,110 @@ fn use_move(sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveUseC
     sim.trigger_event(OnMoveUsed, move_user_id, context, NOTHING, None);
 }
 
+/// Resolves the status of `monster_id` when it tries to move, returning whether the status stops it from moving.
+fn is_prevented_from_moving_by_status(sim: &mut BattleSimulator, monster_id: MonsterID) -> bool {
+    match mon![monster_id].status() {
+        Some(MajorStatus::Sleep) => {
+            if mon![monster_id].status_turn_counter == 0 {
+                _ = CureStatus(sim, monster_id, monster_id);
+                false
+            } else {
+                mon![mut monster_id].status_turn_counter -= 1;
+                sim.log(BattleLogEntry::PreventedFromMoving { monster_id, status: MajorStatus::Sleep });
+                true
+            }
+        },
+        Some(MajorStatus::Freeze) => {
+            if sim.prng().chance(20, 100) {
+                _ = CureStatus(sim, monster_id, monster_id);
+                false
+            } else {
+                sim.log(BattleLogEntry::PreventedFromMoving { monster_id, status: MajorStatus::Freeze });
+                true
+            }
+        },
+        Some(MajorStatus::Paralysis) => {
+            let is_fully_paralysed = sim.prng().chance(25, 100);
+            if is_fully_paralysed {
+                sim.log(BattleLogEntry::PreventedFromMoving { monster_id, status: MajorStatus::Paralysis });
+            }
+            is_fully_paralysed
+        },
+        _ => false,
+    }
+}
+
+/// Rolls whether the move in `context` misses its target, taking into account the move's base accuracy,
+/// the move user's accuracy stage, the target's evasion stage and any `OnModifyAccuracy` handlers.
+fn does_move_miss(sim: &mut BattleSimulator, context: MoveHitContext) -> bool {
+    let MoveHitContext { move_user_id, move_used_id, target_id } = context;
+    
+    // Moves that never miss and moves used on oneself skip the accuracy check.
+    let Some(base_accuracy) = mov![move_used_id].base_accuracy() else {
+        return false;
+    };
+    if move_user_id == target_id {
+        return false;
+    }
+
+    let accuracy_stage = (mon![move_user_id].stat_modifier(Stat::Accuracy) - mon![target_id].stat_modifier(Stat::Evasion)).clamp(-6, 6);
+    let (numerator, denominator) = accuracy_stage_multiplier(accuracy_stage);
+    let accuracy = Percent((base_accuracy as u32 * numerator as u32 / denominator as u32).min(u16::MAX as u32) as u16);
+    let accuracy = sim.trigger_modify_event(OnModifyAccuracy, move_user_id, context, accuracy);
+
+    let roll = sim.generate_random_number_in_range_inclusive(1..=100);
+    roll > accuracy.0
+}
+
 pub(crate) const PerformSwitchOut: Effect<Nothing, SwitchContext> = Effect(perform_switch_out);
 
-fn perform_switch_out(sim: &mut BattleSimulator, effector_id: MonsterID, context: SwitchContext) {
+fn perform_switch_out(sim: &mut BattleSimulator, _effector_id: MonsterID, context: SwitchContext) {
     let SwitchContext { active_monster_id, benched_monster_id } = context;
 
     // Swap board positions of the two Monsters. (We just assume benched_monster_id corresponds to a benched monster at this point).
     mon![mut benched_monster_id].board_position = mon![active_monster_id].board_position;
     mon![mut active_monster_id].board_position = BoardPosition::Bench; 
+    // Volatile statuses don't persist when the Monster leaves the field.
+    mon![mut active_monster_id].volatile_statuses.clear();
+    // Neither do stat stages.
+    mon![mut active_monster_id].stat_modifiers = StatModifierSet::default();
+    mon![mut active_monster_id].crit_stage = 0;
     
-    sim.push_message(format![
-        "{} switched out! Go {}!", 
-        mon![active_monster_id].name(),
-        mon![benched_monster_id].name()
-    ]);
+    sim.log(BattleLogEntry::SwitchedIn { monster_id: benched_monster_id, replaced_monster_id: Some(active_monster_id) });
+
+    sim.trigger_event(OnSwitchIn, benched_monster_id, SwitchInContext::new(benched_monster_id), NOTHING, None);
+}
+
+/// The simulator simulates the damage dealt by the status of the monster `Context` at the end of a turn.
+pub(crate) const DealStatusDamage: Effect<Nothing, MonsterID> = Effect(deal_status_damage);
+
+fn deal_status_damage(sim: &mut BattleSimulator, effector_id: MonsterID, monster_id: MonsterID) {
+    let max_health = mon![monster_id].max_health();
+    let damage = match mon![monster_id].status() {
+        Some(MajorStatus::Burn) => max_health / 16,
+        Some(MajorStatus::Poison) => max_health / 8,
+        Some(MajorStatus::BadPoison) => {
+            // Bad poison stops getting worse once it deals 15/16 of the Monster's max HP.
+            let status_turn_counter = (mon![monster_id].status_turn_counter + 1).min(15);
+            mon![mut monster_id].status_turn_counter = status_turn_counter;
+            max_health * status_turn_counter as u16 / 16
+        },
+        _ => return,
+    };
+    // Residual damage always deals at least 1 HP of damage.
+    let damage = damage.max(1);
+    let status = mon![monster_id].status().expect("We returned early if there was no status.");
+    sim.log(BattleLogEntry::HurtByStatus { monster_id, status });
+    _ = DealDirectDamage(sim, effector_id, (monster_id, damage));
 }
 
 pub(crate) const ReplaceFaintedMonster: Effect<Nothing, (MonsterID, FieldPosition)> = Effect(replace_fainted_monster);
 
-fn replace_fainted_monster(sim: &mut BattleSimulator, effector_id: MonsterID, (benched_monster_id, field_position): (MonsterID, FieldPosition)) {
+fn replace_fainted_monster(sim: &mut BattleSimulator, _effector_id: MonsterID, (benched_monster_id, field_position): (MonsterID, FieldPosition)) {
     mon![mut benched_monster_id].board_position = BoardPosition::Field(field_position);
-    sim.push_message(format![
-        "Go {}!",
-        mon![benched_monster_id].name()
-    ]);
+    sim.log(BattleLogEntry::SwitchedIn { monster_id: benched_monster_id, replaced_monster_id: None });
+
+    sim.trigger_event(OnSwitchIn, benched_monster_id, SwitchInContext::new(benched_monster_id), NOTHING, None);
 }
 
 // public `Effects` usable by users of the crate.
@@ -144,35 +247,49 @@ fn replace_fainted_monster(sim: &mut BattleSimulator, effector_id: MonsterID, (b
 /// 
 /// This is done by calculating the damage first using the formula then calling `DealDirectDamage`
 /// with the resulting damage.
-pub const DealDefaultDamage: Effect<Nothing, MoveHitContext> = Effect(deal_default_damage);
+/// 
+/// Fails if an `OnTryMoveHit` handler blocks the hit or the target is immune. A missed target is never hit,
+/// because `UseMove` rolls accuracy before resolving the move's effects.
+pub const DealDefaultDamage: Effect<Outcome, MoveHitContext> = Effect(deal_default_damage);
 
-fn deal_default_damage(sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveHitContext) {
+#[must_use]
+fn deal_default_damage(sim: &mut BattleSimulator, effector_id: MonsterID, context: MoveHitContext) -> Outcome {
     let MoveHitContext { move_user_id: attacker_id, move_used_id, target_id: defender_id } = context;
 
     if sim.trigger_try_event(OnTryMoveHit, attacker_id, context).failed() {
-        sim.push_message(format!["The move failed to hit {}!", mon![defender_id].name()]);
-        return;
+        sim.log(BattleLogEntry::MoveFailedToHit { move_id: move_used_id, target_id: defender_id });
+        return Outcome::Failure;
     }
 
     let level = mon![attacker_id].level;
     let move_power = mov![move_used_id].base_power();
+    let move_power = sim.trigger_modify_event(OnModifyBasePower, attacker_id, context, move_power);
 
-    let (attackers_attacking_stat, defenders_defense_stat) = match mov![move_used_id].category() {
-        MoveCategory::Physical => {
-            (
-                mon![attacker_id].stat(Stat::PhysicalAttack),
-                mon![defender_id].stat(Stat::PhysicalDefense)
-            )
-        }
-        MoveCategory::Special => {
-            (
-                mon![attacker_id].stat(Stat::SpecialAttack),
-                mon![defender_id].stat(Stat::SpecialDefense)
-            )
-        }
+    let (attacking_stat, defending_stat) = match mov![move_used_id].category() {
+        MoveCategory::Physical => (Stat::PhysicalAttack, Stat::PhysicalDefense),
+        MoveCategory::Special => (Stat::SpecialAttack, Stat::SpecialDefense),
         _ => unreachable!("Expected physical or special move."),
     };
 
+    let is_critical_hit = does_move_crit(sim, context);
+
+    let (attackers_attacking_stat, defenders_defense_stat) = if is_critical_hit {
+        // Critical hits ignore the attacker's unfavourable stat stages and the defender's favourable ones.
+        let attacking_stage = mon![attacker_id].stat_modifier(attacking_stat).max(0);
+        let defending_stage = mon![defender_id].stat_modifier(defending_stat).min(0);
+        (
+            sim.effective_stat_at_stage(attacker_id, attacking_stat, attacking_stage),
+            sim.effective_stat_at_stage(defender_id, defending_stat, defending_stage)
+        )
+    } else {
+        (
+            sim.effective_stat(attacker_id, attacking_stat),
+            sim.effective_stat(defender_id, defending_stat)
+        )
+    };
+    let attackers_attacking_stat = sim.trigger_modify_event(OnModifyAttackStat, attacker_id, context, attackers_attacking_stat);
+    let defenders_defense_stat = sim.trigger_modify_event(OnModifyDefenseStat, attacker_id, context, defenders_defense_stat);
+
     let random_multiplier = sim.generate_random_number_in_range_inclusive(85..=100);
     let random_multiplier = ClampedPercent::from(random_multiplier);
 
@@ -180,6 +297,7 @@ fn deal_default_damage(sim: &mut BattleSimulator, effector_id: MonsterID, contex
         let move_type = mov![move_used_id].type_();
         if mon![attacker_id].is_type(move_type) { Percent(125) } else { Percent(100) }
     };
+    let stab_multiplier = sim.trigger_modify_event(OnModifyStab, attacker_id, context, stab_multiplier);
 
     let move_type = mov![move_used_id].type_();
     let target_primary_type = mon![defender_id].species.primary_type();
@@ -190,48 +308,53 @@ fn deal_default_damage(sim: &mut BattleSimulator, effector_id: MonsterID, contex
     } else {
         matchup!(move_type against target_primary_type)
     };
+    let type_matchup_multiplier = sim.trigger_modify_event(OnModifyTypeEffectiveness, attacker_id, context, type_matchup_multiplier);
 
     // If the opponent is immune, damage calculation is skipped.
     if type_matchup_multiplier.is_matchup_ineffective() {
-        sim.push_message("It was ineffective...");
-        return;
+        sim.log(BattleLogEntry::TargetImmune { move_id: move_used_id, target_id: defender_id });
+        return Outcome::Failure;
     }
 
     // The (WIP) bona-fide damage formula.
-    let mut damage = (2 * level) / 5;
-    damage += 2;
-    damage *= move_power;
-    damage = (damage as f64 * (attackers_attacking_stat as f64 / defenders_defense_stat as f64)) as u16;
+    let level_factor = (2 * level) / 5 + 2;
+    // Widened so that a high base power can't overflow before the stats scale it down.
+    let mut damage = (level_factor as f64 * move_power as f64 * (attackers_attacking_stat as f64 / defenders_defense_stat as f64)) as u16;
     damage /= 50;
     damage += 2;
+    if is_critical_hit {
+        damage = damage.saturating_mul(3) / 2;
+    }
     damage = (damage as f64 * random_multiplier) as u16;
     damage = (damage as f64 * stab_multiplier) as u16;
     damage = (damage as f64 * type_matchup_multiplier) as u16;
-    // TODO: Introduce more damage multipliers as we implement them.
+    // Burned Monsters deal half damage with physical moves.
+    if mon![attacker_id].has_status(MajorStatus::Burn) && mov![move_used_id].category() == MoveCategory::Physical {
+        damage /= 2;
+    }
+    // Weather, terrain, screens, items and abilities can modify the damage of the move.
+    let damage = sim.trigger_modify_event(OnModifyFinalDamage, attacker_id, context, damage);
 
     // Do the calculated damage to the target
-    DealDirectDamage(sim, effector_id, (defender_id, damage));
-
-    let type_effectiveness = match type_matchup_multiplier {
-        Percent(25) | Percent(50) => "not very effective",
-        Percent(100) => "effective",
-        Percent(200) | Percent(400) => "super effective",
-        value => {
-            let type_multiplier_as_float = value.0 as f64 / 100.0f64;
-            unreachable!("Type Effectiveness Multiplier is unexpectedly {type_multiplier_as_float}")
-        }
-    };
-    sim.push_message(format!["It was {type_effectiveness}!"]);
-    sim.push_message(format![
-        "{} took {damage} damage!", 
-        mon![defender_id].name()
-    ]);
-    sim.push_message(format![
-        "{} has {num_hp} health left.",
-        mon![defender_id].name(),
-        num_hp = mon![defender_id].current_health
-    ]);  
+    let move_damage = MoveDamage { move_used_id, effectiveness: Effectiveness::from_multiplier(type_matchup_multiplier), is_critical_hit };
+    deal_damage(sim, effector_id, defender_id, damage, Some(move_damage));
+
+    if is_critical_hit {
+        sim.trigger_event(OnCriticalHit, attacker_id, context, NOTHING, None);
+    }
+    Outcome::Success
+}
+
+/// The chance of landing a critical hit at each critical hit stage, out of 24. Stages above 3 always crit.
+const CRIT_CHANCES_OUT_OF_24: [u16; 4] = [1, 3, 12, 24];
 
+/// Rolls for whether the move in `context` lands a critical hit, based on the move's crit ratio, the
+/// user's critical hit stage and any modification by `OnModifyCritRatio` handlers.
+fn does_move_crit(sim: &mut BattleSimulator, context: MoveHitContext) -> bool {
+    let MoveHitContext { move_user_id, move_used_id, .. } = context;
+    let crit_stage = mov![move_used_id].crit_ratio().saturating_add(mon![move_user_id].crit_stage());
+    let crit_stage = sim.trigger_modify_event(OnModifyCritRatio, move_user_id, context, crit_stage).min(3);
+    sim.prng().chance(CRIT_CHANCES_OUT_OF_24[crit_stage as usize], 24)
 }
 
 /// The simulator simulates dealing damage equalling `Context.1` to the target `Context.0`.
@@ -241,18 +364,73 @@ pub const DealDirectDamage: Effect<u16, (MonsterID, u16)> = Effect(deal_direct_d
 
 #[must_use]
 fn deal_direct_damge(sim: &mut BattleSimulator, effector_id: MonsterID, context: (MonsterID, u16)) -> u16 {
-    let (target_id, mut damage) = context;
+    let (target_id, damage) = context;
+    deal_damage(sim, effector_id, target_id, damage, None)
+}
+
+/// What the battle log records about damage dealt by a move.
+struct MoveDamage {
+    move_used_id: MoveID,
+    effectiveness: Effectiveness,
+    is_critical_hit: bool,
+}
+
+/// Deals `damage` to `target_id` on behalf of `source_id`, and lets handlers know through `OnDamageDealt`.
+fn deal_damage(sim: &mut BattleSimulator, source_id: MonsterID, target_id: MonsterID, mut damage: u16, move_damage: Option<MoveDamage>) -> u16 {
     let original_health = mon![target_id].current_health;
     mon![mut target_id].current_health = original_health.saturating_sub(damage);
-    if mon![target_id].is_fainted() { 
+    let is_fainted = mon![target_id].is_fainted();
+    if is_fainted { 
         damage = original_health;
-        sim.push_message(format!["{} fainted!", mon![target_id].name()]);
+    }
+    let move_used_id = move_damage.as_ref().map(|move_damage| move_damage.move_used_id);
+    sim.log(BattleLogEntry::DamageDealt {
+        source_id,
+        target_id,
+        amount: damage,
+        remaining_health: mon![target_id].current_health,
+        move_used_id,
+        effectiveness: move_damage.as_ref().map(|move_damage| move_damage.effectiveness),
+        is_critical_hit: move_damage.is_some_and(|move_damage| move_damage.is_critical_hit),
+    });
+    if is_fainted {
+        sim.log(BattleLogEntry::Fainted { monster_id: target_id });
         mon![mut target_id].board_position = BoardPosition::Bench;
-    };
-    sim.trigger_event(OnDamageDealt, effector_id, NOTHING, NOTHING, None);
+    }
+    sim.trigger_event(OnDamageDealt, source_id, DamageContext::new(source_id, target_id, damage, move_used_id), NOTHING, None);
     damage
 }
 
+/// The simulator simulates restoring health equalling `Context.1` to the monster `Context.0`, up to its max HP.
+/// 
+/// Returns the actual amount of health restored.
+pub const RestoreHealth: Effect<u16, (MonsterID, u16)> = Effect(restore_health);
+
+#[must_use]
+fn restore_health(sim: &mut BattleSimulator, _effector_id: MonsterID, (target_id, amount): (MonsterID, u16)) -> u16 {
+    let original_health = mon![target_id].current_health;
+    let max_health = mon![target_id].max_health();
+    mon![mut target_id].current_health = original_health.saturating_add(amount).min(max_health);
+    let amount = mon![target_id].current_health - original_health;
+    sim.log(BattleLogEntry::HealthRestored { monster_id: target_id, amount });
+    amount
+}
+
+/// The simulator asks for a benched Monster to switch in for the active monster `Context` once the current
+/// action has been resolved, as with U-turn. The rest of the turn waits until the switch is submitted. This
+/// fails if the monster isn't active or its team has no Monsters to switch in.
+pub const RequestSwitchOut: Effect<Outcome, MonsterID> = Effect(request_switch_out);
+
+pub fn request_switch_out(sim: &mut BattleSimulator, _effector_id: MonsterID, active_monster_id: MonsterID) -> Outcome {
+    let monsters_already_chosen_for_switch = sim.monsters_already_chosen_for_switch();
+    let has_switchable_monsters = not!(sim.battle.switchable_benched_monster_ids(active_monster_id.team_id, &monsters_already_chosen_for_switch).is_empty());
+    if not!(mon![active_monster_id].is_active()) || not!(has_switchable_monsters) {
+        return Outcome::Failure;
+    }
+    sim.battle.pending_decisions.push(PendingDecision::MidMoveSwitch(active_monster_id));
+    Outcome::Success
+}
+
 /// The simulator simulates the activation of the ability `AbilityUseContext.ability_used` owned by
 /// the monster `AbilityUseContext.abilty_owner`.
 pub const ActivateAbility: Effect<Outcome, AbilityUseContext> = Effect(activate_ability);
@@ -262,6 +440,7 @@ pub fn activate_ability(sim: &mut BattleSimulator, effector_id: MonsterID, conte
     let AbilityUseContext { ability_used_id, ability_owner_id } = context;
 
     if sim.trigger_try_event(OnTryActivateAbility, ability_owner_id, context).succeeded() {
+        sim.log(BattleLogEntry::AbilityActivated { ability_id: ability_used_id });
         let ability = abl![ability_used_id];
         (ability.on_activate_effect())(sim, effector_id, context);
         sim.trigger_event(OnAbilityActivated, ability_owner_id, context, NOTHING, None);
@@ -271,7 +450,96 @@ pub fn activate_ability(sim: &mut BattleSimulator, effector_id: MonsterID, conte
     }
 }
 
+/// The simulator simulates the use of the item `ItemUseContext.item_used` held by the monster
+/// `ItemUseContext.item_holder`. Consumable items are removed from their holder after being used.
+pub const UseItem: Effect<Outcome, ItemUseContext> = Effect(use_item);
+
+#[must_use]
+pub fn use_item(sim: &mut BattleSimulator, effector_id: MonsterID, context: ItemUseContext) -> Outcome {
+    let ItemUseContext { item_used_id, item_holder_id } = context;
+
+    let Some(item) = sim.battle.item(item_used_id).copied() else {
+        return Outcome::Failure;
+    };
+
+    if sim.trigger_try_event(OnTryUseItem, item_holder_id, context).succeeded() {
+        sim.log(BattleLogEntry::ItemUsed { item_id: item_used_id });
+        (item.on_use_effect())(sim, effector_id, context);
+        if item.is_consumable() {
+            mon![mut item_holder_id].held_item = None;
+        }
+        sim.trigger_event(OnItemUsed, item_holder_id, context, NOTHING, None);
+        Outcome::Success
+    } else {
+        Outcome::Failure
+    }
+}
+
+/// The simulator simulates removing the held item of the monster `Context`, as with Knock Off. This fails if
+/// the monster isn't holding an item.
+pub const RemoveItem: Effect<Outcome, MonsterID> = Effect(remove_item);
+
+#[must_use]
+pub fn remove_item(sim: &mut BattleSimulator, _effector_id: MonsterID, item_holder_id: MonsterID) -> Outcome {
+    let Some(item) = mon![mut item_holder_id].held_item.take() else {
+        return Outcome::Failure;
+    };
+    sim.log(BattleLogEntry::ItemRemoved { monster_id: item_holder_id, item: item.species() });
+    Outcome::Success
+}
+
+/// The simulator simulates the monster `Context.1` taking the held item of the monster `Context.0`, as with
+/// Thief. This fails if `Context.0` isn't holding an item or `Context.1` already is.
+pub const TransferItem: Effect<Outcome, (MonsterID, MonsterID)> = Effect(transfer_item);
+
+#[must_use]
+pub fn transfer_item(sim: &mut BattleSimulator, _effector_id: MonsterID, (giver_id, receiver_id): (MonsterID, MonsterID)) -> Outcome {
+    if mon![giver_id].held_item().is_none() || mon![receiver_id].held_item().is_some() {
+        return Outcome::Failure;
+    }
+    let mut item = mon![mut giver_id].held_item.take().expect("We checked that the giver holds an item.");
+    item.id = ItemID::from_owner(receiver_id);
+    mon![mut receiver_id].held_item = Some(item);
+    sim.log(BattleLogEntry::ItemTransferred { giver_id, receiver_id, item: item.species() });
+    Outcome::Success
+}
+
+/// The simulator simulates the monsters `Context.0` and `Context.1` swapping their held items, as with Trick.
+/// This fails if neither monster is holding an item.
+pub const SwapItems: Effect<Outcome, (MonsterID, MonsterID)> = Effect(swap_items);
+
+#[must_use]
+pub fn swap_items(sim: &mut BattleSimulator, _effector_id: MonsterID, (first_monster_id, second_monster_id): (MonsterID, MonsterID)) -> Outcome {
+    let first_item = mon![mut first_monster_id].held_item.take();
+    let second_item = mon![mut second_monster_id].held_item.take();
+    if first_item.is_none() && second_item.is_none() {
+        sim.log(BattleLogEntry::EffectFailed);
+        return Outcome::Failure;
+    }
+    mon![mut first_monster_id].held_item = second_item.map(|item| Item { id: ItemID::from_owner(first_monster_id), ..item });
+    mon![mut second_monster_id].held_item = first_item.map(|item| Item { id: ItemID::from_owner(second_monster_id), ..item });
+    sim.log(BattleLogEntry::ItemsSwapped { first_monster_id, second_monster_id });
+    Outcome::Success
+}
+
+/// The simulator simulates raising the critical hit stage of the monster `Context.0` by `Context.1` stages, as with
+/// Focus Energy. This fails if the monster's critical hit stage is already at its maximum of 3.
+pub const RaiseCritStage: Effect<Outcome, (MonsterID, u8)> = Effect(raise_crit_stage);
+
+#[must_use]
+pub fn raise_crit_stage(sim: &mut BattleSimulator, _effector_id: MonsterID, (affected_monster_id, number_of_stages): (MonsterID, u8)) -> Outcome {
+    if mon![affected_monster_id].crit_stage() >= 3 {
+        sim.log(BattleLogEntry::EffectFailed);
+        return Outcome::Failure;
+    }
+    mon![mut affected_monster_id].crit_stage = mon![affected_monster_id].crit_stage().saturating_add(number_of_stages).min(3);
+    sim.log(BattleLogEntry::CritStageRaised { monster_id: affected_monster_id });
+    Outcome::Success
+}
+
 /// The simulator simulates the raising of stat `Context.1` of monster `Context.0` by `Context.2` stages
+/// 
+/// `OnTryRaiseStat` is broadcast by the effector, so handlers can tell whose effect is raising the stat.
 pub const RaiseStat: Effect<Outcome, (MonsterID, Stat, u8)> = Effect(raise_stat);
 
 #[must_use]
@@ -280,23 +548,23 @@ pub fn raise_stat(
     effector_id: MonsterID,
     (affected_monster_id, stat, number_of_stages): (MonsterID, Stat, u8), 
 ) -> Outcome {
-    if sim.trigger_try_event(OnTryRaiseStat, affected_monster_id, NOTHING).succeeded() {
+    let context = StatChangeContext::new(affected_monster_id, stat, number_of_stages);
+    if sim.trigger_try_event(OnTryRaiseStat, effector_id, context).succeeded() {
         let effective_stages = mon![mut affected_monster_id].stat_modifiers.raise_stat(stat, number_of_stages);
 
-        sim.push_message(format![
-            "{monster}\'s {stat} was raised by {effective_stages} stage(s)!",
-            monster = mon![affected_monster_id].name(),
-        ]);
+        sim.log(BattleLogEntry::StatChanged { monster_id: affected_monster_id, stat, direction: StatChangeDirection::Raised, stages: effective_stages });
 
         Outcome::Success
     } else {
-        sim.push_message(format!["{monster}'s stats cannot get any higher.", monster = mon![affected_monster_id].name()]);
+        sim.log(BattleLogEntry::StatChangeFailed { monster_id: affected_monster_id, stat, direction: StatChangeDirection::Raised });
 
         Outcome::Failure
     }
 }
 
 /// The simulator simulates the lowering of stat `Context.1` of monster `Context.0` by `Context.2` stages
+/// 
+/// `OnTryLowerStat` is broadcast by the effector, so abilities like Clear Body can block only their opponents.
 pub const LowerStat: Effect<Outcome, (MonsterID, Stat, u8)> = Effect(lower_stat);
 
 #[must_use]
@@ -305,18 +573,264 @@ pub fn lower_stat(
     effector_id: MonsterID,
     (affected_monster_id, stat, number_of_stages): (MonsterID, Stat, u8), 
 ) -> Outcome {
-    if sim.trigger_try_event(OnTryLowerStat, affected_monster_id, NOTHING).succeeded() {
+    let context = StatChangeContext::new(affected_monster_id, stat, number_of_stages);
+    if sim.trigger_try_event(OnTryLowerStat, effector_id, context).succeeded() {
         let effective_stages = mon![mut affected_monster_id].stat_modifiers.lower_stat(stat, number_of_stages);
 
-        sim.push_message(format![
-            "{monster}\'s {stat} was lowered by {effective_stages} stage(s)!",
-            monster = mon![affected_monster_id].name(),
-        ]);
+        sim.log(BattleLogEntry::StatChanged { monster_id: affected_monster_id, stat, direction: StatChangeDirection::Lowered, stages: effective_stages });
 
         Outcome::Success
     } else {
-        sim.push_message(format!["{monster}'s stats were not lowered.", monster = mon![affected_monster_id].name()]);
+        sim.log(BattleLogEntry::StatChangeFailed { monster_id: affected_monster_id, stat, direction: StatChangeDirection::Lowered });
 
         Outcome::Failure
     }
-}
\ No newline at end of file
+}
+
+/// The simulator simulates inflicting the status `Context.1` on the monster `Context.0`. This fails if the 
+/// monster already has a status or its type makes it immune to the status.
+pub const InflictStatus: Effect<Outcome, (MonsterID, MajorStatus)> = Effect(inflict_status);
+
+#[must_use]
+pub fn inflict_status(
+    sim: &mut BattleSimulator,
+    _effector_id: MonsterID,
+    (affected_monster_id, status): (MonsterID, MajorStatus),
+) -> Outcome {
+    if let Some(existing_status) = mon![affected_monster_id].status() {
+        if existing_status == status {
+            sim.log(BattleLogEntry::AlreadyHasStatus { monster_id: affected_monster_id, status });
+        } else {
+            sim.log(BattleLogEntry::EffectFailed);
+        }
+        return Outcome::Failure;
+    }
+
+    let (primary_type, secondary_type) = mon![affected_monster_id].species().type_();
+    let is_type_immune = status.is_type_immune(primary_type) || secondary_type.is_some_and(|type_| status.is_type_immune(type_));
+    if is_type_immune {
+        sim.log(BattleLogEntry::NotAffected { monster_id: affected_monster_id });
+        return Outcome::Failure;
+    }
+
+    let context = StatusContext::new(affected_monster_id, status);
+    if sim.trigger_try_event(OnTryInflictStatus, affected_monster_id, context).succeeded() {
+        let status_turn_counter = match status {
+            MajorStatus::Sleep => sim.generate_random_number_in_range_inclusive(1..=3) as u8,
+            _ => 0,
+        };
+        mon![mut affected_monster_id].status = Some(status);
+        mon![mut affected_monster_id].status_turn_counter = status_turn_counter;
+
+        sim.log(BattleLogEntry::StatusInflicted { monster_id: affected_monster_id, status });
+
+        sim.trigger_event(OnStatusInflicted, affected_monster_id, context, NOTHING, None);
+        Outcome::Success
+    } else {
+        sim.log(BattleLogEntry::EffectFailed);
+
+        Outcome::Failure
+    }
+}
+
+/// The simulator simulates curing the monster `Context` of its status. This fails if the monster has no status.
+pub const CureStatus: Effect<Outcome, MonsterID> = Effect(cure_status);
+
+#[must_use]
+pub fn cure_status(
+    sim: &mut BattleSimulator,
+    _effector_id: MonsterID,
+    affected_monster_id: MonsterID,
+) -> Outcome {
+    let Some(status) = mon![affected_monster_id].status() else {
+        return Outcome::Failure;
+    };
+
+    mon![mut affected_monster_id].status = None;
+    mon![mut affected_monster_id].status_turn_counter = 0;
+    sim.log(BattleLogEntry::StatusCured { monster_id: affected_monster_id, status });
+
+    Outcome::Success
+}
+
+/// The simulator simulates adding the volatile status `Context.1` to the monster `Context.0`. This fails if
+/// the monster already has that volatile status.
+pub const AddVolatileStatus: Effect<Outcome, (MonsterID, &'static VolatileStatusSpecies)> = Effect(add_volatile_status);
+
+#[must_use]
+pub fn add_volatile_status(
+    sim: &mut BattleSimulator,
+    _effector_id: MonsterID,
+    (affected_monster_id, species): (MonsterID, &'static VolatileStatusSpecies),
+) -> Outcome {
+    if mon![affected_monster_id].has_volatile_status(species) {
+        sim.log(BattleLogEntry::EffectFailed);
+        return Outcome::Failure;
+    }
+
+    let duration = match species.duration() {
+        VolatileStatusDuration::UntilSwitchOut => None,
+        VolatileStatusDuration::Turns(turns) => Some(turns),
+        VolatileStatusDuration::RandomTurns { min, max } => Some(sim.generate_random_number_in_range_inclusive(min as u16..=max as u16) as u8),
+    };
+    mon![mut affected_monster_id].volatile_statuses.push(VolatileStatus::new(species, duration));
+    sim.log(BattleLogEntry::VolatileStatusAdded { monster_id: affected_monster_id, species });
+
+    species.on_start_effect()(sim, affected_monster_id, affected_monster_id);
+    Outcome::Success
+}
+
+/// The simulator simulates removing the volatile status `Context.1` from the monster `Context.0`. This fails if
+/// the monster doesn't have that volatile status.
+pub const RemoveVolatileStatus: Effect<Outcome, (MonsterID, &'static VolatileStatusSpecies)> = Effect(remove_volatile_status);
+
+#[must_use]
+pub fn remove_volatile_status(
+    sim: &mut BattleSimulator,
+    _effector_id: MonsterID,
+    (affected_monster_id, species): (MonsterID, &'static VolatileStatusSpecies),
+) -> Outcome {
+    if not!(mon![affected_monster_id].has_volatile_status(species)) {
+        return Outcome::Failure;
+    }
+
+    mon![mut affected_monster_id].volatile_statuses.retain(|volatile_status| volatile_status.species() != species);
+    sim.log(BattleLogEntry::VolatileStatusRemoved { monster_id: affected_monster_id, species });
+
+    species.on_end_effect()(sim, affected_monster_id, affected_monster_id);
+    Outcome::Success
+}
+
+/// The simulator advances the turn counters of the volatile statuses of the monster `Context` at the end 
+/// of a turn, removing any that have expired.
+pub(crate) const TickVolatileStatuses: Effect<Nothing, MonsterID> = Effect(tick_volatile_statuses);
+
+fn tick_volatile_statuses(sim: &mut BattleSimulator, effector_id: MonsterID, monster_id: MonsterID) {
+    let mut expired_statuses = Vec::new();
+    for volatile_status in mon![mut monster_id].volatile_statuses.iter_mut() {
+        volatile_status.turn_counter = volatile_status.turn_counter.saturating_add(1);
+        if volatile_status.has_expired() {
+            expired_statuses.push(volatile_status.species());
+        }
+    }
+    for species in expired_statuses {
+        _ = RemoveVolatileStatus(sim, effector_id, (monster_id, species));
+    }
+}
+
+/// The simulator simulates setting the weather to `Context`, replacing any existing weather. This fails if
+/// the weather is already `Context`, or if `Context` is a terrain.
+pub const SetWeather: Effect<Outcome, &'static FieldEffectSpecies> = Effect(set_weather);
+
+#[must_use]
+pub fn set_weather(sim: &mut BattleSimulator, _effector_id: MonsterID, species: &'static FieldEffectSpecies) -> Outcome {
+    if species.kind() != FieldEffectKind::Weather {
+        return Outcome::Failure;
+    }
+    if sim.battle.field_state.is_weather(species) {
+        sim.log(BattleLogEntry::EffectFailed);
+        return Outcome::Failure;
+    }
+    sim.battle.field_state.weather = Some(FieldEffect::new(species));
+    sim.log(BattleLogEntry::FieldEffectStarted { species });
+    Outcome::Success
+}
+
+/// The simulator simulates clearing the current weather. This fails if there is no weather.
+pub const ClearWeather: Effect<Outcome, Nothing> = Effect(clear_weather);
+
+#[must_use]
+pub fn clear_weather(sim: &mut BattleSimulator, _effector_id: MonsterID, _context: Nothing) -> Outcome {
+    let Some(weather) = sim.battle.field_state.weather.take() else {
+        return Outcome::Failure;
+    };
+    sim.log(BattleLogEntry::FieldEffectEnded { species: weather.species() });
+    Outcome::Success
+}
+
+/// The simulator simulates setting the terrain to `Context`, replacing any existing terrain. This fails if
+/// the terrain is already `Context`, or if `Context` is a weather.
+pub const SetTerrain: Effect<Outcome, &'static FieldEffectSpecies> = Effect(set_terrain);
+
+#[must_use]
+pub fn set_terrain(sim: &mut BattleSimulator, _effector_id: MonsterID, species: &'static FieldEffectSpecies) -> Outcome {
+    if species.kind() != FieldEffectKind::Terrain {
+        return Outcome::Failure;
+    }
+    if sim.battle.field_state.is_terrain(species) {
+        sim.log(BattleLogEntry::EffectFailed);
+        return Outcome::Failure;
+    }
+    sim.battle.field_state.terrain = Some(FieldEffect::new(species));
+    sim.log(BattleLogEntry::FieldEffectStarted { species });
+    Outcome::Success
+}
+
+/// The simulator simulates clearing the current terrain. This fails if there is no terrain.
+pub const ClearTerrain: Effect<Outcome, Nothing> = Effect(clear_terrain);
+
+#[must_use]
+pub fn clear_terrain(sim: &mut BattleSimulator, _effector_id: MonsterID, _context: Nothing) -> Outcome {
+    let Some(terrain) = sim.battle.field_state.terrain.take() else {
+        return Outcome::Failure;
+    };
+    sim.log(BattleLogEntry::FieldEffectEnded { species: terrain.species() });
+    Outcome::Success
+}
+
+/// The simulator resolves the end of turn effects of the weather and terrain on the monster `Context`.
+pub(crate) const ResolveFieldEndOfTurnEffects: Effect<Nothing, MonsterID> = Effect(resolve_field_end_of_turn_effects);
+
+fn resolve_field_end_of_turn_effects(sim: &mut BattleSimulator, effector_id: MonsterID, monster_id: MonsterID) {
+    let field_effects = sim.battle.field_state.field_effects().copied().collect::<Vec<_>>();
+    for field_effect in field_effects {
+        if mon![monster_id].is_active() {
+            field_effect.species().end_of_turn_effect()(sim, effector_id, monster_id);
+        }
+    }
+}
+
+/// The simulator simulates adding the side condition `Context.1` to the team `Context.0`. If the team already
+/// has the condition, another layer is added instead, and this fails if it already has the maximum number of layers.
+pub const AddSideCondition: Effect<Outcome, (TeamID, &'static SideConditionSpecies)> = Effect(add_side_condition);
+
+#[must_use]
+pub fn add_side_condition(
+    sim: &mut BattleSimulator,
+    _effector_id: MonsterID,
+    (team_id, species): (TeamID, &'static SideConditionSpecies),
+) -> Outcome {
+    let side_conditions = &mut sim.battle.team_mut(team_id).side_conditions;
+    if let Some(side_condition) = side_conditions.iter_mut().find(|side_condition| side_condition.species() == species) {
+        if side_condition.layers >= species.max_layers() {
+            sim.log(BattleLogEntry::EffectFailed);
+            return Outcome::Failure;
+        }
+        side_condition.layers += 1;
+    } else {
+        side_conditions.push(SideCondition::new(species));
+    }
+
+    sim.log(BattleLogEntry::SideConditionAdded { team_id, species });
+    Outcome::Success
+}
+
+/// The simulator simulates removing every layer of the side condition `Context.1` from the team `Context.0`. This
+/// fails if the team doesn't have that side condition.
+pub const RemoveSideCondition: Effect<Outcome, (TeamID, &'static SideConditionSpecies)> = Effect(remove_side_condition);
+
+#[must_use]
+pub fn remove_side_condition(
+    sim: &mut BattleSimulator,
+    _effector_id: MonsterID,
+    (team_id, species): (TeamID, &'static SideConditionSpecies),
+) -> Outcome {
+    if not!(sim.battle.team(team_id).has_side_condition(species)) {
+        return Outcome::Failure;
+    }
+
+    sim.battle.team_mut(team_id).side_conditions.retain(|side_condition| side_condition.species() != species);
+
+    sim.log(BattleLogEntry::SideConditionRemoved { team_id, species });
+    Outcome::Success
+}
@@ -1,10 +1,11 @@
 use core::fmt::Debug;
 
+pub mod event_stack;
 pub mod events;
-#[cfg(all(test, feature = "debug"))]
+#[cfg(test)]
 mod tests ;
 
-use crate::{sim::{game_mechanics::MonsterID, ordering::sort_by_activation_order, BattleState, Nothing, Outcome, Percent}, BattleSimulator};
+use crate::{sim::{game_mechanics::{MonsterID, Stat, TeamID}, ordering::{sort_by_activation_order, ActivationOrder}, BattleState, Nothing, Outcome, Percent, NOTHING}, BattleSimulator};
 use contexts::*;
 pub use events::*;
 use monsim_utils::not;
@@ -36,6 +37,9 @@ impl EventDispatcher {
     /// `default` tells the resolver what value it should return if there are no event handlers, or the event handlers fall through.
     ///
     /// `short_circuit` is an optional value that, if returned by a handler in the chain, the resolution short-circuits and returns early.
+    /// 
+    /// If the event would be dispatched more than `MAX_EVENT_DEPTH` events deep, no handlers are resolved
+    /// and `default` is returned. `simulate_turn` then fails with `SimError::EventDepthLimitReached`.
     pub fn dispatch_event<R: PartialEq + Copy, C: Copy>(
         sim: &mut BattleSimulator,
 
@@ -46,33 +50,172 @@ impl EventDispatcher {
         short_circuit: Option<R>,
     ) -> R {
         
-        let mut owned_event_handlers = sim.battle.event_handlers_for(event);
-
-        if owned_event_handlers.is_empty() {
+        if not!(sim.event_stack.try_push(event.name(), broadcaster_id)) {
             return default;
         }
- 
-        sort_by_activation_order(&mut sim.battle.prng, &mut owned_event_handlers, |owned_event_handler| {
-            owned_event_handler.activation_order
-        });
+        let owned_event_handlers = EventDispatcher::sorted_event_handlers_for(sim, event);
 
+        // Each handler decides the result on its own, the result of the previous handler is not passed on.
+        // Use `dispatch_modify_event` for events whose handlers should build on each other's results.
         let mut relay = default;
-        for OwnedEventHandler { event_handler, owner_id, filtering_options, .. } in owned_event_handlers.into_iter() {
-            if EventDispatcher::does_event_pass_event_receivers_filtering_options(&sim.battle, broadcaster_id, owner_id, filtering_options) {
-                // INFO: Removed relaying the outcome of the previous handler from the event resolution. It will be
-                // reintroduced if it ever turns out to be useful. Otherwise remove this comment. 
-                relay = (event_handler.effect)(sim, owner_id, event_context);
+        for owned_event_handler in owned_event_handlers.into_iter() {
+            if let Some(effector_id) = EventDispatcher::effector_if_passes_filter(&sim.battle, broadcaster_id, owned_event_handler) {
+                relay = EventDispatcher::resolve_event_handler(sim, owned_event_handler.event_handler, effector_id, event_context, relay);
                 // Return early if the relay becomes the short-circuiting value.
                 if let Some(value) = short_circuit {
                     if relay == value {
-                        return relay;
+                        break;
                     }
                 };
             }
         }
+        sim.event_stack.pop();
         relay
     }
 
+    /// Passes `initial_value` through every handler for `event` in activation order, each handler receiving
+    /// the value returned by the previous one alongside `event_context`, and returns the final value.
+    pub fn dispatch_modify_event<V: Copy, C: Copy>(
+        sim: &mut BattleSimulator,
+
+        event: impl Event<EventReturnType = V, ContextType = (C, V)>,
+        broadcaster_id: MonsterID,
+        event_context: C,
+        initial_value: V,
+    ) -> V {
+
+        if not!(sim.event_stack.try_push(event.name(), broadcaster_id)) {
+            return initial_value;
+        }
+        let owned_event_handlers = EventDispatcher::sorted_event_handlers_for(sim, event);
+
+        let mut value = initial_value;
+        for owned_event_handler in owned_event_handlers.into_iter() {
+            if let Some(effector_id) = EventDispatcher::effector_if_passes_filter(&sim.battle, broadcaster_id, owned_event_handler) {
+                value = EventDispatcher::resolve_event_handler(sim, owned_event_handler.event_handler, effector_id, (event_context, value), value);
+            }
+        }
+        sim.event_stack.pop();
+        value
+    }
+
+    /// Resolves every handler for `event` without a broadcaster, once for each Monster it applies to. Monster
+    /// handlers are resolved as their owner, field handlers as every active Monster and side handlers as every
+    /// active Monster on their side. The resolutions are ordered by the current Speed of the Monster they are
+    /// resolved as, and stop as soon as the battle is finished, so that faints are checked after each one.
+    pub fn dispatch_residual_event<C: Copy>(
+        sim: &mut BattleSimulator,
+
+        event: impl Event<EventReturnType = Nothing, ContextType = C>,
+        event_context: C,
+    ) {
+
+        let active_monster_ids = sim.battle.active_monsters().map(|monster| monster.id).collect::<Vec<_>>();
+        let mut residuals = Vec::new();
+        for owned_event_handler in sim.battle.event_handlers_for(event) {
+            let effector_ids = match owned_event_handler.owner {
+                EventHandlerOwner::Monster(owner_id) => vec![owner_id],
+                EventHandlerOwner::Field => active_monster_ids.clone(),
+                EventHandlerOwner::Side(team_id) => active_monster_ids.iter()
+                    .copied()
+                    .filter(|monster_id| monster_id.team_id == team_id)
+                    .collect(),
+            };
+            for effector_id in effector_ids {
+                let activation_order = ActivationOrder {
+                    speed: sim.effective_stat(effector_id, Stat::Speed),
+                    ..owned_event_handler.activation_order
+                };
+                residuals.push((owned_event_handler, effector_id, activation_order));
+            }
+        }
+        sort_by_activation_order(&mut sim.battle.prng, &mut residuals, |(_, _, activation_order)| activation_order);
+
+        for (owned_event_handler, effector_id, _) in residuals.into_iter() {
+            if sim.battle.is_finished() {
+                break;
+            }
+            // An earlier residual may have caused the effector to faint or leave the field.
+            let effector = sim.battle.monster(effector_id);
+            // Field handlers have no owner that could be benched, so they only resolve as active Monsters.
+            let requires_being_active = owned_event_handler.filtering_options.requires_being_active
+                || owned_event_handler.owner == EventHandlerOwner::Field;
+            if effector.is_fainted() || (requires_being_active && not!(effector.is_active())) {
+                continue;
+            }
+            if not!(sim.event_stack.try_push(event.name(), effector_id)) {
+                return;
+            }
+            EventDispatcher::resolve_event_handler(sim, owned_event_handler.event_handler, effector_id, event_context, NOTHING);
+            sim.event_stack.pop();
+        }
+    }
+
+    fn sorted_event_handlers_for<E: Event>(sim: &mut BattleSimulator, event: E) -> Vec<OwnedEventHandler<E>> {
+        let mut owned_event_handlers = sim.battle.event_handlers_for(event);
+        sort_by_activation_order(&mut sim.battle.prng, &mut owned_event_handlers, |owned_event_handler| {
+            owned_event_handler.activation_order
+        });
+        owned_event_handlers
+    }
+
+    /// Returns the Monster the handler should be resolved as, if the handler responds to `broadcaster_id`.
+    fn effector_if_passes_filter<E: Event>(battle: &BattleState, broadcaster_id: MonsterID, owned_event_handler: OwnedEventHandler<E>) -> Option<MonsterID> {
+        let OwnedEventHandler { owner, filtering_options, .. } = owned_event_handler;
+        // Field handlers apply to every Monster, so they are resolved from the broadcaster's point of view.
+        let (passes_filter, effector_id) = match owner {
+            EventHandlerOwner::Monster(owner_id) => (
+                EventDispatcher::does_event_pass_event_receivers_filtering_options(battle, broadcaster_id, owner_id, filtering_options),
+                owner_id
+            ),
+            EventHandlerOwner::Field => (true, broadcaster_id),
+            EventHandlerOwner::Side(team_id) => (
+                EventDispatcher::does_event_pass_side_filtering_options(battle, broadcaster_id, team_id, filtering_options),
+                broadcaster_id
+            ),
+        };
+        passes_filter.then_some(effector_id)
+    }
+
+    /// `fallback` is what a script handler returns if its script fails, the failure is kept as the error
+    /// `simulate_turn` returns.
+    #[cfg_attr(not(feature = "scripting"), allow(unused_variables))]
+    fn resolve_event_handler<E: Event>(
+        sim: &mut BattleSimulator,
+        event_handler: EventHandler<E>,
+        effector_id: MonsterID,
+        event_context: E::ContextType,
+        fallback: E::EventReturnType,
+    ) -> E::EventReturnType {
+        #[cfg(feature = "scripting")]
+        if let Some(script_function) = event_handler.script_function {
+            return crate::sim::scripting::with_script_function(script_function, fallback, || {
+                (event_handler.effect)(sim, effector_id, event_context)
+            });
+        }
+        (event_handler.effect)(sim, effector_id, event_context)
+    }
+
+    /// Side conditions have no position on the field, so only the team relation of the broadcaster is checked.
+    /// Broadcasters on the side's own team count as `ALLIES`, and broadcasters on the other team as `OPPONENTS`.
+    /// Side handlers are resolved as the broadcaster, so `requires_being_active` applies to the broadcaster.
+    fn does_event_pass_side_filtering_options(
+        battle: &BattleState,
+        event_broadcaster_id: MonsterID,
+        side_team_id: TeamID,
+        receiver_filtering_options: EventFilteringOptions,
+    ) -> bool {
+        if receiver_filtering_options.requires_being_active && not!(battle.monster(event_broadcaster_id).is_active()) {
+            return false;
+        }
+        let broadcaster_relation_flag = if event_broadcaster_id.team_id == side_team_id {
+            TargetFlags::ALLIES
+        } else {
+            TargetFlags::OPPONENTS
+        };
+        receiver_filtering_options.allowed_broadcaster_relation_flags.contains(broadcaster_relation_flag)
+    }
+
     fn does_event_pass_event_receivers_filtering_options(
         battle: &BattleState,
         event_broadcaster_id: MonsterID,
@@ -99,13 +242,14 @@ impl EventDispatcher {
         let mut broadcaster_relation_flags = TargetFlags::empty();
         let event_broadcaster_field_position = battle.monster(event_broadcaster_id)
             .board_position
-            .field_position()
-            .expect("We assume broadcasters must be on the field.");
+            .field_position();
         let event_receiver_field_position = battle.monster(event_receiver_id)
             .board_position
             .field_position();
-        // The event receiver might be benched.
-        if let Some(event_receiver_field_position) = event_receiver_field_position {
+        // The event receiver might be benched, and so might the broadcaster if it just fainted. A Monster
+        // broadcasting to itself is only `SELF`, it is neither adjacent nor nonadjacent to itself.
+        let is_self = event_broadcaster_id == event_receiver_id;
+        if let (false, Some(event_broadcaster_field_position), Some(event_receiver_field_position)) = (is_self, event_broadcaster_field_position, event_receiver_field_position) {
             if event_broadcaster_field_position.is_adjacent_to(event_receiver_field_position) {
                 broadcaster_relation_flags |= TargetFlags::ADJACENT
             } else {
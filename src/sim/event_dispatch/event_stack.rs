@@ -0,0 +1,75 @@
+use crate::sim::{EventHandlerSource, MonsterID, SimError};
+
+/// The deepest chain of events that can be dispatched from within each other, _e.g._ an ability
+/// activating inside an `OnTryMoveHit` handler, which in turn triggers `OnTryActivateAbility`.
+pub const MAX_EVENT_DEPTH: usize = 32;
+
+/// Tracks the chain of events currently being dispatched, so that handlers which keep triggering each
+/// other are stopped instead of recursing forever. It also keeps the first error raised while dispatching,
+/// which `simulate_turn` returns once the current action is over.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventStack {
+    frames: Vec<EventFrame>,
+    error: Option<SimError>,
+}
+
+/// An event being dispatched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventFrame {
+    pub event_name: &'static str,
+    pub broadcaster_id: MonsterID,
+}
+
+impl EventStack {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes a frame for the event about to be dispatched. Fails if the depth limit has been reached,
+    /// in which case the event must not be dispatched, and the error is kept until `take_error` is called.
+    pub(crate) fn try_push(&mut self, event_name: &'static str, broadcaster_id: MonsterID) -> bool {
+        if self.frames.len() >= MAX_EVENT_DEPTH {
+            self.record_error(SimError::EventDepthLimitReached(
+                self.frames.iter().map(|frame| frame.event_name).chain(std::iter::once(event_name)).collect()
+            ));
+            return false;
+        }
+        self.frames.push(EventFrame { event_name, broadcaster_id });
+        true
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.frames.pop();
+    }
+
+    /// Keeps `error` until `take_error` is called, unless an earlier error is already being kept.
+    pub(crate) fn record_error(&mut self, error: SimError) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    pub(crate) fn take_error(&mut self) -> Option<SimError> {
+        self.error.take()
+    }
+
+    /// The events currently being dispatched, outermost first.
+    pub fn frames(&self) -> &[EventFrame] {
+        &self.frames
+    }
+
+    #[inline(always)]
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+/// Disables the event handlers from `source`, either for as long as a `BattleSimulator::with_suppressed`
+/// scope lasts, or for a number of turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suppression {
+    pub source: EventHandlerSource,
+    /// `None` for suppressions that last until the end of their scope.
+    pub turns_remaining: Option<u8>,
+}
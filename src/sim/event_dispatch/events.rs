@@ -1,22 +1,96 @@
 use super::*;
 pub use generated::*;
-use crate::sim::{Effect, ActivationOrder, EffectFunction};
+use monsim_utils::MaxSizedVec;
+use crate::sim::{Effect, ActivationOrder, AbilityID, EffectFunction, FieldEffectSpecies, ItemID, MoveID, SideConditionSpecies, TeamID, VolatileStatusSpecies};
+
+/// The most handlers an `EventHandlerDeck` can hold for a single event.
+pub const MAX_EVENT_HANDLERS_PER_EVENT: usize = 4;
+
+/// The handlers an `EventHandlerDeck` holds for one event, in the order they were added.
+pub type EventHandlers<E> = MaxSizedVec<EventHandler<E>, MAX_EVENT_HANDLERS_PER_EVENT>;
 
 /// Stores an `Effect` that gets simulated in response to an `Event` being triggered.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct EventHandler<E: Event> {
     pub event: E,
     pub effect: Effect<E::EventReturnType, E::ContextType>,
+    /// Overrides the `order` of the handler's owner, to order it relative to the owner's other handlers for the same event.
+    pub order: Option<u16>,
+    /// Overrides the `EventFilteringOptions` of the handler's owner.
+    pub filtering_options: Option<EventFilteringOptions>,
     #[cfg(feature = "debug")]
     pub source_code_location: &'static str,
+    /// The name of the script function backing this handler, if it was added with `EventHandlerDeck::add_script`.
+    #[cfg(feature = "scripting")]
+    pub script_function: Option<&'static str>,
 }
 
-impl<'a, E: Event + Debug> Debug for EventHandler<E> {
+impl<E: Event> EventHandler<E> {
+    #[cfg(feature="debug")]
+    pub const fn new(event: E, effect: EffectFunction<E::EventReturnType, E::ContextType>, source_code_location: &'static str) -> Self {
+        EventHandler {
+            event,
+            effect: Effect::from(effect),
+            order: None,
+            filtering_options: None,
+            source_code_location,
+            #[cfg(feature = "scripting")]
+            script_function: None,
+        }
+    }
+
+    #[cfg(not(feature="debug"))]
+    pub const fn new(event: E, effect: EffectFunction<E::EventReturnType, E::ContextType>) -> Self {
+        EventHandler {
+            event,
+            effect: Effect::from(effect),
+            order: None,
+            filtering_options: None,
+            #[cfg(feature = "scripting")]
+            script_function: None,
+        }
+    }
+
+    pub const fn with_order(mut self, order: u16) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    pub const fn with_filtering_options(mut self, filtering_options: EventFilteringOptions) -> Self {
+        self.filtering_options = Some(filtering_options);
+        self
+    }
+
+    /// Attaches the handler to `owner`. The handler's own order and filtering options, if it has any,
+    /// take precedence over the ones given here, which are the owner's.
+    pub(crate) fn owned_by(
+        self,
+        owner: EventHandlerOwner,
+        source: EventHandlerSource,
+        activation_order: ActivationOrder,
+        filtering_options: EventFilteringOptions
+    ) -> OwnedEventHandler<E> {
+        OwnedEventHandler {
+            event_handler: self,
+            owner,
+            source,
+            activation_order: ActivationOrder {
+                order: self.order.unwrap_or(activation_order.order),
+                ..activation_order
+            },
+            filtering_options: self.filtering_options.unwrap_or(filtering_options),
+        }
+    }
+}
+
+impl<E: Event + Debug> Debug for EventHandler<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         #[cfg(feature = "debug")]
         let out = {
             f.debug_struct("EventHandler")
                 .field("event", &self.event)
+                .field("order", &self.order)
+                .field("filtering_options", &self.filtering_options)
                 .field("source_code_location", &self.source_code_location)
                 .finish()
         };
@@ -31,21 +105,66 @@ impl<'a, E: Event + Debug> Debug for EventHandler<E> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct OwnedEventHandler<E: Event> {
     pub event_handler: EventHandler<E>,
-    pub owner_id: MonsterID,
+    pub owner: EventHandlerOwner,
+    pub source: EventHandlerSource,
     pub activation_order: ActivationOrder,
     pub filtering_options: EventFilteringOptions,
 }
 
+/// What an `EventHandler` belongs to, which decides whose point of view the handler is resolved from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventHandlerOwner {
+    /// The handler comes from the Monster itself, or its ability, moves or volatile statuses.
+    Monster(MonsterID),
+    /// The handler comes from the weather or terrain, and is resolved with the broadcaster as the effector.
+    Field,
+    /// The handler comes from a side condition on the team `TeamID`, and is resolved with the broadcaster as the effector.
+    Side(TeamID),
+}
+
+/// The entity whose `EventHandlerDeck` an `EventHandler` comes from, which is what gets suppressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventHandlerSource {
+    /// The Monster's species.
+    Monster(MonsterID),
+    Ability(AbilityID),
+    Item(ItemID),
+    Move(MoveID),
+    /// One volatile status of the Monster.
+    VolatileStatus(
+        MonsterID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        &'static VolatileStatusSpecies,
+    ),
+    FieldEffect(
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        &'static FieldEffectSpecies,
+    ),
+    /// One side condition on the team.
+    SideCondition(
+        TeamID,
+        #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+        &'static SideConditionSpecies,
+    ),
+}
+
+impl EventHandlerOwner {
+    pub fn monster_id(&self) -> Option<MonsterID> {
+        match self {
+            EventHandlerOwner::Monster(monster_id) => Some(*monster_id),
+            EventHandlerOwner::Field | EventHandlerOwner::Side(_) => None,
+        }
+    }
+}
+
 pub trait Event: Clone + Copy + PartialEq + Eq {
-    type EventReturnType: Sized + Clone + Copy + PartialEq + Eq;
+    type EventReturnType: Sized + Clone + Copy + PartialEq + Eq + 'static;
     type ContextType: Sized + Clone + Copy + PartialEq + Eq;
 
-    fn corresponding_handler(
-        &self,
-        event_handler_deck: EventHandlerDeck,
-    ) -> Option<EventHandler<Self>>;
+    fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self>;
 
-    fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>>;
+    fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self>;
 
     fn name(&self) -> &'static str;
 }
@@ -57,32 +176,54 @@ impl EventHandlerDeck {
 
     #[cfg(feature="debug")]
     pub fn add<E: Event>(&mut self, event: E, effect: EffectFunction<E::EventReturnType, E::ContextType>, source_code_location: &'static str) -> EventHandlerDeck {
-        *event.corresponding_handler_mut(self) = Some(
-            EventHandler {
-                event,
-                effect: Effect::from(effect),
-                source_code_location,
-            }
-        );
-        *self 
+        self.add_handler(EventHandler::new(event, effect, source_code_location))
     }
 
     #[cfg(not(feature="debug"))]
-    pub fn add<E: Event>(&mut self, event: E, effect: fn(&mut BattleSimulator, E::ContextType) -> E::EventReturnType) -> EventHandlerDeck {
-        *event.corresponding_handler_mut(self) = Some(
+    pub fn add<E: Event>(&mut self, event: E, effect: EffectFunction<E::EventReturnType, E::ContextType>) -> EventHandlerDeck {
+        self.add_handler(EventHandler::new(event, effect))
+    }
+
+    /// Adds `event_handler` after any handlers already in the deck for the same event. Use this
+    /// instead of `add` to give the handler its own order or filtering options.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the deck already holds `MAX_EVENT_HANDLERS_PER_EVENT` handlers for the event.
+    pub fn add_handler<E: Event>(&mut self, event_handler: EventHandler<E>) -> EventHandlerDeck {
+        let event_name = event_handler.event.name();
+        if event_handler.event.corresponding_handlers_mut(self).try_push(event_handler).is_err() {
+            panic!("An EventHandlerDeck cannot hold more than {MAX_EVENT_HANDLERS_PER_EVENT} handlers for {event_name}.");
+        }
+        *self 
+    }
+
+    /// Adds a handler for `event` backed by the script function `function_name`, which must have been
+    /// loaded with `scripting::load_script` before the event is triggered.
+    #[cfg(feature = "scripting")]
+    pub fn add_script<E: Event>(&mut self, event: E, function_name: &'static str) -> EventHandlerDeck
+    where
+        E::ContextType: crate::sim::scripting::IntoScriptValue,
+        E::EventReturnType: crate::sim::scripting::FromScriptValue,
+    {
+        self.add_handler(
             EventHandler {
                 event,
-                effect: Effect::from(effect),
-            }
-        );
-        *self 
+                effect: Effect::from(crate::sim::scripting::call_script_handler::<E>),
+                order: None,
+                filtering_options: None,
+                #[cfg(feature = "debug")]
+                source_code_location: function_name,
+                script_function: Some(function_name),
+            }
+        )
     }
 }
 
 pub mod contexts {
     use monsim_utils::MaxSizedVec;
 
-    use crate::{sim::{MonsterID, MoveID}, AbilityID};
+    use crate::{sim::{ItemID, MajorStatus, MonsterID, MoveID, Stat}, AbilityID};
 
     /// `move_user_id`: MonsterID of the Monster using the move.
     /// 
@@ -146,6 +287,24 @@ pub mod contexts {
         }
     }
 
+    /// `item_holder_id`: MonsterID of the Monster whose item is being used.
+    /// 
+    /// `item_used_id`: ItemID of the Item being used.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ItemUseContext {
+        pub item_holder_id: MonsterID,
+        pub item_used_id: ItemID,
+    }
+
+    impl ItemUseContext {
+        pub fn new(item_holder: MonsterID) -> Self {
+            Self {
+                item_used_id: ItemID { owner_id: item_holder },
+                item_holder_id: item_holder,
+            }
+        }
+    }
+
     /// `active_monster_id`: MonsterID of the Monster to be switched out.
     /// 
     /// `benched_monster_id`: MonsterID of the Monster to be switched in.
@@ -163,6 +322,119 @@ pub mod contexts {
             }
         }
     }
+
+    /// `monster_id`: MonsterID of the Monster that was switched in.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SwitchInContext {
+        pub monster_id: MonsterID,
+    }
+
+    impl SwitchInContext {
+        pub fn new(monster_id: MonsterID) -> Self {
+            Self {
+                monster_id,
+            }
+        }
+    }
+
+    /// `turn_number`: The number of the turn that is starting or ending.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct TurnContext {
+        pub turn_number: u16,
+    }
+
+    impl TurnContext {
+        pub fn new(turn_number: u16) -> Self {
+            Self {
+                turn_number,
+            }
+        }
+    }
+
+    /// `monster_id`: MonsterID of the Monster whose stat is being calculated.
+    /// 
+    /// `stat`: The Stat being calculated.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StatContext {
+        pub monster_id: MonsterID,
+        pub stat: Stat,
+    }
+
+    impl StatContext {
+        pub fn new(monster_id: MonsterID, stat: Stat) -> Self {
+            Self {
+                monster_id,
+                stat,
+            }
+        }
+    }
+
+    /// `monster_id`: MonsterID of the Monster being inflicted with the status.
+    /// 
+    /// `status`: The MajorStatus being inflicted.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StatusContext {
+        pub monster_id: MonsterID,
+        pub status: MajorStatus,
+    }
+
+    impl StatusContext {
+        pub fn new(monster_id: MonsterID, status: MajorStatus) -> Self {
+            Self {
+                monster_id,
+                status,
+            }
+        }
+    }
+
+    /// `source_id`: MonsterID of the Monster dealing the damage. This is the damaged Monster itself for
+    /// residual damage such as from poison.
+    /// 
+    /// `target_id`: MonsterID of the Monster taking the damage.
+    /// 
+    /// `amount`: The HP the target actually lost.
+    /// 
+    /// `move_used_id`: MoveID of the Move dealing the damage, if the damage was dealt by a Move.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct DamageContext {
+        pub source_id: MonsterID,
+        pub target_id: MonsterID,
+        pub amount: u16,
+        pub move_used_id: Option<MoveID>,
+    }
+
+    impl DamageContext {
+        pub fn new(source_id: MonsterID, target_id: MonsterID, amount: u16, move_used_id: Option<MoveID>) -> Self {
+            Self {
+                source_id,
+                target_id,
+                amount,
+                move_used_id,
+            }
+        }
+    }
+
+    /// `monster_id`: MonsterID of the Monster whose stat is being changed.
+    /// 
+    /// `stat`: The Stat being changed.
+    /// 
+    /// `stages`: The number of stages the stat is being raised or lowered by.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StatChangeContext {
+        pub monster_id: MonsterID,
+        pub stat: Stat,
+        pub stages: u8,
+    }
+
+    impl StatChangeContext {
+        pub fn new(monster_id: MonsterID, stat: Stat, stages: u8) -> Self {
+            Self {
+                monster_id,
+                stat,
+                stages,
+            }
+        }
+    }
 }
 
 // Generated.
@@ -177,15 +449,32 @@ mod generated {
         event OnMoveUsed(MoveUseContext) => Nothing,
 
         event OnTryMoveHit(MoveHitContext) => Outcome,
-        event OnHit(Nothing) => Nothing,
+        event OnHit(MoveHitContext) => Nothing,
 
-        event OnDamageDealt(Nothing) => Nothing,
+        event OnDamageDealt(DamageContext) => Nothing,
         event OnTryActivateAbility(AbilityUseContext) => Outcome,
         event OnAbilityActivated(AbilityUseContext) => Nothing,
-        event OnModifyAccuracy(MoveUseContext) => Percent,
-        event OnTryRaiseStat(Nothing) => Outcome,
-        event OnTryLowerStat(Nothing) => Outcome,
+        event OnTryUseItem(ItemUseContext) => Outcome,
+        event OnItemUsed(ItemUseContext) => Nothing,
+        event OnModifyAccuracy(MoveHitContext, Percent) => Percent,
+        event OnMoveMissed(MoveHitContext) => Nothing,
+        event OnTryRaiseStat(StatChangeContext) => Outcome,
+        event OnTryLowerStat(StatChangeContext) => Outcome,
         event OnStatusMoveUsed(MoveUseContext) => Nothing,
+        event OnModifyStat(StatContext, u16) => u16,
+        event OnModifyBasePower(MoveHitContext, u16) => u16,
+        event OnModifyAttackStat(MoveHitContext, u16) => u16,
+        event OnModifyDefenseStat(MoveHitContext, u16) => u16,
+        event OnModifyStab(MoveHitContext, Percent) => Percent,
+        event OnModifyTypeEffectiveness(MoveHitContext, Percent) => Percent,
+        event OnModifyFinalDamage(MoveHitContext, u16) => u16,
+        event OnModifyCritRatio(MoveHitContext, u8) => u8,
+        event OnCriticalHit(MoveHitContext) => Nothing,
+        event OnTryInflictStatus(StatusContext) => Outcome,
+        event OnStatusInflicted(StatusContext) => Nothing,
+        event OnSwitchIn(SwitchInContext) => Nothing,
+        event OnTurnStart(TurnContext) => Nothing,
+        event OnTurnEnd(TurnContext) => Nothing,
     }
 }
 
@@ -197,30 +486,64 @@ mod generated {
     use event_dex::*;
     #[derive(Debug, Clone, Copy)]
     pub struct EventHandlerDeck {
-        pub on_try_move: Option<EventHandler<OnTryMove>>,
-        pub on_move_used: Option<EventHandler<OnMoveUsed>>,
-        pub on_try_move_hit: Option<EventHandler<OnTryMoveHit>>,
-        pub on_hit: Option<EventHandler<OnHit>>,
-        pub on_damage_dealt: Option<EventHandler<OnDamageDealt>>,
-        pub on_try_activate_ability: Option<EventHandler<OnTryActivateAbility>>,
-        pub on_ability_activated: Option<EventHandler<OnAbilityActivated>>,
-        pub on_modify_accuracy: Option<EventHandler<OnModifyAccuracy>>,
-        pub on_try_raise_stat: Option<EventHandler<OnTryRaiseStat>>,
-        pub on_try_lower_stat: Option<EventHandler<OnTryLowerStat>>,
-        pub on_status_move_used: Option<EventHandler<OnStatusMoveUsed>>,
+        pub on_try_move: EventHandlers<OnTryMove>,
+        pub on_move_used: EventHandlers<OnMoveUsed>,
+        pub on_try_move_hit: EventHandlers<OnTryMoveHit>,
+        pub on_hit: EventHandlers<OnHit>,
+        pub on_damage_dealt: EventHandlers<OnDamageDealt>,
+        pub on_try_activate_ability: EventHandlers<OnTryActivateAbility>,
+        pub on_ability_activated: EventHandlers<OnAbilityActivated>,
+        pub on_try_use_item: EventHandlers<OnTryUseItem>,
+        pub on_item_used: EventHandlers<OnItemUsed>,
+        pub on_modify_accuracy: EventHandlers<OnModifyAccuracy>,
+        pub on_move_missed: EventHandlers<OnMoveMissed>,
+        pub on_try_raise_stat: EventHandlers<OnTryRaiseStat>,
+        pub on_try_lower_stat: EventHandlers<OnTryLowerStat>,
+        pub on_status_move_used: EventHandlers<OnStatusMoveUsed>,
+        pub on_modify_stat: EventHandlers<OnModifyStat>,
+        pub on_modify_base_power: EventHandlers<OnModifyBasePower>,
+        pub on_modify_attack_stat: EventHandlers<OnModifyAttackStat>,
+        pub on_modify_defense_stat: EventHandlers<OnModifyDefenseStat>,
+        pub on_modify_stab: EventHandlers<OnModifyStab>,
+        pub on_modify_type_effectiveness: EventHandlers<OnModifyTypeEffectiveness>,
+        pub on_modify_final_damage: EventHandlers<OnModifyFinalDamage>,
+        pub on_modify_crit_ratio: EventHandlers<OnModifyCritRatio>,
+        pub on_critical_hit: EventHandlers<OnCriticalHit>,
+        pub on_try_inflict_status: EventHandlers<OnTryInflictStatus>,
+        pub on_status_inflicted: EventHandlers<OnStatusInflicted>,
+        pub on_switch_in: EventHandlers<OnSwitchIn>,
+        pub on_turn_start: EventHandlers<OnTurnStart>,
+        pub on_turn_end: EventHandlers<OnTurnEnd>,
     }
     pub(super) const DEFAULT_EVENT_HANDLERS: EventHandlerDeck = EventHandlerDeck {
-        on_try_move: None,
-        on_move_used: None,
-        on_try_move_hit: None,
-        on_hit: None,
-        on_damage_dealt: None,
-        on_try_activate_ability: None,
-        on_ability_activated: None,
-        on_modify_accuracy: None,
-        on_try_raise_stat: None,
-        on_try_lower_stat: None,
-        on_status_move_used: None,
+        on_try_move: MaxSizedVec::empty(),
+        on_move_used: MaxSizedVec::empty(),
+        on_try_move_hit: MaxSizedVec::empty(),
+        on_hit: MaxSizedVec::empty(),
+        on_damage_dealt: MaxSizedVec::empty(),
+        on_try_activate_ability: MaxSizedVec::empty(),
+        on_ability_activated: MaxSizedVec::empty(),
+        on_try_use_item: MaxSizedVec::empty(),
+        on_item_used: MaxSizedVec::empty(),
+        on_modify_accuracy: MaxSizedVec::empty(),
+        on_move_missed: MaxSizedVec::empty(),
+        on_try_raise_stat: MaxSizedVec::empty(),
+        on_try_lower_stat: MaxSizedVec::empty(),
+        on_status_move_used: MaxSizedVec::empty(),
+        on_modify_stat: MaxSizedVec::empty(),
+        on_modify_base_power: MaxSizedVec::empty(),
+        on_modify_attack_stat: MaxSizedVec::empty(),
+        on_modify_defense_stat: MaxSizedVec::empty(),
+        on_modify_stab: MaxSizedVec::empty(),
+        on_modify_type_effectiveness: MaxSizedVec::empty(),
+        on_modify_final_damage: MaxSizedVec::empty(),
+        on_modify_crit_ratio: MaxSizedVec::empty(),
+        on_critical_hit: MaxSizedVec::empty(),
+        on_try_inflict_status: MaxSizedVec::empty(),
+        on_status_inflicted: MaxSizedVec::empty(),
+        on_switch_in: MaxSizedVec::empty(),
+        on_turn_start: MaxSizedVec::empty(),
+        on_turn_end: MaxSizedVec::empty(),
     };
     pub mod event_dex {
         use super::*;
@@ -230,10 +553,10 @@ mod generated {
         impl Event for OnTryMove {
             type EventReturnType = Outcome;
             type ContextType = MoveUseContext;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_try_move
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_try_move
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_try_move
             }
             fn name(&self) -> &'static str {
@@ -246,10 +569,10 @@ mod generated {
         impl Event for OnMoveUsed {
             type EventReturnType = Nothing;
             type ContextType = MoveUseContext;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_move_used
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_move_used
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_move_used
             }
             fn name(&self) -> &'static str {
@@ -262,10 +585,10 @@ mod generated {
         impl Event for OnTryMoveHit {
             type EventReturnType = Outcome;
             type ContextType = MoveHitContext;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_try_move_hit
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_try_move_hit
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_try_move_hit
             }
             fn name(&self) -> &'static str {
@@ -277,11 +600,11 @@ mod generated {
 
         impl Event for OnHit {
             type EventReturnType = Nothing;
-            type ContextType = Nothing;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_hit
+            type ContextType = MoveHitContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_hit
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_hit
             }
             fn name(&self) -> &'static str {
@@ -293,11 +616,11 @@ mod generated {
 
         impl Event for OnDamageDealt {
             type EventReturnType = Nothing;
-            type ContextType = Nothing;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_damage_dealt
+            type ContextType = DamageContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_damage_dealt
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_damage_dealt
             }
             fn name(&self) -> &'static str {
@@ -310,10 +633,10 @@ mod generated {
         impl Event for OnTryActivateAbility {
             type EventReturnType = Outcome;
             type ContextType = AbilityUseContext;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_try_activate_ability
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_try_activate_ability
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_try_activate_ability
             }
             fn name(&self) -> &'static str {
@@ -326,10 +649,10 @@ mod generated {
         impl Event for OnAbilityActivated {
             type EventReturnType = Nothing;
             type ContextType = AbilityUseContext;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_ability_activated
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_ability_activated
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_ability_activated
             }
             fn name(&self) -> &'static str {
@@ -337,15 +660,47 @@ mod generated {
             }
         }
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnTryUseItem;
+
+        impl Event for OnTryUseItem {
+            type EventReturnType = Outcome;
+            type ContextType = ItemUseContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_try_use_item
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_try_use_item
+            }
+            fn name(&self) -> &'static str {
+                "OnTryUseItem"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnItemUsed;
+
+        impl Event for OnItemUsed {
+            type EventReturnType = Nothing;
+            type ContextType = ItemUseContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_item_used
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_item_used
+            }
+            fn name(&self) -> &'static str {
+                "OnItemUsed"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub struct OnModifyAccuracy;
 
         impl Event for OnModifyAccuracy {
             type EventReturnType = Percent;
-            type ContextType = MoveUseContext;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_modify_accuracy
+            type ContextType = (MoveHitContext, Percent);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_accuracy
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_modify_accuracy
             }
             fn name(&self) -> &'static str {
@@ -353,15 +708,31 @@ mod generated {
             }
         }
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnMoveMissed;
+
+        impl Event for OnMoveMissed {
+            type EventReturnType = Nothing;
+            type ContextType = MoveHitContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_move_missed
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_move_missed
+            }
+            fn name(&self) -> &'static str {
+                "OnMoveMissed"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub struct OnTryRaiseStat;
 
         impl Event for OnTryRaiseStat {
             type EventReturnType = Outcome;
-            type ContextType = Nothing;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_try_raise_stat
+            type ContextType = StatChangeContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_try_raise_stat
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_try_raise_stat
             }
             fn name(&self) -> &'static str {
@@ -373,11 +744,11 @@ mod generated {
 
         impl Event for OnTryLowerStat {
             type EventReturnType = Outcome;
-            type ContextType = Nothing;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_try_lower_stat
+            type ContextType = StatChangeContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_try_lower_stat
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_try_lower_stat
             }
             fn name(&self) -> &'static str {
@@ -390,15 +761,239 @@ mod generated {
         impl Event for OnStatusMoveUsed {
             type EventReturnType = Nothing;
             type ContextType = MoveUseContext;
-            fn corresponding_handler(&self, event_handler_deck: EventHandlerDeck) -> Option<EventHandler<Self>> {
-                event_handler_deck.on_status_move_used
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_status_move_used
             }
-            fn corresponding_handler_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut Option<EventHandler<Self>> {
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
                 &mut event_handler_deck.on_status_move_used
             }
             fn name(&self) -> &'static str {
                 "OnStatusMoveUsed"
             }
         }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyStat;
+
+        impl Event for OnModifyStat {
+            type EventReturnType = u16;
+            type ContextType = (StatContext, u16);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_stat
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_stat
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyStat"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyBasePower;
+
+        impl Event for OnModifyBasePower {
+            type EventReturnType = u16;
+            type ContextType = (MoveHitContext, u16);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_base_power
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_base_power
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyBasePower"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyAttackStat;
+
+        impl Event for OnModifyAttackStat {
+            type EventReturnType = u16;
+            type ContextType = (MoveHitContext, u16);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_attack_stat
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_attack_stat
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyAttackStat"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyDefenseStat;
+
+        impl Event for OnModifyDefenseStat {
+            type EventReturnType = u16;
+            type ContextType = (MoveHitContext, u16);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_defense_stat
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_defense_stat
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyDefenseStat"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyStab;
+
+        impl Event for OnModifyStab {
+            type EventReturnType = Percent;
+            type ContextType = (MoveHitContext, Percent);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_stab
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_stab
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyStab"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyTypeEffectiveness;
+
+        impl Event for OnModifyTypeEffectiveness {
+            type EventReturnType = Percent;
+            type ContextType = (MoveHitContext, Percent);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_type_effectiveness
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_type_effectiveness
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyTypeEffectiveness"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyFinalDamage;
+
+        impl Event for OnModifyFinalDamage {
+            type EventReturnType = u16;
+            type ContextType = (MoveHitContext, u16);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_final_damage
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_final_damage
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyFinalDamage"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnModifyCritRatio;
+
+        impl Event for OnModifyCritRatio {
+            type EventReturnType = u8;
+            type ContextType = (MoveHitContext, u8);
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_modify_crit_ratio
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_modify_crit_ratio
+            }
+            fn name(&self) -> &'static str {
+                "OnModifyCritRatio"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnCriticalHit;
+
+        impl Event for OnCriticalHit {
+            type EventReturnType = Nothing;
+            type ContextType = MoveHitContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_critical_hit
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_critical_hit
+            }
+            fn name(&self) -> &'static str {
+                "OnCriticalHit"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnTryInflictStatus;
+
+        impl Event for OnTryInflictStatus {
+            type EventReturnType = Outcome;
+            type ContextType = StatusContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_try_inflict_status
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_try_inflict_status
+            }
+            fn name(&self) -> &'static str {
+                "OnTryInflictStatus"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnStatusInflicted;
+
+        impl Event for OnStatusInflicted {
+            type EventReturnType = Nothing;
+            type ContextType = StatusContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_status_inflicted
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_status_inflicted
+            }
+            fn name(&self) -> &'static str {
+                "OnStatusInflicted"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnSwitchIn;
+
+        impl Event for OnSwitchIn {
+            type EventReturnType = Nothing;
+            type ContextType = SwitchInContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_switch_in
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_switch_in
+            }
+            fn name(&self) -> &'static str {
+                "OnSwitchIn"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnTurnStart;
+
+        impl Event for OnTurnStart {
+            type EventReturnType = Nothing;
+            type ContextType = TurnContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_turn_start
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_turn_start
+            }
+            fn name(&self) -> &'static str {
+                "OnTurnStart"
+            }
+        }
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct OnTurnEnd;
+
+        impl Event for OnTurnEnd {
+            type EventReturnType = Nothing;
+            type ContextType = TurnContext;
+            fn corresponding_handlers<'a>(&self, event_handler_deck: &'a EventHandlerDeck) -> &'a EventHandlers<Self> {
+                &event_handler_deck.on_turn_end
+            }
+            fn corresponding_handlers_mut<'a>(&self, event_handler_deck: &'a mut EventHandlerDeck) -> &'a mut EventHandlers<Self> {
+                &mut event_handler_deck.on_turn_end
+            }
+            fn name(&self) -> &'static str {
+                "OnTurnEnd"
+            }
+        }
     }
 }
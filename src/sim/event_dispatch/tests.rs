@@ -1,4 +1,4 @@
-use crate::{prng::Prng, test_monster_dex::Zombler};
+use crate::test_monster_dex::Zombler;
 
 #[test]
 fn test_if_priority_sorting_is_deterministic() {
@@ -10,7 +10,7 @@ fn test_if_priority_sorting_is_deterministic() {
         test_move_dex::{Bubble, Ember, Scratch, Tackle},
     };
     let mut result = [Vec::new(), Vec::new()];
-    for i in 0..=1 {
+    for handler_owner_names in &mut result {
         let test_battle = BattleState::spawn()
             .add_ally_team(
                 MonsterTeam::spawn()
@@ -53,9 +53,9 @@ fn test_if_priority_sorting_is_deterministic() {
         let mut owned_event_handlers = sim.battle.event_handlers_for(OnTryMoveHit);
         crate::sim::ordering::sort_by_activation_order(&mut prng, &mut owned_event_handlers, |it| it.activation_order);
 
-        result[i] = owned_event_handlers
+        *handler_owner_names = owned_event_handlers
             .into_iter()
-            .map(|event_handler| sim.battle.monster(event_handler.owner_id).name())
+            .map(|event_handler| sim.battle.monster(event_handler.owner.monster_id().unwrap()).name())
             .collect::<Vec<_>>();
     }
 
@@ -182,7 +182,7 @@ fn test_priority_sorting_with_speed_ties() {
 
         result[i] = owned_event_handlers
             .into_iter()
-            .map(|owned_event_handler| sim.battle.monster(owned_event_handler.owner_id).name())
+            .map(|owned_event_handler| sim.battle.monster(owned_event_handler.owner.monster_id().unwrap()).name())
             .collect::<Vec<_>>();
     }
 
@@ -271,14 +271,17 @@ fn test_filtering_events_by_broadcaster_relations() {
 #[test]
 #[cfg(feature = "debug")]
 fn test_print_owned_event_handler() {
-    use crate::sim::{test_ability_dex::FlashFire, event_dispatch::OwnedEventHandler, MonsterID};
+    use crate::sim::{test_ability_dex::FlashFire, event_dispatch::{EventHandlerOwner, OwnedEventHandler}, MonsterID};
     let owned_event_handler = OwnedEventHandler {
-        event_handler: FlashFire.event_handlers().on_try_move_hit.unwrap(),
+        event_handler: FlashFire.event_handlers().on_try_move_hit[0],
         activation_order: crate::ActivationOrder { priority: 0, speed: 11, order: FlashFire.order() },
-        owner_id: MonsterID {
+        owner: EventHandlerOwner::Monster(MonsterID {
             team_id: crate::sim::TeamID::Allies,
             monster_number: crate::sim::MonsterNumber::_1,
-        },
+        }),
+        source: crate::sim::EventHandlerSource::Ability(crate::sim::AbilityID {
+            owner_id: MonsterID { team_id: crate::sim::TeamID::Allies, monster_number: crate::sim::MonsterNumber::_1 },
+        }),
         filtering_options: crate::sim::EventFilteringOptions::default(),
     };
     println!("{:#?}", owned_event_handler);
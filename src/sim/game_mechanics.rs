@@ -1,20 +1,39 @@
 pub mod ability;
-#[cfg(feature = "debug")]
+pub mod field_state;
+#[cfg(any(test, feature = "debug"))]
+pub(crate) mod test_field_effect_dex;
+#[cfg(any(test, feature = "debug"))]
 pub(crate) mod test_ability_dex;
+pub mod item;
+#[cfg(any(test, feature = "debug"))]
+pub(crate) mod test_item_dex;
 pub mod monster;
-#[cfg(feature = "debug")]
+#[cfg(any(test, feature = "debug"))]
 pub(crate) mod test_monster_dex;
 pub mod move_;
-#[cfg(feature = "debug")]
+#[cfg(any(test, feature = "debug"))]
 pub(crate) mod test_move_dex;
+pub mod side_condition;
+#[cfg(any(test, feature = "debug"))]
+pub(crate) mod test_side_condition_dex;
+pub mod status;
 pub mod team;
+pub mod volatile_status;
+#[cfg(any(test, feature = "debug"))]
+pub(crate) mod test_volatile_status_dex;
 
 pub use ability::*;
+pub use field_state::*;
+pub use item::*;
 pub use monster::*;
 pub use move_::*;
+pub use side_condition::*;
+pub use status::*;
 pub use team::*;
+pub use volatile_status::*;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     Bug,
     Dark,
@@ -4,8 +4,10 @@ use crate::{sim::{event_dispatch::EventFilteringOptions, EventHandlerDeck, Monst
 use core::fmt::Debug;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ability {
     pub(crate) id: AbilityID, 
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
     pub(crate) species: &'static AbilitySpecies,
 }
 
@@ -43,6 +45,7 @@ impl Ability {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AbilityID {
     pub owner_id: MonsterID
 }
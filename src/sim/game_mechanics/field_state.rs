@@ -0,0 +1,196 @@
+use monsim_utils::Nothing;
+
+use crate::{sim::{EventHandlerDeck, MonsterID, SpeciesText}, Effect};
+use core::fmt::Debug;
+
+/// The state of the battlefield shared by every Monster in the battle, the current weather and terrain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldState {
+    pub(crate) weather: Option<FieldEffect>,
+    pub(crate) terrain: Option<FieldEffect>,
+}
+
+impl FieldState {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    pub fn weather(&self) -> Option<FieldEffect> {
+        self.weather
+    }
+
+    #[inline(always)]
+    pub fn terrain(&self) -> Option<FieldEffect> {
+        self.terrain
+    }
+
+    pub fn is_weather(&self, species: &FieldEffectSpecies) -> bool {
+        self.weather.is_some_and(|weather| weather.species() == species)
+    }
+
+    pub fn is_terrain(&self, species: &FieldEffectSpecies) -> bool {
+        self.terrain.is_some_and(|terrain| terrain.species() == species)
+    }
+
+    pub(crate) fn field_effects(&self) -> impl Iterator<Item = &FieldEffect> {
+        self.weather.iter().chain(self.terrain.iter())
+    }
+}
+
+/// A weather or terrain currently in effect on the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldEffect {
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+    pub(crate) species: &'static FieldEffectSpecies,
+    /// The number of turns left before the effect ends, `None` if it lasts indefinitely.
+    pub(crate) turns_remaining: Option<u8>,
+}
+
+impl FieldEffect {
+    pub(crate) fn new(species: &'static FieldEffectSpecies) -> Self {
+        Self {
+            species,
+            turns_remaining: species.duration,
+        }
+    }
+
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.species.event_handlers)()
+    }
+
+    #[inline(always)]
+    pub fn species(&self) -> &'static FieldEffectSpecies {
+        self.species
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.species.name
+    }
+
+    #[inline(always)]
+    pub fn turns_remaining(&self) -> Option<u8> {
+        self.turns_remaining
+    }
+}
+
+/// Which slot of the `FieldState` a field effect occupies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldEffectKind {
+    Weather,
+    Terrain,
+}
+
+/// The species of a weather or terrain, _e.g._ Rain or Electric Terrain.
+#[derive(Clone, Copy)]
+pub struct FieldEffectSpecies {
+    dex_number: u16,
+    name: &'static str,
+    kind: FieldEffectKind,
+    duration: Option<u8>,
+    start_message: &'static str,
+    end_message: &'static str,
+    end_of_turn_effect: Effect<Nothing, MonsterID>,
+    event_handlers: fn() -> EventHandlerDeck,
+    order: u16,
+}
+
+impl Debug for FieldEffectSpecies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:03} {}", self.dex_number, self.name)
+    }
+}
+
+impl PartialEq for FieldEffectSpecies {
+    fn eq(&self, other: &Self) -> bool {
+        self.dex_number == other.dex_number
+    }
+}
+
+impl Eq for FieldEffectSpecies {}
+
+impl FieldEffectSpecies {
+    pub const fn from_dex_data(dex_data: FieldEffectDexEntry) -> Self {
+        let FieldEffectDexEntry { dex_number, name, kind, duration, start_message, end_message, end_of_turn_effect, event_handlers, order } = dex_data;
+
+        Self {
+            dex_number,
+            name,
+            kind,
+            duration,
+            start_message,
+            end_message,
+            end_of_turn_effect,
+            event_handlers,
+            order,
+        }
+    }
+
+    #[inline(always)]
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.event_handlers)()
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn kind(&self) -> FieldEffectKind {
+        self.kind
+    }
+
+    #[inline(always)]
+    pub fn duration(&self) -> Option<u8> {
+        self.duration
+    }
+
+    /// The `SpeciesText` with the key `start_message`.
+    #[inline(always)]
+    pub fn start_message(&self) -> SpeciesText {
+        SpeciesText::new(self, "start_message", self.start_message)
+    }
+
+    /// The `SpeciesText` with the key `end_message`.
+    #[inline(always)]
+    pub fn end_message(&self) -> SpeciesText {
+        SpeciesText::new(self, "end_message", self.end_message)
+    }
+
+    #[inline(always)]
+    pub fn end_of_turn_effect(&self) -> Effect<Nothing, MonsterID> {
+        self.end_of_turn_effect
+    }
+
+    #[inline(always)]
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    #[inline(always)]
+    pub fn dex_number(&self) -> u16 {
+        self.dex_number
+    }
+}
+
+pub struct FieldEffectDexEntry {
+    pub dex_number: u16,
+    pub name: &'static str,
+    /// Whether the effect is a weather or a terrain, only `SetWeather` or `SetTerrain` respectively can start it.
+    pub kind: FieldEffectKind,
+    /// The number of turns the effect lasts for, `None` if it lasts until replaced or cleared.
+    pub duration: Option<u8>,
+    /// The template of the message logged when the effect starts.
+    pub start_message: &'static str,
+    /// The template of the message logged when the effect ends.
+    pub end_message: &'static str,
+    /// Resolved for each active Monster at the end of every turn the effect is active, the context is the Monster.
+    pub end_of_turn_effect: Effect<Nothing, MonsterID>,
+    /// Field effect handlers respond to events broadcast by any Monster, and are resolved with the broadcaster as the effector.
+    pub event_handlers: fn() -> EventHandlerDeck,
+    pub order: u16,
+}
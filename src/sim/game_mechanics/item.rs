@@ -0,0 +1,150 @@
+use monsim_utils::Nothing;
+
+use crate::{sim::{event_dispatch::EventFilteringOptions, EventHandlerDeck, MonsterID}, Effect, ItemUseContext};
+use core::fmt::Debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Item {
+    pub(crate) id: ItemID, 
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+    pub(crate) species: &'static ItemSpecies,
+}
+
+impl Item {
+
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.species.event_handlers)()
+    }
+    
+    #[inline(always)]
+    pub fn species(&self) -> & 'static ItemSpecies {
+        self.species
+    }
+    
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.species.name
+    }
+    
+    #[inline(always)]
+    pub fn order(&self) -> u16 {
+        self.species.order
+    }
+    
+    #[inline(always)]
+    pub fn dex_number(&self) -> u16 {
+        self.species.dex_number
+    }
+
+    #[inline(always)]
+    pub fn is_consumable(&self) -> bool {
+        self.species.is_consumable
+    }
+    
+    #[inline(always)]
+    pub fn on_use_effect(&self) -> Effect<Nothing, ItemUseContext> {
+        self.species.on_use_effect
+    }
+    
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemID {
+    pub owner_id: MonsterID
+}
+impl ItemID {
+    pub(crate) fn from_owner(item_holder: MonsterID) -> ItemID {
+        ItemID { owner_id: item_holder }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ItemSpecies {
+    dex_number: u16,
+    name: &'static str,
+    is_consumable: bool,
+    on_use_effect: Effect<Nothing, ItemUseContext>,
+    event_handlers: fn() -> EventHandlerDeck,
+    event_filtering_options: EventFilteringOptions,
+    order: u16,
+}
+
+
+impl Debug for ItemSpecies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:03} {}", self.dex_number, self.name)
+    }
+}
+
+impl PartialEq for ItemSpecies {
+    fn eq(&self, other: &Self) -> bool {
+        self.dex_number == other.dex_number
+    }
+}
+
+impl Eq for ItemSpecies {}
+
+impl ItemSpecies {
+    pub const fn from_dex_data(dex_data: ItemDexEntry) -> Self {
+        let ItemDexEntry { dex_number, name, is_consumable, on_use_effect, event_handlers, event_filtering_options, order } = dex_data;
+        
+        Self {
+            dex_number,
+            name,
+            is_consumable,
+            on_use_effect,
+            event_handlers,
+            event_filtering_options,
+            order,
+        }
+    }
+
+    #[inline(always)]
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.event_handlers)()
+    }
+    
+    #[inline(always)]
+    pub fn on_use_effect(&self) -> Effect<Nothing, ItemUseContext> {
+        self.on_use_effect
+    }
+    
+    #[inline(always)]
+    pub fn name(&self) -> & 'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn is_consumable(&self) -> bool {
+        self.is_consumable
+    }
+    
+    #[inline(always)]
+    pub fn event_filtering_options(&self) -> EventFilteringOptions {
+        self.event_filtering_options
+    }
+    
+    #[inline(always)]
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+    
+    #[inline(always)]
+    pub fn dex_number(&self) -> u16 {
+        self.dex_number
+    }
+    
+}
+
+pub struct ItemDexEntry {
+    pub dex_number: u16,
+    pub name: &'static str,
+    /// Consumable items, like berries, are used up and removed from their holder after being used once.
+    pub is_consumable: bool,
+    pub on_use_effect: Effect<Nothing, ItemUseContext>,
+    pub event_handlers: fn() -> EventHandlerDeck,
+    pub event_filtering_options: EventFilteringOptions,
+    pub order: u16,
+}
@@ -2,16 +2,20 @@ use core::{fmt::Debug, panic};
 use std::{fmt::{Display, Formatter}, ops::{Index, IndexMut}};
 
 use monsim_utils::MaxSizedVec;
-use tap::Pipe;
 
-use super::{Ability, TeamID};
-use crate::{sim::{targetting::{BoardPosition, FieldPosition}, ActivationOrder, EventFilteringOptions, EventHandlerDeck, Type}, Event, Move, OwnedEventHandler};
+use super::{Ability, Item, MajorStatus, TeamID, VolatileStatus, VolatileStatusSpecies};
+use crate::{sim::{targetting::{BoardPosition, FieldPosition}, ActivationOrder, EventFilteringOptions, EventHandlerDeck, Type}, Event, EventHandlerOwner, EventHandlerSource, Move, OwnedEventHandler};
+
+/// Aliased so that serde doesn't try to borrow nicknames from the input when deserializing.
+type Nickname = Option<&'static str>;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Monster {
     pub(crate) id: MonsterID,
     
-    pub(crate) nickname: Option<&'static str>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::static_str_option"))]
+    pub(crate) nickname: Nickname,
     pub(crate) effort_values: StatSet,
     pub(crate) current_health: u16,
     pub(crate) individual_values: StatSet,
@@ -19,10 +23,18 @@ pub struct Monster {
     pub(crate) nature: MonsterNature,
     pub(crate) board_position: BoardPosition,
     pub(crate) stat_modifiers: StatModifierSet,
+    pub(crate) status: Option<MajorStatus>,
+    /// Counts turns for statuses that need it, the remaining turns of sleep or the number of turns spent badly poisoned.
+    pub(crate) status_turn_counter: u8,
+    pub(crate) volatile_statuses: Vec<VolatileStatus>,
+    /// Critical hit stages gained from moves like Focus Energy, reset when the Monster switches out.
+    pub(crate) crit_stage: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
     pub(crate) species: &'static MonsterSpecies,
     
     pub(crate) moveset: MaxSizedVec<Move, 4>,
     pub(crate) ability: Ability,
+    pub(crate) held_item: Option<Item>,
 }
 
 impl PartialEq for Monster {
@@ -66,6 +78,11 @@ impl Display for Monster {
         out.push_str("\t│\t├── ");
         out.push_str(format!["abl {}\n", self.ability.name()].as_str());
 
+        if let Some(held_item) = self.held_item {
+            out.push_str("\t│\t├── ");
+            out.push_str(format!["itm {}\n", held_item.name()].as_str());
+        }
+
         for (i, move_) in self.moveset.into_iter().enumerate() {
             if i < number_of_effects - 1 {
                 out.push_str("\t│\t├── ");
@@ -100,13 +117,49 @@ impl Monster { // public
         Monster::calculate_max_health(self.species.base_stat(Stat::Hp), self.individual_values[Stat::Hp], self.effort_values[Stat::Hp], self.level)
     }
 
+    /// Returns the value of `stat` after applying the Monster's current stat stage for that stat.
+    /// Modifications to the stat from Abilities and Items are not included, the simulator applies 
+    /// those using `BattleSimulator::effective_stat`.
+    /// 
+    /// Accuracy and Evasion only exist as stat stages, so their value is always 0. Use `Monster::stat_modifier`
+    /// for their stages.
+    /// 
+    /// Paralysis halving Speed is also applied here.
     #[inline(always)]
     pub fn stat(&self, stat: Stat) -> u16 {
         match stat {
             Stat::Hp => self.max_health(),
+            _ => self.stat_at_stage(stat, self.stat_modifiers[stat]),
+        }
+    }
+
+    /// Returns the value of `stat` as if the Monster's stat stage for that stat was `stage`. Critical
+    /// hits use this to ignore certain stat stages.
+    pub fn stat_at_stage(&self, stat: Stat, stage: i8) -> u16 {
+        match stat {
+            Stat::Hp => self.max_health(),
+            _ => {
+                let (numerator, denominator) = stat_stage_multiplier(stage);
+                let staged_stat = self.unmodified_stat(stat) * numerator / denominator;
+                if stat == Stat::Speed && self.status == Some(MajorStatus::Paralysis) {
+                    staged_stat / 2
+                } else {
+                    staged_stat
+                }
+            }
+        }
+    }
+
+    /// Returns the value of `stat` as calculated from the Monster's base stats, IVs, EVs, level
+    /// and nature, ignoring any stat stages.
+    #[inline(always)]
+    pub fn unmodified_stat(&self, stat: Stat) -> u16 {
+        match stat {
+            Stat::Hp => self.max_health(),
+            Stat::Accuracy | Stat::Evasion => 0,
             _ => {
-                // TODO: Division is supposed to be floating point here.
-                ((2 * self.species.base_stats[stat] + self.individual_values[stat] + (self.effort_values[stat] / 4)) * self.level) / 100 + 5 // * self.nature[stat]
+                let raw_stat = ((2 * self.species.base_stats[stat] + self.individual_values[stat] + (self.effort_values[stat] / 4)) * self.level) / 100 + 5;
+                (raw_stat as f64 * self.nature[stat]) as u16
             }
         }
     }
@@ -126,9 +179,38 @@ impl Monster { // public
         self.current_health == 0
     }
 
+    /// Returns the Monster's current stage for `stat`. HP has no stat stage, so its stage is always 0.
     #[inline(always)]
     pub fn stat_modifier(&self, stat: Stat) -> i8 {
-        self.stat_modifiers[stat]
+        match stat {
+            Stat::Hp => 0,
+            _ => self.stat_modifiers[stat],
+        }
+    }
+
+    #[inline(always)]
+    pub fn crit_stage(&self) -> u8 {
+        self.crit_stage
+    }
+
+    #[inline(always)]
+    pub fn status(&self) -> Option<MajorStatus> {
+        self.status
+    }
+
+    #[inline(always)]
+    pub fn has_status(&self, status: MajorStatus) -> bool {
+        self.status == Some(status)
+    }
+
+    #[inline(always)]
+    pub fn volatile_statuses(&self) -> &[VolatileStatus] {
+        &self.volatile_statuses
+    }
+
+    #[inline(always)]
+    pub fn has_volatile_status(&self, species: &VolatileStatusSpecies) -> bool {
+        self.volatile_statuses.iter().any(|volatile_status| volatile_status.species() == species)
     }
 
     #[inline(always)]
@@ -136,6 +218,11 @@ impl Monster { // public
         &self.ability
     }
 
+    #[inline(always)]
+    pub fn held_item(&self) -> Option<&Item> {
+        self.held_item.as_ref()
+    }
+
     #[inline(always)]
     pub fn moveset(&self) -> &MaxSizedVec<Move, 4> {
         &self.moveset
@@ -175,70 +262,111 @@ impl Monster { // private
         ((2 * base_hp + hp_iv + (hp_ev / 4)) * level) / 100 + level + 10
     }
 
-    pub(crate) fn ability_event_handler_for<E: Event>(&self, event: E) -> Option<OwnedEventHandler<E>> {
-        event.corresponding_handler(self.ability.event_handlers()) 
-            .map(|event_handler| { // Add an OwnedEventHandler if an EventHandler exists.
-                OwnedEventHandler {
-                    event_handler,
-                    owner_id: self.id,
-                    activation_order: ActivationOrder {
+    pub(crate) fn ability_event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
+        event.corresponding_handlers(&self.ability.event_handlers())
+            .into_iter()
+            .map(|event_handler| {
+                event_handler.owned_by(
+                    EventHandlerOwner::Monster(self.id),
+                    EventHandlerSource::Ability(self.ability.id),
+                    ActivationOrder {
                         priority: 0,
                         speed: self.stat(Stat::Speed),
                         order: self.ability.order(),
                     },
-                    filtering_options: EventFilteringOptions::default(),
-                }
-            }
-        )
+                    self.ability.species().event_filtering_options(),
+                )
+            })
+            .collect::<Vec<_>>()
+    }
+
+    pub(crate) fn item_event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
+        let Some(held_item) = self.held_item else {
+            return Vec::new();
+        };
+        event.corresponding_handlers(&held_item.event_handlers())
+            .into_iter()
+            .map(|event_handler| {
+                event_handler.owned_by(
+                    EventHandlerOwner::Monster(self.id),
+                    EventHandlerSource::Item(held_item.id),
+                    ActivationOrder {
+                        priority: 0,
+                        speed: self.stat(Stat::Speed),
+                        order: held_item.order(),
+                    },
+                    held_item.species().event_filtering_options(),
+                )
+            })
+            .collect::<Vec<_>>()
     }
 
     pub(crate) fn moveset_event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
         self.moveset
             .iter()
-            .filter_map(|move_| {
-                event.corresponding_handler(move_.event_handlers()) 
-                    .map(|event_handler| { // Add an OwnedEventHandler if an EventHandler exists.
-                        OwnedEventHandler {
-                            event_handler,
-                            owner_id: self.id,
-                            activation_order: ActivationOrder {
+            .flat_map(|move_| {
+                event.corresponding_handlers(&move_.event_handlers())
+                    .into_iter()
+                    .map(|event_handler| {
+                        event_handler.owned_by(
+                            EventHandlerOwner::Monster(self.id),
+                            EventHandlerSource::Move(move_.id),
+                            ActivationOrder {
                                 priority: move_.priority(),
                                 speed: self.stat(Stat::Speed),
                                 order: 0,
                             },
-                            filtering_options: EventFilteringOptions::default(),
-                        }
+                            EventFilteringOptions::default(),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+    }
+
+    pub(crate) fn volatile_status_event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
+        self.volatile_statuses
+            .iter()
+            .flat_map(|volatile_status| {
+                event.corresponding_handlers(&volatile_status.event_handlers())
+                    .into_iter()
+                    .map(|event_handler| {
+                        event_handler.owned_by(
+                            EventHandlerOwner::Monster(self.id),
+                            EventHandlerSource::VolatileStatus(self.id, volatile_status.species()),
+                            ActivationOrder {
+                                priority: 0,
+                                speed: self.stat(Stat::Speed),
+                                order: volatile_status.species().order(),
+                            },
+                            volatile_status.species().event_filtering_options(),
+                        )
                     })
                 })
                 .collect::<Vec<_>>()
     }
 
+    /// Every handler for `event` on the Monster, its ability, held item, moves and volatile statuses.
+    /// Each of these can have several handlers for the same event.
     pub(crate) fn event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
-        let mut out = Vec::new();
-        event.corresponding_handler((self.species.event_handlers)()) 
-            .map(|event_handler| { // Add an OwnedEventHandler if an EventHandler exists.
-                OwnedEventHandler {
-                    event_handler,
-                    owner_id: self.id,
-                    activation_order:  ActivationOrder {
+        let mut out = event.corresponding_handlers(&self.species.event_handlers())
+            .into_iter()
+            .map(|event_handler| {
+                event_handler.owned_by(
+                    EventHandlerOwner::Monster(self.id),
+                    EventHandlerSource::Monster(self.id),
+                    ActivationOrder {
                         priority: 0,
                         speed: self.stat(Stat::Speed),
                         order: 0,
                     },
-                    filtering_options: EventFilteringOptions::default(),
-                }
+                    EventFilteringOptions::default(),
+                )
             })
-            .pipe(|optional_owned_event_handler| { // TODO: pipe_if_some
-                if let Some(owned_event_handler) = optional_owned_event_handler {
-                    out.push(owned_event_handler);
-                }
-            });
-        self.ability_event_handler_for(event).pipe(|optional_owned_event_handler| {
-            if let Some(owned_event_handler) = optional_owned_event_handler {
-                out.push(owned_event_handler)
-            }
-        });
+            .collect::<Vec<_>>();
+        out.append(&mut self.ability_event_handlers_for(event));
+        out.append(&mut self.item_event_handlers_for(event));
         out.append(&mut self.moveset_event_handlers_for(event));
+        out.append(&mut self.volatile_status_event_handlers_for(event));
         out
     }
 
@@ -253,9 +381,13 @@ impl Monster { // private
     pub(crate) fn status_string(&self) -> String {
         let mut out = String::new();
         out.push_str(&format![
-            "{} ({}) [HP: {}/{}] @ {}\n",
-            self.full_name(), self.id, self.current_health, self.max_health(), self.board_position
+            "{} ({}) [HP: {}/{}]",
+            self.full_name(), self.id, self.current_health, self.max_health()
         ]);
+        if let Some(status) = self.status {
+            out.push_str(&format![" [{status}]"]);
+        }
+        out.push_str(&format![" @ {}\n", self.board_position]);
         out
     }
 }
@@ -271,12 +403,14 @@ pub struct MonsterSpecies {
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MonsterID {
     pub team_id: TeamID,
     pub monster_number: MonsterNumber,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MonsterNumber {
     #[default]
     _1,
@@ -403,6 +537,10 @@ impl MonsterSpecies {
         (self.event_handlers)()
     }
 
+    #[inline(always)]
+    pub fn dex_number(&self) -> u16 {
+        self.dex_number
+    }
 }
 
 impl From<usize> for MonsterNumber {
@@ -420,6 +558,7 @@ impl From<usize> for MonsterNumber {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Stat {
     Hp,
     PhysicalAttack,
@@ -427,9 +566,14 @@ pub enum Stat {
     SpecialAttack,
     SpecialDefense,
     Speed,
+    /// Accuracy only exists as a stat stage, it has no base value.
+    Accuracy,
+    /// Evasion only exists as a stat stage, it has no base value.
+    Evasion,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatSet {
     hp: u16,
     att: u16,
@@ -440,12 +584,15 @@ pub struct StatSet {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatModifierSet {
     att: i8,
     def: i8,
     spa: i8,
     spd: i8,
     spe: i8,
+    acc: i8,
+    eva: i8,
 }
 
 impl Debug for MonsterSpecies {
@@ -487,6 +634,8 @@ impl Index<Stat> for StatSet {
             Stat::SpecialAttack => &self.spa,
             Stat::SpecialDefense => &self.spd,
             Stat::Speed => &self.spe,
+            // Accuracy and Evasion have no base value.
+            Stat::Accuracy | Stat::Evasion => &0,
         }
     }
 }
@@ -508,6 +657,8 @@ impl Index<Stat> for StatModifierSet {
             Stat::SpecialAttack => &self.spa,
             Stat::SpecialDefense => &self.spd,
             Stat::Speed => &self.spe,
+            Stat::Accuracy => &self.acc,
+            Stat::Evasion => &self.eva,
         }
     }
 }
@@ -521,13 +672,64 @@ impl IndexMut<Stat> for StatModifierSet {
             Stat::SpecialAttack => &mut self.spa,
             Stat::SpecialDefense => &mut self.spd,
             Stat::Speed => &mut self.spe,
+            Stat::Accuracy => &mut self.acc,
+            Stat::Evasion => &mut self.eva,
         }
     }
 }
 
+/// The multiplier applied to a stat at a given stat stage, as a `(numerator, denominator)` pair.
+/// Indexed by `stage + 6`, so that the first entry corresponds to stage -6 and the last to stage +6.
+const STAT_STAGE_MULTIPLIERS: [(u16, u16); 13] = [
+    (2, 8),
+    (2, 7),
+    (2, 6),
+    (2, 5),
+    (2, 4),
+    (2, 3),
+    (2, 2),
+    (3, 2),
+    (4, 2),
+    (5, 2),
+    (6, 2),
+    (7, 2),
+    (8, 2),
+];
+
+/// Returns the `(numerator, denominator)` pair to multiply a stat by at the given `stage`.
+pub fn stat_stage_multiplier(stage: i8) -> (u16, u16) {
+    assert!((-6..=6).contains(&stage), "Stat stages are expected to be between -6 and +6, found {stage}.");
+    STAT_STAGE_MULTIPLIERS[(stage + 6) as usize]
+}
+
+/// The multiplier applied to a move's accuracy at a given combined accuracy/evasion stage, as a 
+/// `(numerator, denominator)` pair. Indexed the same way as `STAT_STAGE_MULTIPLIERS`.
+const ACCURACY_STAGE_MULTIPLIERS: [(u16, u16); 13] = [
+    (3, 9),
+    (3, 8),
+    (3, 7),
+    (3, 6),
+    (3, 5),
+    (3, 4),
+    (3, 3),
+    (4, 3),
+    (5, 3),
+    (6, 3),
+    (7, 3),
+    (8, 3),
+    (9, 3),
+];
+
+/// Returns the `(numerator, denominator)` pair to multiply a move's accuracy by at the given `stage`,
+/// which is the move user's accuracy stage minus the target's evasion stage, clamped to [-6, 6].
+pub fn accuracy_stage_multiplier(stage: i8) -> (u16, u16) {
+    assert!((-6..=6).contains(&stage), "Accuracy stages are expected to be between -6 and +6, found {stage}.");
+    ACCURACY_STAGE_MULTIPLIERS[(stage + 6) as usize]
+}
+
 impl StatModifierSet {
-    pub const fn new(att: i8, def: i8, spa: i8, spd: i8, spe: i8) -> Self {
-        Self { att, def, spa, spd, spe }
+    pub const fn new(att: i8, def: i8, spa: i8, spd: i8, spe: i8, acc: i8, eva: i8) -> Self {
+        Self { att, def, spa, spd, spe, acc, eva }
     }
 
     pub fn raise_stat(&mut self, stat: Stat, by_stages: u8) -> u8 {
@@ -560,11 +762,14 @@ impl Display for Stat {
             Stat::SpecialAttack => write!(f, "Special Attack"),
             Stat::SpecialDefense => write!(f, "Special Defense"),
             Stat::Speed => write!(f, "Speed"),
+            Stat::Accuracy => write!(f, "Accuracy"),
+            Stat::Evasion => write!(f, "Evasiveness"),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MonsterNature {
     /// Neutral (+-Attack)
     Hardy,
@@ -679,7 +884,7 @@ impl Index<Stat> for MonsterNature {
 
             MonsterNature::Lax => match index {
                 Stat::PhysicalDefense => &RAISED,
-                Stat::SpecialAttack => &LOWERED,
+                Stat::SpecialDefense => &LOWERED,
                 _ => &NEUTRAL,
             },
 
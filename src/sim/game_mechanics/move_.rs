@@ -1,12 +1,11 @@
-use monsim_utils::Nothing;
-
-use crate::{sim::{event_dispatch::{EventFilteringOptions, EventHandlerDeck}, Type}, Effect, MonsterID, MoveHitContext, TargetFlags};
+use crate::{sim::{event_dispatch::{EventFilteringOptions, EventHandlerDeck}, EffectDescription, Type}, MonsterID, TargetFlags};
 use core::fmt::Debug;
-use std::ops::Range;
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     pub(crate) id: MoveID,
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
     pub(crate) species: &'static MoveSpecies, 
     
     pub(crate) current_power_points: u8,
@@ -18,7 +17,7 @@ impl Move {
         self.species.name
     }
 
-    pub fn on_hit_effect(&self) -> Effect<Nothing, MoveHitContext> {
+    pub fn on_hit_effect(&self) -> EffectDescription {
         self.species.on_hit_effect
     }
 
@@ -32,11 +31,18 @@ impl Move {
         self.species.base_power
     }
 
+    /// `None` means the move never misses.
     #[inline(always)]
-    pub fn base_accuracy(&self) -> u16 {
+    pub fn base_accuracy(&self) -> Option<u16> {
         self.species.base_accuracy
     }
 
+    /// The number of critical hit stages the move adds on top of the user's own.
+    #[inline(always)]
+    pub fn crit_ratio(&self) -> u8 {
+        self.species.crit_ratio
+    }
+
     #[inline(always)]
     pub fn current_power_points(&self) -> u8 {
         self.current_power_points
@@ -74,7 +80,7 @@ impl Move {
     
     #[inline(always)]
     pub(crate) fn event_handlers(&self) -> EventHandlerDeck {
-        (self.species.event_handlers)()
+        self.species.event_handlers()
     }
     
     pub(crate) fn hits_per_target(&self) -> Hits {
@@ -87,12 +93,13 @@ pub struct MoveSpecies {
     dex_number: u16,
     name: &'static str,
     
-    on_hit_effect: Effect<Nothing, MoveHitContext>,
+    on_hit_effect: EffectDescription,
     hits_per_target: Hits,
     
-    base_accuracy: u16,
+    base_accuracy: Option<u16>,
     base_power: u16,
     category: MoveCategory,
+    crit_ratio: u8,
     max_power_points: u8,
     priority: i8,
     targets: TargetFlags,
@@ -106,7 +113,7 @@ impl Debug for MoveSpecies {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "#{:03} {},\n\t type: {:?},\n\t base accuracy: {}",
+            "#{:03} {},\n\t type: {:?},\n\t base accuracy: {:?}",
             self.dex_number, self.name, self.type_, self.base_accuracy
         )
     }
@@ -129,6 +136,7 @@ impl MoveSpecies {
             base_accuracy, 
             base_power, 
             category, 
+            crit_ratio, 
             max_power_points, 
             priority, 
             targets, 
@@ -145,6 +153,7 @@ impl MoveSpecies {
             base_accuracy,
             base_power,
             category,
+            crit_ratio,
             max_power_points,
             priority,
             targets,
@@ -171,14 +180,23 @@ impl MoveSpecies {
     }
 
     #[inline(always)]
-    pub fn on_hit_effect(&self) -> Effect<Nothing, MoveHitContext> {
+    pub fn on_hit_effect(&self) -> EffectDescription {
         self.on_hit_effect
     }
-    
-    
+
+    #[inline(always)]
+    pub fn dex_number(&self) -> u16 {
+        self.dex_number
+    }
+
+    #[inline(always)]
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.event_handlers)()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoveCategory {
     Physical,
     Special,
@@ -186,6 +204,7 @@ pub enum MoveCategory {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoveNumber {
     _1,
     _2,
@@ -209,12 +228,15 @@ pub struct MoveDexEntry {
     pub dex_number: u16,
     pub name: &'static str,
 
-    pub on_hit_effect: Effect<Nothing, MoveHitContext>,
+    pub on_hit_effect: EffectDescription,
     pub hits_per_target: Hits,
     
-    pub base_accuracy: u16,
+    /// `None` means the move never misses.
+    pub base_accuracy: Option<u16>,
     pub base_power: u16,
     pub category: MoveCategory,
+    /// The number of critical hit stages the move adds, 0 for most moves and 1 for high critical hit ratio moves.
+    pub crit_ratio: u8,
     pub max_power_points: u8,
     pub priority: i8,
     pub targets: TargetFlags,
@@ -225,12 +247,14 @@ pub struct MoveDexEntry {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveID {
     pub owner_id: MonsterID,
     pub move_number: MoveNumber,
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Hits {
     /// Hits the target once
     Once,
@@ -239,13 +263,3 @@ pub enum Hits {
     /// Hits the target a random number of times in a range (inclusive on both ends).
     RandomlyInRange{ min: u8, max: u8 }
 }
-
-impl Hits {
-    pub(crate) fn to_range(&self) -> Range<u8> {
-        match *self {
-            Hits::Once => 0..1,
-            Hits::MultipleTimes(number_of_hits) => 0..number_of_hits,
-            Hits::RandomlyInRange { min: start, max: end } => start..end,
-        }
-    } 
-}
\ No newline at end of file
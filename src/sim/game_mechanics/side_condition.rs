@@ -0,0 +1,159 @@
+use crate::sim::{event_dispatch::EventFilteringOptions, EventHandlerDeck, SpeciesText};
+use core::fmt::Debug;
+
+/// A condition on one side of the battle that affects every Monster on that team, such as a screen
+/// or an entry hazard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SideCondition {
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+    pub(crate) species: &'static SideConditionSpecies,
+    /// The number of turns left before the condition ends, `None` if it lasts indefinitely.
+    pub(crate) turns_remaining: Option<u8>,
+    /// The number of times the condition has been stacked, for conditions like Spikes.
+    pub(crate) layers: u8,
+}
+
+impl SideCondition {
+    pub(crate) fn new(species: &'static SideConditionSpecies) -> Self {
+        Self {
+            species,
+            turns_remaining: species.duration,
+            layers: 1,
+        }
+    }
+
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.species.event_handlers)()
+    }
+
+    #[inline(always)]
+    pub fn species(&self) -> &'static SideConditionSpecies {
+        self.species
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.species.name
+    }
+
+    #[inline(always)]
+    pub fn turns_remaining(&self) -> Option<u8> {
+        self.turns_remaining
+    }
+
+    #[inline(always)]
+    pub fn layers(&self) -> u8 {
+        self.layers
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct SideConditionSpecies {
+    dex_number: u16,
+    name: &'static str,
+    duration: Option<u8>,
+    max_layers: u8,
+    start_message: &'static str,
+    end_message: &'static str,
+    event_handlers: fn() -> EventHandlerDeck,
+    event_filtering_options: EventFilteringOptions,
+    order: u16,
+}
+
+impl Debug for SideConditionSpecies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:03} {}", self.dex_number, self.name)
+    }
+}
+
+impl PartialEq for SideConditionSpecies {
+    fn eq(&self, other: &Self) -> bool {
+        self.dex_number == other.dex_number
+    }
+}
+
+impl Eq for SideConditionSpecies {}
+
+impl SideConditionSpecies {
+    pub const fn from_dex_data(dex_data: SideConditionDexEntry) -> Self {
+        let SideConditionDexEntry { dex_number, name, duration, max_layers, start_message, end_message, event_handlers, event_filtering_options, order } = dex_data;
+
+        Self {
+            dex_number,
+            name,
+            duration,
+            max_layers,
+            start_message,
+            end_message,
+            event_handlers,
+            event_filtering_options,
+            order,
+        }
+    }
+
+    #[inline(always)]
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.event_handlers)()
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn duration(&self) -> Option<u8> {
+        self.duration
+    }
+
+    #[inline(always)]
+    pub fn max_layers(&self) -> u8 {
+        self.max_layers
+    }
+
+    /// The `SpeciesText` with the key `start_message`.
+    #[inline(always)]
+    pub fn start_message(&self) -> SpeciesText {
+        SpeciesText::new(self, "start_message", self.start_message)
+    }
+
+    /// The `SpeciesText` with the key `end_message`.
+    #[inline(always)]
+    pub fn end_message(&self) -> SpeciesText {
+        SpeciesText::new(self, "end_message", self.end_message)
+    }
+
+    #[inline(always)]
+    pub fn event_filtering_options(&self) -> EventFilteringOptions {
+        self.event_filtering_options
+    }
+
+    #[inline(always)]
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    #[inline(always)]
+    pub fn dex_number(&self) -> u16 {
+        self.dex_number
+    }
+}
+
+pub struct SideConditionDexEntry {
+    pub dex_number: u16,
+    pub name: &'static str,
+    /// The number of turns the condition lasts for, `None` if it lasts until removed.
+    pub duration: Option<u8>,
+    /// How many times the condition can be stacked on the same side, 1 for conditions that don't stack.
+    pub max_layers: u8,
+    /// The template of the message logged when the condition is added, `{team}` being the team it was added to.
+    pub start_message: &'static str,
+    /// The template of the message logged when the condition is removed, `{team}` being the team it was removed from.
+    pub end_message: &'static str,
+    /// Side condition handlers are resolved with the broadcaster as the effector. For the purposes of filtering,
+    /// broadcasters on the side's own team count as `ALLIES` and broadcasters on the other team as `OPPONENTS`.
+    pub event_handlers: fn() -> EventHandlerDeck,
+    pub event_filtering_options: EventFilteringOptions,
+    pub order: u16,
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use super::Type;
+
+/// A persistent, non-volatile status condition. A Monster can only have one at a time, and it
+/// stays on the Monster when it switches out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MajorStatus {
+    /// Halves the damage of the Monster's physical moves and deals 1/16 of its max HP at the end of each turn.
+    Burn,
+    /// The Monster cannot move, but has a 20% chance to thaw out each time it tries to.
+    Freeze,
+    /// Halves the Monster's Speed and gives it a 25% chance to be unable to move each time it tries to.
+    Paralysis,
+    /// Deals 1/8 of the Monster's max HP at the end of each turn.
+    Poison,
+    /// Deals n/16 of the Monster's max HP at the end of each turn, where n is the number of turns it has been badly poisoned.
+    BadPoison,
+    /// The Monster cannot move for 1-3 turns.
+    Sleep,
+}
+
+impl MajorStatus {
+    /// Returns whether a Monster of type `type_` is immune to this status.
+    pub fn is_type_immune(&self, type_: Type) -> bool {
+        match self {
+            MajorStatus::Burn => type_ == Type::Fire,
+            MajorStatus::Freeze => type_ == Type::Ice,
+            MajorStatus::Paralysis => type_ == Type::Electric,
+            MajorStatus::Poison | MajorStatus::BadPoison => type_ == Type::Poison || type_ == Type::Steel,
+            MajorStatus::Sleep => false,
+        }
+    }
+
+    /// The text used in messages when the status is inflicted, as in "Dandyleo was burned!".
+    pub fn inflicted_text(&self) -> &'static str {
+        match self {
+            MajorStatus::Burn => "burned",
+            MajorStatus::Freeze => "frozen solid",
+            MajorStatus::Paralysis => "paralysed",
+            MajorStatus::Poison => "poisoned",
+            MajorStatus::BadPoison => "badly poisoned",
+            MajorStatus::Sleep => "put to sleep",
+        }
+    }
+}
+
+impl Display for MajorStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MajorStatus::Burn => write!(f, "BRN"),
+            MajorStatus::Freeze => write!(f, "FRZ"),
+            MajorStatus::Paralysis => write!(f, "PAR"),
+            MajorStatus::Poison => write!(f, "PSN"),
+            MajorStatus::BadPoison => write!(f, "TOX"),
+            MajorStatus::Sleep => write!(f, "SLP"),
+        }
+    }
+}
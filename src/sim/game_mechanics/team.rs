@@ -1,15 +1,17 @@
 use std::{fmt::{Debug, Display, Formatter}, ops::{Index, IndexMut}};
-use monsim_utils::{Ally, MaxSizedVec, Opponent};
+use monsim_utils::{not, Ally, MaxSizedVec, Opponent};
 
-use crate::{sim::{targetting::BoardPosition, MonsterNumber}, Event, OwnedEventHandler};
-use super::Monster;
+use crate::{sim::{targetting::BoardPosition, ActivationOrder, MonsterNumber}, Event, EventHandlerOwner, EventHandlerSource, OwnedEventHandler};
+use super::{Monster, SideCondition, SideConditionSpecies};
 
 const MAX_BATTLERS_PER_TEAM: usize = 6;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MonsterTeam {
     pub id: TeamID,
     monsters: MaxSizedVec<Monster, 6>,
+    pub(crate) side_conditions: Vec<SideCondition>,
 }
 
 impl Index<MonsterNumber> for MonsterTeam {
@@ -28,19 +30,20 @@ impl IndexMut<MonsterNumber> for MonsterTeam {
 
 impl MonsterTeam {
     pub fn new(monsters: Vec<Monster>, id: TeamID) -> Self {
-        assert!(monsters.first().is_some(), "There is not a single monster in the team.");
+        assert!(not!(monsters.is_empty()), "There is not a single monster in the team.");
         assert!(monsters.len() <= MAX_BATTLERS_PER_TEAM);
         let monsters = MaxSizedVec::from_vec(monsters);
         MonsterTeam {
             id,
-            monsters 
+            monsters,
+            side_conditions: Vec::new(),
         }
     }
 
     pub fn active_monsters(&self) -> Vec<&Monster> {
         self.monsters()
             .filter(|monster| {
-                if let BoardPosition::Field(_) = monster.board_position { true } else { false }
+                matches!(monster.board_position, BoardPosition::Field(_))
             })
             .collect()
     }
@@ -53,14 +56,49 @@ impl MonsterTeam {
         self.monsters.iter_mut()
     }
 
+    pub fn side_conditions(&self) -> &[SideCondition] {
+        &self.side_conditions
+    }
+
+    pub fn side_condition(&self, species: &SideConditionSpecies) -> Option<&SideCondition> {
+        self.side_conditions.iter().find(|side_condition| side_condition.species() == species)
+    }
+
+    pub fn has_side_condition(&self, species: &SideConditionSpecies) -> bool {
+        self.side_condition(species).is_some()
+    }
+
     pub fn event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
         let mut out = Vec::new();
         for monster in self.monsters.iter() {
             out.append(&mut monster.event_handlers_for(event))
         }
+        out.append(&mut self.side_condition_event_handlers_for(event));
         out
     }
 
+    fn side_condition_event_handlers_for<E: Event>(&self, event: E) -> Vec<OwnedEventHandler<E>> {
+        self.side_conditions
+            .iter()
+            .flat_map(|side_condition| {
+                event.corresponding_handlers(&side_condition.event_handlers())
+                    .into_iter()
+                    .map(|event_handler| {
+                        event_handler.owned_by(
+                            EventHandlerOwner::Side(self.id),
+                            EventHandlerSource::SideCondition(self.id, side_condition.species()),
+                            ActivationOrder {
+                                priority: 0,
+                                speed: 0,
+                                order: side_condition.species().order(),
+                            },
+                            side_condition.species().event_filtering_options(),
+                        )
+                    })
+            })
+            .collect::<Vec<_>>()
+    }
+
     pub(crate) fn team_status_string(&self) -> String {
         let mut out = String::new();
         for monster in self.monsters() {
@@ -71,6 +109,7 @@ impl MonsterTeam {
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TeamID {
     #[default]
     Allies,
@@ -97,6 +136,7 @@ impl Display for TeamID {
 
 /// A container for storing an object of type `T` for each team.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PerTeam<T> {
     ally_team_item: Ally<T>,
     opponent_team_item: Opponent<T>,
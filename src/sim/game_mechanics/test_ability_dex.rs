@@ -5,26 +5,39 @@ use monsim_utils::not;
 
 use super::{ability::AbilitySpecies, Type};
 use crate::{
-    effects::*, event_dex::*, sim::{event_dispatch::contexts::MoveUseContext, EventFilteringOptions, EventHandler, EventHandlerDeck, Outcome}, source_code_location, AbilityDexEntry, AbilityID, AbilityUseContext, MoveHitContext
+    effects::*, event_dex::*, sim::{event_dispatch::contexts::MoveUseContext, EventFilteringOptions, EventHandler, EventHandlerDeck, Outcome}, AbilityDexEntry, AbilityID, AbilityUseContext, MoveHitContext
 };
+#[cfg(feature = "debug")]
+use crate::source_code_location;
 
 pub const FlashFire: AbilitySpecies = AbilitySpecies::from_dex_data( 
     AbilityDexEntry {
         dex_number: 001,
         name: "Flash Fire",
         event_handlers: | | {
-            EventHandlerDeck::empty()
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
                 .add(OnTryMoveHit, |sim, effector_id, MoveHitContext { move_user_id, move_used_id, target_id}| {
                     if mov![move_used_id].is_type(Type::Fire) {
                         let activation_succeeded = ActivateAbility(sim, effector_id,AbilityUseContext::new(effector_id));
                         return not!(activation_succeeded);
                     }
                     Outcome::Success
-                }, source_code_location!())
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnTryMoveHit, |sim, effector_id, MoveHitContext { move_user_id, move_used_id, target_id}| {
+                    if mov![move_used_id].is_type(Type::Fire) {
+                        let activation_succeeded = ActivateAbility(sim, effector_id,AbilityUseContext::new(effector_id));
+                        return not!(activation_succeeded);
+                    }
+                    Outcome::Success
+                });
+            out
         },
         on_activate_effect: Effect::from(|sim, effector_id, AbilityUseContext { ability_used_id, ability_owner_id }| {
-            let effector_name = mon![effector_id].name();
-            sim.push_message(format!["{effector_name}'s Flash Fire activated!"]);
+            sim.log_species_message(&FlashFire, "activated", "{monster}'s Flash Fire activated!", effector_id);
         }),
         event_filtering_options: EventFilteringOptions::default(),
         order: 0,
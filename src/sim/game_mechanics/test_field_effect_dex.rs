@@ -0,0 +1,66 @@
+#![allow(non_upper_case_globals, clippy::zero_prefixed_literal, unused)]
+
+use monsim_macros::{mon, mov};
+
+use super::{FieldEffectDexEntry, FieldEffectKind, FieldEffectSpecies, Type};
+use crate::{
+    effects::*, event_dex::*, sim::{EventHandlerDeck, Percent}, Effect, MoveHitContext
+};
+#[cfg(feature = "debug")]
+use crate::source_code_location;
+
+pub const Rain: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 001,
+        name: "Rain",
+        kind: FieldEffectKind::Weather,
+        duration: Some(5),
+        start_message: "It started to rain!",
+        end_message: "The rain stopped.",
+        end_of_turn_effect: Effect::from(|_sim, _effector_id, _monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, effector_id, (MoveHitContext { move_user_id, move_used_id, target_id }, damage)| {
+                    if mov![move_used_id].is_type(Type::Water) {
+                        (damage as f64 * Percent(150)) as u16
+                    } else if mov![move_used_id].is_type(Type::Fire) {
+                        (damage as f64 * Percent(50)) as u16
+                    } else {
+                        damage
+                    }
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, effector_id, (MoveHitContext { move_user_id, move_used_id, target_id }, damage)| {
+                    if mov![move_used_id].is_type(Type::Water) {
+                        (damage as f64 * Percent(150)) as u16
+                    } else if mov![move_used_id].is_type(Type::Fire) {
+                        (damage as f64 * Percent(50)) as u16
+                    } else {
+                        damage
+                    }
+                });
+            out
+        },
+        order: 0,
+    }
+);
+
+pub const GrassyTerrain: FieldEffectSpecies = FieldEffectSpecies::from_dex_data(
+    FieldEffectDexEntry {
+        dex_number: 002,
+        name: "Grassy Terrain",
+        kind: FieldEffectKind::Terrain,
+        duration: Some(5),
+        start_message: "Grass grew to cover the battlefield!",
+        end_message: "The grass disappeared from the battlefield.",
+        end_of_turn_effect: Effect::from(|sim, _effector_id, monster_id| {
+            let amount = (mon![monster_id].max_health() / 16).max(1);
+            _ = RestoreHealth(sim, monster_id, (monster_id, amount));
+        }),
+        event_handlers: EventHandlerDeck::empty,
+        order: 0,
+    }
+);
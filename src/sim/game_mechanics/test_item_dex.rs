@@ -0,0 +1,83 @@
+#![allow(non_upper_case_globals, clippy::zero_prefixed_literal, unused)]
+
+use monsim_macros::{mon, mov};
+use monsim_utils::{not, Percent};
+
+use super::{ItemDexEntry, ItemSpecies, Type};
+use crate::{
+    effects::*, event_dex::*, sim::{targetting::TargetFlags, DamageContext, EventFilteringOptions, EventHandlerDeck, Nothing}, Effect, ItemUseContext, MoveHitContext
+};
+#[cfg(feature = "debug")]
+use crate::source_code_location;
+
+pub const SitrusBerry: ItemSpecies = ItemSpecies::from_dex_data(
+    ItemDexEntry {
+        dex_number: 001,
+        name: "Sitrus Berry",
+        is_consumable: true,
+        on_use_effect: Effect::from(|sim, effector_id, ItemUseContext { item_holder_id, item_used_id }| {
+            let amount = mon![item_holder_id].max_health() / 4;
+            _ = RestoreHealth(sim, effector_id, (item_holder_id, amount));
+            sim.log_species_message(&SitrusBerry, "restored_health", "{monster} restored its health using its Sitrus Berry!", item_holder_id);
+        }),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnDamageDealt, |sim, effector_id, DamageContext { target_id, .. }| {
+                    let is_at_half_health_or_less = mon![effector_id].current_health() <= mon![effector_id].max_health() / 2;
+                    if target_id == effector_id && is_at_half_health_or_less && not!(mon![effector_id].is_fainted()) {
+                        _ = UseItem(sim, effector_id, ItemUseContext::new(effector_id));
+                    }
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnDamageDealt, |sim, effector_id, DamageContext { target_id, .. }| {
+                    let is_at_half_health_or_less = mon![effector_id].current_health() <= mon![effector_id].max_health() / 2;
+                    if target_id == effector_id && is_at_half_health_or_less && not!(mon![effector_id].is_fainted()) {
+                        _ = UseItem(sim, effector_id, ItemUseContext::new(effector_id));
+                    }
+                });
+            out
+        },
+        // The berry checks the holder's health after any damage to the holder, including residual damage it deals itself.
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::SELF
+                .union(TargetFlags::ALLIES)
+                .union(TargetFlags::OPPONENTS)
+                .union(TargetFlags::ADJACENT)
+                .union(TargetFlags::NONADJACENT),
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const Charcoal: ItemSpecies = ItemSpecies::from_dex_data(
+    ItemDexEntry {
+        dex_number: 002,
+        name: "Charcoal",
+        is_consumable: false,
+        on_use_effect: Effect::from(|_sim, _effector_id, _context| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    if mov![move_used_id].is_type(Type::Fire) { (damage as f64 * Percent(120)) as u16 } else { damage }
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_used_id, .. }, damage)| {
+                    if mov![move_used_id].is_type(Type::Fire) { (damage as f64 * Percent(120)) as u16 } else { damage }
+                });
+            out
+        },
+        // Charcoal boosts the holder's own moves.
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::SELF,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
@@ -5,17 +5,18 @@ use super::{
     Type,
 };
 use crate::{effects::*, sim::{
-    event_dispatch::EventFilteringOptions, BattleState, MonsterID, Move, Stat
+    event_dispatch::EventFilteringOptions, BattleState, BuiltInEffect, EffectDescription, EffectTarget, MonsterID, Move, Stat
 }, BattleSimulator, EventHandlerDeck, Hits, MoveDexEntry, MoveUseContext, TargetFlags};
 
 pub const Tackle: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 001,
         name: "Tackle",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Physical,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -33,10 +34,11 @@ pub const Scratch: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 002,
         name: "Scratch",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Physical,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -54,10 +56,11 @@ pub const Ember: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 003,
         name: "Ember",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Special,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -74,10 +77,11 @@ pub const Bubble: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 004,
         name: "Bubble",
-        on_hit_effect: DealDefaultDamage,
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        base_accuracy: Some(100),
         base_power: 40,
         category: MoveCategory::Special,
+        crit_ratio: 0,
         max_power_points: 35,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -94,12 +98,11 @@ pub const Growl: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 005,
         name: "Growl",
-        on_hit_effect: Effect::from(|sim, effector_id, context| { 
-            _ = LowerStat(sim, context.move_user_id, (context.target_id, Stat::PhysicalAttack, 1)); 
-        }),
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::LowerStat { target: EffectTarget::Target, stat: Stat::PhysicalAttack, stages: 1 }),
+        base_accuracy: Some(100),
         base_power: 0,
         category: MoveCategory::Status,
+        crit_ratio: 0,
         max_power_points: 40,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -116,13 +119,14 @@ pub const DragonDance: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 006,
         name: "Dragon Dance",
-        on_hit_effect: Effect::from(|sim, effector_id, context| {
-            RaiseStat(sim, context.move_user_id, (context.target_id, Stat::PhysicalAttack, 1));
-            RaiseStat(sim, context.move_user_id, (context.target_id, Stat::Speed,          1));
-        }),
-        base_accuracy: 100,
+        on_hit_effect: EffectDescription::Sequence(&[
+            EffectDescription::BuiltIn(BuiltInEffect::RaiseStat { target: EffectTarget::User, stat: Stat::PhysicalAttack, stages: 1 }),
+            EffectDescription::BuiltIn(BuiltInEffect::RaiseStat { target: EffectTarget::User, stat: Stat::Speed,          stages: 1 }),
+        ]),
+        base_accuracy: None,
         base_power: 0,
         category: MoveCategory::Status,
+        crit_ratio: 0,
         max_power_points: 20,
         hits_per_target: Hits::Once,
         priority: 0,
@@ -137,11 +141,12 @@ pub const BulletSeed: MoveSpecies = MoveSpecies::from_dex_entry(
     MoveDexEntry {
         dex_number: 007,
         name: "Bullet Seed",
-        on_hit_effect: DealDefaultDamage,
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
         hits_per_target: Hits::RandomlyInRange { min: 2, max: 5 },
-        base_accuracy: 100,
+        base_accuracy: Some(100),
         base_power: 25,
         category: MoveCategory::Physical,
+        crit_ratio: 0,
         max_power_points: 20,
         priority: 0,
         targets: TargetFlags::ANY
@@ -153,3 +158,25 @@ pub const BulletSeed: MoveSpecies = MoveSpecies::from_dex_entry(
         event_filtering_options: EventFilteringOptions::default(),
     }
 );
+
+pub const FireFang: MoveSpecies = MoveSpecies::from_dex_entry(
+    MoveDexEntry {
+        dex_number: 008,
+        name: "Fire Fang",
+        on_hit_effect: EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage),
+        hits_per_target: Hits::Once,
+        base_accuracy: Some(95),
+        base_power: 65,
+        category: MoveCategory::Physical,
+        crit_ratio: 0,
+        max_power_points: 15,
+        priority: 0,
+        targets: TargetFlags::ANY
+            .union(TargetFlags::ADJACENT)
+            .union(TargetFlags::ALLIES)
+            .union(TargetFlags::OPPONENTS),
+        type_: Type::Fire,
+        event_handlers: EventHandlerDeck::empty,
+        event_filtering_options: EventFilteringOptions::default(),
+    }
+);
@@ -0,0 +1,92 @@
+#![allow(non_upper_case_globals, clippy::zero_prefixed_literal, unused)]
+
+use monsim_macros::{mon, mov};
+
+use super::{MoveCategory, SideConditionDexEntry, SideConditionSpecies};
+use crate::{
+    effects::*, event_dex::*, sim::{targetting::TargetFlags, EventFilteringOptions, EventHandlerDeck}, MoveHitContext, SwitchInContext
+};
+#[cfg(feature = "debug")]
+use crate::source_code_location;
+
+pub const Spikes: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 001,
+        name: "Spikes",
+        duration: None,
+        max_layers: 3,
+        start_message: "{team} is surrounded by spikes!",
+        end_message: "{team} is no longer surrounded by spikes.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, effector_id, SwitchInContext { monster_id }| {
+                    let layers = sim.battle.team(monster_id.team_id)
+                        .side_condition(&Spikes)
+                        .map_or(0, |side_condition| side_condition.layers());
+                    let max_health = mon![monster_id].max_health();
+                    let damage = match layers {
+                        1 => max_health / 8,
+                        2 => max_health / 6,
+                        _ => max_health / 4,
+                    };
+                    sim.log_species_message(&Spikes, "hurt", "{monster} is hurt by the spikes!", monster_id);
+                    _ = DealDirectDamage(sim, effector_id, (monster_id, damage.max(1)));
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnSwitchIn, |sim, effector_id, SwitchInContext { monster_id }| {
+                    let layers = sim.battle.team(monster_id.team_id)
+                        .side_condition(&Spikes)
+                        .map_or(0, |side_condition| side_condition.layers());
+                    let max_health = mon![monster_id].max_health();
+                    let damage = match layers {
+                        1 => max_health / 8,
+                        2 => max_health / 6,
+                        _ => max_health / 4,
+                    };
+                    sim.log_species_message(&Spikes, "hurt", "{monster} is hurt by the spikes!", monster_id);
+                    _ = DealDirectDamage(sim, effector_id, (monster_id, damage.max(1)));
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::ALLIES,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const Reflect: SideConditionSpecies = SideConditionSpecies::from_dex_data(
+    SideConditionDexEntry {
+        dex_number: 002,
+        name: "Reflect",
+        duration: Some(5),
+        max_layers: 1,
+        start_message: "{team} is protected by Reflect!",
+        end_message: "{team} is no longer protected by Reflect.",
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_user_id, move_used_id, target_id }, damage)| {
+                    let is_target_protected = move_user_id.team_id != target_id.team_id;
+                    if is_target_protected && mov![move_used_id].category() == MoveCategory::Physical { damage / 2 } else { damage }
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnModifyFinalDamage, |sim, _effector_id, (MoveHitContext { move_user_id, move_used_id, target_id }, damage)| {
+                    let is_target_protected = move_user_id.team_id != target_id.team_id;
+                    if is_target_protected && mov![move_used_id].category() == MoveCategory::Physical { damage / 2 } else { damage }
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::OPPONENTS,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
@@ -0,0 +1,158 @@
+#![allow(non_upper_case_globals, clippy::zero_prefixed_literal, unused)]
+
+use monsim_macros::{mon, mov};
+
+use super::{MoveCategory, VolatileStatusDexEntry, VolatileStatusDuration, VolatileStatusSpecies};
+use crate::{
+    effects::*, event_dex::*, sim::{event_dispatch::contexts::{MoveUseContext, TurnContext}, targetting::TargetFlags, EventFilteringOptions, EventHandlerDeck, Outcome, Rng}, Effect
+};
+#[cfg(feature = "debug")]
+use crate::source_code_location;
+
+pub const Flinch: VolatileStatusSpecies = VolatileStatusSpecies::from_dex_data(
+    VolatileStatusDexEntry {
+        dex_number: 001,
+        name: "Flinch",
+        duration: VolatileStatusDuration::Turns(1),
+        on_start_effect: Effect::from(|_sim, _effector_id, _affected_monster_id| {}),
+        on_end_effect: Effect::from(|_sim, _effector_id, _affected_monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnTryMove, |sim, effector_id, MoveUseContext { move_user_id, move_used_id, target_ids }| {
+                    sim.log_species_message(&Flinch, "flinched", "{monster} flinched and couldn't move!", effector_id);
+                    Outcome::Failure
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnTryMove, |sim, effector_id, MoveUseContext { move_user_id, move_used_id, target_ids }| {
+                    sim.log_species_message(&Flinch, "flinched", "{monster} flinched and couldn't move!", effector_id);
+                    Outcome::Failure
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::SELF,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const Confusion: VolatileStatusSpecies = VolatileStatusSpecies::from_dex_data(
+    VolatileStatusDexEntry {
+        dex_number: 002,
+        name: "Confusion",
+        duration: VolatileStatusDuration::RandomTurns { min: 2, max: 5 },
+        on_start_effect: Effect::from(|sim, _effector_id, affected_monster_id| {
+            sim.log_species_message(&Confusion, "started", "{monster} became confused!", affected_monster_id);
+        }),
+        on_end_effect: Effect::from(|sim, _effector_id, affected_monster_id| {
+            sim.log_species_message(&Confusion, "ended", "{monster} snapped out of its confusion!", affected_monster_id);
+        }),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnTryMove, |sim, effector_id, MoveUseContext { move_user_id, move_used_id, target_ids }| {
+                    if sim.prng().chance(1, 3) {
+                        sim.log_species_message(&Confusion, "hurt", "{monster} hurt itself in its confusion!", effector_id);
+                        let damage = mon![effector_id].max_health() / 8;
+                        _ = DealDirectDamage(sim, effector_id, (effector_id, damage.max(1)));
+                        return Outcome::Failure;
+                    }
+                    Outcome::Success
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnTryMove, |sim, effector_id, MoveUseContext { move_user_id, move_used_id, target_ids }| {
+                    if sim.prng().chance(1, 3) {
+                        sim.log_species_message(&Confusion, "hurt", "{monster} hurt itself in its confusion!", effector_id);
+                        let damage = mon![effector_id].max_health() / 8;
+                        _ = DealDirectDamage(sim, effector_id, (effector_id, damage.max(1)));
+                        return Outcome::Failure;
+                    }
+                    Outcome::Success
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::SELF,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const Bound: VolatileStatusSpecies = VolatileStatusSpecies::from_dex_data(
+    VolatileStatusDexEntry {
+        dex_number: 003,
+        name: "Bound",
+        duration: VolatileStatusDuration::Turns(4),
+        on_start_effect: Effect::from(|_sim, _effector_id, _affected_monster_id| {}),
+        on_end_effect: Effect::from(|sim, _effector_id, affected_monster_id| {
+            sim.log_species_message(&Bound, "ended", "{monster} was freed!", affected_monster_id);
+        }),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnTurnEnd, |sim, effector_id, _context: TurnContext| {
+                    sim.log_species_message(&Bound, "hurt", "{monster} is hurt by the bind!", effector_id);
+                    let damage = mon![effector_id].max_health() / 8;
+                    _ = DealDirectDamage(sim, effector_id, (effector_id, damage.max(1)));
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnTurnEnd, |sim, effector_id, _context: TurnContext| {
+                    sim.log_species_message(&Bound, "hurt", "{monster} is hurt by the bind!", effector_id);
+                    let damage = mon![effector_id].max_health() / 8;
+                    _ = DealDirectDamage(sim, effector_id, (effector_id, damage.max(1)));
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::SELF,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
+
+pub const Taunt: VolatileStatusSpecies = VolatileStatusSpecies::from_dex_data(
+    VolatileStatusDexEntry {
+        dex_number: 004,
+        name: "Taunt",
+        duration: VolatileStatusDuration::Turns(3),
+        on_start_effect: Effect::from(|_sim, _effector_id, _affected_monster_id| {}),
+        on_end_effect: Effect::from(|_sim, _effector_id, _affected_monster_id| {}),
+        event_handlers: | | {
+            #[cfg(feature="debug")]
+            let out = EventHandlerDeck::empty()
+                .add(OnTryMove, |sim, effector_id, MoveUseContext { move_user_id, move_used_id, target_ids }| {
+                    if mov![move_used_id].category() == MoveCategory::Status {
+                        sim.log_species_message(&Taunt, "prevented", "{monster} can't use status moves after the taunt!", effector_id);
+                        return Outcome::Failure;
+                    }
+                    Outcome::Success
+                }, source_code_location!());
+
+            #[cfg(not(feature="debug"))]
+            let out = EventHandlerDeck::empty()
+                .add(OnTryMove, |sim, effector_id, MoveUseContext { move_user_id, move_used_id, target_ids }| {
+                    if mov![move_used_id].category() == MoveCategory::Status {
+                        sim.log_species_message(&Taunt, "prevented", "{monster} can't use status moves after the taunt!", effector_id);
+                        return Outcome::Failure;
+                    }
+                    Outcome::Success
+                });
+            out
+        },
+        event_filtering_options: EventFilteringOptions {
+            allowed_broadcaster_relation_flags: TargetFlags::SELF,
+            requires_being_active: true,
+        },
+        order: 0,
+    }
+);
@@ -0,0 +1,164 @@
+use monsim_utils::Nothing;
+
+use crate::{sim::{event_dispatch::EventFilteringOptions, EventHandlerDeck, MonsterID}, Effect};
+use core::fmt::Debug;
+
+/// A temporary condition on a Monster, such as confusion or flinching. Unlike a `MajorStatus`, a
+/// Monster can have any number of different volatile statuses at once, and all of them are removed
+/// when it switches out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolatileStatus {
+    #[cfg_attr(feature = "serde", serde(with = "crate::sim::species_registry::serialization::species_ref"))]
+    pub(crate) species: &'static VolatileStatusSpecies,
+    /// The number of turns the status has been on the Monster for.
+    pub(crate) turn_counter: u8,
+    /// The number of turns after which the status is removed, decided when the status is added.
+    pub(crate) duration: Option<u8>,
+}
+
+impl VolatileStatus {
+    pub(crate) fn new(species: &'static VolatileStatusSpecies, duration: Option<u8>) -> Self {
+        Self {
+            species,
+            turn_counter: 0,
+            duration,
+        }
+    }
+
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.species.event_handlers)()
+    }
+
+    #[inline(always)]
+    pub fn species(&self) -> &'static VolatileStatusSpecies {
+        self.species
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.species.name
+    }
+
+    #[inline(always)]
+    pub fn turn_counter(&self) -> u8 {
+        self.turn_counter
+    }
+
+    #[inline(always)]
+    pub fn duration(&self) -> Option<u8> {
+        self.duration
+    }
+
+    /// Returns whether the status has lasted for its full duration and should be removed.
+    #[inline(always)]
+    pub fn has_expired(&self) -> bool {
+        self.duration.is_some_and(|duration| self.turn_counter >= duration)
+    }
+}
+
+/// How long a volatile status lasts once it has been added to a Monster. Every volatile status is
+/// also removed when the Monster switches out, regardless of its duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolatileStatusDuration {
+    UntilSwitchOut,
+    Turns(u8),
+    /// A random number of turns between `min` and `max` inclusive, rolled when the status is added.
+    RandomTurns { min: u8, max: u8 },
+}
+
+#[derive(Clone, Copy)]
+pub struct VolatileStatusSpecies {
+    dex_number: u16,
+    name: &'static str,
+    duration: VolatileStatusDuration,
+    on_start_effect: Effect<Nothing, MonsterID>,
+    on_end_effect: Effect<Nothing, MonsterID>,
+    event_handlers: fn() -> EventHandlerDeck,
+    event_filtering_options: EventFilteringOptions,
+    order: u16,
+}
+
+impl Debug for VolatileStatusSpecies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:03} {}", self.dex_number, self.name)
+    }
+}
+
+impl PartialEq for VolatileStatusSpecies {
+    fn eq(&self, other: &Self) -> bool {
+        self.dex_number == other.dex_number
+    }
+}
+
+impl Eq for VolatileStatusSpecies {}
+
+impl VolatileStatusSpecies {
+    pub const fn from_dex_data(dex_data: VolatileStatusDexEntry) -> Self {
+        let VolatileStatusDexEntry { dex_number, name, duration, on_start_effect, on_end_effect, event_handlers, event_filtering_options, order } = dex_data;
+
+        Self {
+            dex_number,
+            name,
+            duration,
+            on_start_effect,
+            on_end_effect,
+            event_handlers,
+            event_filtering_options,
+            order,
+        }
+    }
+
+    #[inline(always)]
+    pub fn event_handlers(&self) -> EventHandlerDeck {
+        (self.event_handlers)()
+    }
+
+    #[inline(always)]
+    pub fn on_start_effect(&self) -> Effect<Nothing, MonsterID> {
+        self.on_start_effect
+    }
+
+    #[inline(always)]
+    pub fn on_end_effect(&self) -> Effect<Nothing, MonsterID> {
+        self.on_end_effect
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn duration(&self) -> VolatileStatusDuration {
+        self.duration
+    }
+
+    #[inline(always)]
+    pub fn event_filtering_options(&self) -> EventFilteringOptions {
+        self.event_filtering_options
+    }
+
+    #[inline(always)]
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    #[inline(always)]
+    pub fn dex_number(&self) -> u16 {
+        self.dex_number
+    }
+}
+
+pub struct VolatileStatusDexEntry {
+    pub dex_number: u16,
+    pub name: &'static str,
+    pub duration: VolatileStatusDuration,
+    /// Resolved after the status is added to the Monster, the context is the affected Monster.
+    pub on_start_effect: Effect<Nothing, MonsterID>,
+    /// Resolved after the status is removed from the Monster, except when it is removed by switching out.
+    pub on_end_effect: Effect<Nothing, MonsterID>,
+    pub event_handlers: fn() -> EventHandlerDeck,
+    pub event_filtering_options: EventFilteringOptions,
+    pub order: u16,
+}
@@ -0,0 +1,358 @@
+/*!
+Loads species and message catalogs from TOML or JSON data files, so that content can be defined without writing Rust.
+Loaded species are leaked to `'static`, like the `const` species in the dex files, and registered
+in a `Dex`. Monsters and moves are supported, moves describe their behaviour with an
+`EffectDescription`.
+
+```toml
+[[monsters]]
+dex_number = 1
+name = "Dandyleo"
+primary_type = "Grass"
+base_stats = { hp = 40, att = 45, def = 50, spa = 120, spd = 65, spe = 60 }
+
+[[moves]]
+dex_number = 1
+name = "Acid Spray"
+type = "Poison"
+category = "Special"
+base_power = 40
+base_accuracy = 100
+max_power_points = 20
+targets = ["ADJACENT", "OPPONENTS"]
+effects = [
+    { effect = "DealDefaultDamage" },
+    { effect = "LowerStat", target = "Target", stat = "SpecialDefense", stages = 2 },
+]
+```
+
+Message catalogs replace message templates by key and species names by the name the species is
+registered with in the `Dex`.
+
+```toml
+[messages]
+move_used = "{monster} setzt {move} ein!"
+super_effective = "Das ist sehr effektiv!"
+
+[monsters]
+Dandyleo = "Löwenzahn"
+
+[moves]
+"Acid Spray" = "Säurespray"
+```
+*/
+
+use std::{collections::BTreeMap, error::Error, fmt::Display, ops::RangeInclusive, path::Path};
+
+use monsim_utils::not;
+use serde::Deserialize;
+
+use crate::sim::{
+    localisation::LocalisationError, BuiltInEffect, Dex, DexError, DexSection, DexSpecies, EffectDescription, MessageCatalog, EventFilteringOptions, EventHandlerDeck, Hits, MonsterDexEntry, MonsterSpecies, MoveCategory, MoveDexEntry,
+    MoveSpecies, StatSet, TargetFlags, Type,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpeciesFile {
+    #[serde(default)]
+    monsters: Vec<MonsterData>,
+    #[serde(default)]
+    moves: Vec<MoveData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MonsterData {
+    dex_number: u16,
+    name: String,
+    primary_type: Type,
+    #[serde(default)]
+    secondary_type: Option<Type>,
+    base_stats: StatSet,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveData {
+    dex_number: u16,
+    name: String,
+    #[serde(rename = "type")]
+    type_: Type,
+    category: MoveCategory,
+    #[serde(default)]
+    base_power: u16,
+    /// Leaving out the accuracy means the move never misses.
+    #[serde(default)]
+    base_accuracy: Option<u16>,
+    max_power_points: u8,
+    #[serde(default)]
+    priority: i8,
+    #[serde(default)]
+    crit_ratio: u8,
+    /// The names of the `TargetFlags`, _e.g._ `["ADJACENT", "OPPONENTS"]`.
+    targets: Vec<String>,
+    #[serde(default = "MoveData::default_hits")]
+    hits_per_target: Hits,
+    #[serde(default = "MoveData::default_effects")]
+    effects: EffectDescription,
+}
+
+impl MoveData {
+    fn default_hits() -> Hits {
+        Hits::Once
+    }
+
+    fn default_effects() -> EffectDescription {
+        EffectDescription::BuiltIn(BuiltInEffect::DealDefaultDamage)
+    }
+}
+
+/// Loads the species in the TOML or JSON file at `path`, depending on its extension, into `dex`.
+pub fn load_species_from_file(path: impl AsRef<Path>, dex: &mut Dex) -> Result<(), LoadError> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => load_species_from_toml(&source, dex),
+        Some("json") => load_species_from_json(&source, dex),
+        _ => Err(LoadError::UnsupportedFileType(path.display().to_string())),
+    }
+}
+
+pub fn load_species_from_toml(source: &str, dex: &mut Dex) -> Result<(), LoadError> {
+    register_species(toml::from_str(source)?, dex)
+}
+
+pub fn load_species_from_json(source: &str, dex: &mut Dex) -> Result<(), LoadError> {
+    register_species(serde_json::from_str(source)?, dex)
+}
+
+/// The highest base power a loaded move can have, higher powers could overflow the damage formula.
+const MAX_BASE_POWER: u16 = 250;
+const PRIORITY_RANGE: RangeInclusive<i8> = -7..=5;
+
+/// Checks every species in `species_file` before registering any of them, so that a file with an error
+/// leaves `dex` unchanged.
+fn register_species(species_file: SpeciesFile, dex: &mut Dex) -> Result<(), LoadError> {
+    let SpeciesFile { monsters, moves } = species_file;
+
+    let moves = moves.into_iter()
+        .map(|move_data| {
+            let targets = validate_move(&move_data)?;
+            Ok((move_data, targets))
+        })
+        .collect::<Result<Vec<_>, LoadError>>()?;
+
+    let mut staged_dex = dex.clone();
+    for monster_data in monsters {
+        let MonsterData { dex_number, name, primary_type, secondary_type, base_stats } = monster_data;
+        let species: &'static MonsterSpecies = Box::leak(Box::new(MonsterSpecies::from_dex_entry(MonsterDexEntry {
+            dex_number,
+            name: String::leak(name),
+            primary_type,
+            secondary_type,
+            base_stats,
+            event_handlers: EventHandlerDeck::empty,
+        })));
+        staged_dex.register(species)?;
+    }
+
+    for (move_data, targets) in moves {
+        let MoveData { dex_number, name, type_, category, base_power, base_accuracy, max_power_points, priority, crit_ratio, targets: _, hits_per_target, effects } = move_data;
+        let species: &'static MoveSpecies = Box::leak(Box::new(MoveSpecies::from_dex_entry(MoveDexEntry {
+            dex_number,
+            name: String::leak(name),
+            on_hit_effect: effects,
+            hits_per_target,
+            base_accuracy,
+            base_power,
+            category,
+            crit_ratio,
+            max_power_points,
+            priority,
+            targets,
+            type_,
+            event_handlers: EventHandlerDeck::empty,
+            event_filtering_options: EventFilteringOptions::default(),
+        })));
+        staged_dex.register(species)?;
+    }
+
+    *dex = staged_dex;
+    Ok(())
+}
+
+/// Returns the `TargetFlags` of the move if its data is valid.
+fn validate_move(move_data: &MoveData) -> Result<TargetFlags, LoadError> {
+    let MoveData { name, category, base_power, base_accuracy, max_power_points, priority, targets, effects, .. } = move_data;
+    let targets = targets.iter().try_fold(TargetFlags::empty(), |flags, flag_name| {
+        TargetFlags::from_name(flag_name)
+            .map(|flag| flags | flag)
+            .ok_or_else(|| LoadError::UnknownTargetFlag(flag_name.clone()))
+    })?;
+    if *max_power_points == 0 {
+        return Err(LoadError::NoPowerPoints(name.clone()));
+    }
+    // Status moves have no base power to deal damage with.
+    if *category == MoveCategory::Status && effects.deals_default_damage() {
+        return Err(LoadError::StatusMoveDealsDamage(name.clone()));
+    }
+    if *base_power > MAX_BASE_POWER {
+        return Err(LoadError::BasePowerOutOfRange { move_name: name.clone(), base_power: *base_power });
+    }
+    if let Some(base_accuracy) = *base_accuracy {
+        if not!((1..=100).contains(&base_accuracy)) {
+            return Err(LoadError::BaseAccuracyOutOfRange { move_name: name.clone(), base_accuracy });
+        }
+    }
+    if not!(PRIORITY_RANGE.contains(priority)) {
+        return Err(LoadError::PriorityOutOfRange { move_name: name.clone(), priority: *priority });
+    }
+    Ok(targets)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    messages: BTreeMap<String, String>,
+    #[serde(default)]
+    monsters: BTreeMap<String, String>,
+    #[serde(default)]
+    moves: BTreeMap<String, String>,
+    #[serde(default)]
+    abilities: BTreeMap<String, String>,
+    #[serde(default)]
+    items: BTreeMap<String, String>,
+    #[serde(default)]
+    volatile_statuses: BTreeMap<String, String>,
+    #[serde(default)]
+    field_effects: BTreeMap<String, String>,
+    #[serde(default)]
+    side_conditions: BTreeMap<String, String>,
+}
+
+/// Loads the message catalog in the TOML or JSON file at `path`, depending on its extension. The species it
+/// renames are looked up in `dex`.
+pub fn load_message_catalog_from_file(path: impl AsRef<Path>, dex: &Dex) -> Result<MessageCatalog, LoadError> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => load_message_catalog_from_toml(&source, dex),
+        Some("json") => load_message_catalog_from_json(&source, dex),
+        _ => Err(LoadError::UnsupportedFileType(path.display().to_string())),
+    }
+}
+
+pub fn load_message_catalog_from_toml(source: &str, dex: &Dex) -> Result<MessageCatalog, LoadError> {
+    build_message_catalog(toml::from_str(source)?, dex)
+}
+
+pub fn load_message_catalog_from_json(source: &str, dex: &Dex) -> Result<MessageCatalog, LoadError> {
+    build_message_catalog(serde_json::from_str(source)?, dex)
+}
+
+fn build_message_catalog(catalog_file: CatalogFile, dex: &Dex) -> Result<MessageCatalog, LoadError> {
+    let CatalogFile { messages, monsters, moves, abilities, items, volatile_statuses, field_effects, side_conditions } = catalog_file;
+
+    let mut catalog = MessageCatalog::new();
+    for (key, template) in messages {
+        catalog.set_template(&key, template)?;
+    }
+    rename_species(&mut catalog, dex.monsters(), monsters)?;
+    rename_species(&mut catalog, dex.moves(), moves)?;
+    rename_species(&mut catalog, dex.abilities(), abilities)?;
+    rename_species(&mut catalog, dex.items(), items)?;
+    rename_species(&mut catalog, dex.volatile_statuses(), volatile_statuses)?;
+    rename_species(&mut catalog, dex.field_effects(), field_effects)?;
+    rename_species(&mut catalog, dex.side_conditions(), side_conditions)?;
+    Ok(catalog)
+}
+
+fn rename_species<T: DexSpecies>(catalog: &mut MessageCatalog, section: &DexSection<T>, names: BTreeMap<String, String>) -> Result<(), LoadError> {
+    for (registered_name, localised_name) in names {
+        let species = section.find_by_name(&registered_name).ok_or(LoadError::UnknownSpecies { kind: T::KIND, name: registered_name })?;
+        catalog.set_species_name(species, localised_name);
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    UnsupportedFileType(String),
+    UnknownTargetFlag(String),
+    /// The move with this name has no power points.
+    NoPowerPoints(String),
+    /// The Status move with this name deals default damage, which is the default for `effects`.
+    StatusMoveDealsDamage(String),
+    /// The base power of the move is higher than 250.
+    BasePowerOutOfRange { move_name: String, base_power: u16 },
+    /// The base accuracy of the move is not between 1 and 100.
+    BaseAccuracyOutOfRange { move_name: String, base_accuracy: u16 },
+    /// The priority of the move is not between -7 and +5.
+    PriorityOutOfRange { move_name: String, priority: i8 },
+    Dex(DexError),
+    Localisation(LocalisationError),
+    UnknownSpecies { kind: &'static str, name: String },
+}
+
+impl Error for LoadError {}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "{}", error),
+            LoadError::Toml(error) => write!(f, "{}", error),
+            LoadError::Json(error) => write!(f, "{}", error),
+            LoadError::UnsupportedFileType(path) => write!(f, "{} is not a .toml or .json file.", path),
+            LoadError::UnknownTargetFlag(flag_name) => write!(f, "{} is not a target flag.", flag_name),
+            LoadError::NoPowerPoints(move_name) => write!(f, "{} must have at least 1 max power point.", move_name),
+            LoadError::StatusMoveDealsDamage(move_name) => write!(f, "{} is a Status move, so its effects cannot deal default damage.", move_name),
+            LoadError::BasePowerOutOfRange { move_name, base_power } => write!(
+                f, "{} has a base power of {}, but base power cannot be higher than {}.", move_name, base_power, MAX_BASE_POWER
+            ),
+            LoadError::BaseAccuracyOutOfRange { move_name, base_accuracy } => write!(
+                f, "{} has a base accuracy of {}, but base accuracy must be between 1 and 100.", move_name, base_accuracy
+            ),
+            LoadError::PriorityOutOfRange { move_name, priority } => write!(
+                f, "{} has a priority of {}, but priority must be between {} and {}.", move_name, priority, PRIORITY_RANGE.start(), PRIORITY_RANGE.end()
+            ),
+            LoadError::Dex(error) => write!(f, "{}", error),
+            LoadError::Localisation(error) => write!(f, "{}", error),
+            LoadError::UnknownSpecies { kind, name } => write!(f, "No {} named {} is registered in the Dex.", kind, name),
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(error: std::io::Error) -> Self {
+        LoadError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for LoadError {
+    fn from(error: toml::de::Error) -> Self {
+        LoadError::Toml(error)
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(error: serde_json::Error) -> Self {
+        LoadError::Json(error)
+    }
+}
+
+impl From<DexError> for LoadError {
+    fn from(error: DexError) -> Self {
+        LoadError::Dex(error)
+    }
+}
+
+impl From<LocalisationError> for LoadError {
+    fn from(error: LocalisationError) -> Self {
+        LoadError::Localisation(error)
+    }
+}
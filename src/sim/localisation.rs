@@ -0,0 +1,454 @@
+/*!
+Battle text is written as messages, each of which is a key into a `MessageCatalog` plus the arguments
+to fill in its template. A catalog only needs to contain the templates and species names it changes,
+everything else falls back to the built-in English, so catalogs can translate the whole battle log or
+just add flavour text to a few messages.
+
+```ignore
+let mut catalog = MessageCatalog::new();
+catalog.set_template("move_used", "{monster} setzt {move} ein!")?;
+catalog.set_species_name(&Dandyleo, "Löwenzahn");
+let german_log = battle.render_battle_log(&catalog);
+```
+
+Templates refer to their arguments by name in braces, _e.g._ `{monster}`, and `{{` and `}}` stand for
+literal braces. Placeholders for arguments a message doesn't have are left as they are.
+
+Species write some messages of their own, such as the start message of a weather or what a berry does
+when it is eaten. Their templates are the `SpeciesText`s the species are defined with, which catalogs
+replace for each species with `set_species_template`.
+*/
+
+use std::{collections::BTreeMap, error::Error, fmt::Display};
+
+use crate::sim::{
+    BattleLogEntry, BattleLogRenderer, BattleState, Colour, DexSpecies, StyledText, TextStyle, FieldEffectSpecies, FieldPosition, ItemSpecies, MajorStatus, MonsterID, MoveID, SideConditionSpecies, Stat, TeamID,
+};
+
+/// A line of battle text before it has been put into words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub key: &'static str,
+    pub arguments: Vec<(&'static str, MessageArgument)>,
+    /// Where the template comes from if the message was written by a species rather than being in `ENGLISH_TEMPLATES`.
+    pub species_text: Option<SpeciesText>,
+}
+
+impl Message {
+    pub fn new(key: &'static str) -> Self {
+        Self { key, arguments: Vec::new(), species_text: None }
+    }
+
+    pub fn from_species_text(species_text: SpeciesText) -> Self {
+        Self { key: species_text.key, arguments: Vec::new(), species_text: Some(species_text) }
+    }
+
+    pub fn with(mut self, name: &'static str, argument: MessageArgument) -> Self {
+        self.arguments.push((name, argument));
+        self
+    }
+
+    fn argument(&self, name: &str) -> Option<MessageArgument> {
+        self.arguments
+            .iter()
+            .find(|(argument_name, _)| *argument_name == name)
+            .map(|(_, argument)| *argument)
+    }
+}
+
+/// A value filled into a message template. Monsters, moves and other species are written with their
+/// localised names, stats, statuses, teams and positions with the templates `stat.*`, `status.*`, `team.*`
+/// and `position.*`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageArgument {
+    Monster(MonsterID),
+    Move(MoveID),
+    Item(&'static ItemSpecies),
+    FieldEffect(&'static FieldEffectSpecies),
+    SideCondition(&'static SideConditionSpecies),
+    Stat(Stat),
+    Status(MajorStatus),
+    Team(TeamID),
+    Position(FieldPosition),
+    Number(u16),
+}
+
+/// A message template written by a species, in English. Catalogs can replace the template with the `key`
+/// for that species, the same way they rename it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeciesText {
+    /// The `RegisteredSpecies::KIND` of the species.
+    pub kind: &'static str,
+    pub dex_number: u16,
+    pub key: &'static str,
+    pub english_template: &'static str,
+}
+
+impl SpeciesText {
+    pub fn new<T: DexSpecies>(species: &T, key: &'static str, english_template: &'static str) -> Self {
+        Self { kind: T::KIND, dex_number: species.dex_number(), key, english_template }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use std::{collections::BTreeSet, sync::Mutex};
+
+    use super::SpeciesText;
+
+    use serde::{Deserialize, Serialize};
+
+    /// A `SpeciesText` with its strings owned, which are interned as `&'static str`s when it is deserialized.
+    #[derive(Serialize, Deserialize)]
+    struct SerializedSpeciesText {
+        kind: String,
+        dex_number: u16,
+        key: String,
+        english_template: String,
+    }
+
+    static INTERNED_STRINGS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    /// Each distinct string is leaked once, so loading many battles doesn't leak more than loading one.
+    fn intern(string: String) -> &'static str {
+        let mut interned_strings = INTERNED_STRINGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(interned_string) = interned_strings.get(string.as_str()) {
+            return interned_string;
+        }
+        let interned_string: &'static str = Box::leak(string.into_boxed_str());
+        interned_strings.insert(interned_string);
+        interned_string
+    }
+
+    impl serde::Serialize for SpeciesText {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let SpeciesText { kind, dex_number, key, english_template } = *self;
+            SerializedSpeciesText { kind: kind.to_owned(), dex_number, key: key.to_owned(), english_template: english_template.to_owned() }.serialize(serializer)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for SpeciesText {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let SerializedSpeciesText { kind, dex_number, key, english_template } = SerializedSpeciesText::deserialize(deserializer)?;
+            Ok(SpeciesText { kind: intern(kind), dex_number, key: intern(key), english_template: intern(english_template) })
+        }
+    }
+}
+
+/// The built-in English template for every message key.
+pub const ENGLISH_TEMPLATES: &[(&str, &str)] = &[
+    ("divider", "---"),
+    ("blank_line", ""),
+    ("turn_started", "Turn {turn_number}"),
+    ("unable_to_act", "{monster} fainted so it was unable to act."),
+    ("fast_asleep", "{monster} is fast asleep."),
+    ("frozen_solid", "{monster} is frozen solid!"),
+    ("fully_paralysed", "{monster} is paralysed! It can't move!"),
+    ("cannot_move", "{monster} can't move!"),
+    ("move_used", "{monster} used {move}"),
+    ("move_had_no_targets", "{monster}'s {move} has no targets..."),
+    ("move_failed", "The move failed!"),
+    ("move_missed", "{monster}'s attack missed {target}!"),
+    ("move_failed_to_hit", "The move failed to hit {target}!"),
+    ("move_hit_multiple_times", "The move hit {number_of_hits} time(s)"),
+    ("target_immune", "It was ineffective..."),
+    ("critical_hit", "A critical hit!"),
+    ("not_very_effective", "It was not very effective!"),
+    ("effective", "It was effective!"),
+    ("super_effective", "It was super effective!"),
+    ("damage_taken", "{monster} took {amount} damage!"),
+    ("health_left", "{monster} has {remaining_health} health left."),
+    ("fainted", "{monster} fainted!"),
+    ("switched_out", "{replaced_monster} switched out! Go {monster}!"),
+    ("sent_in", "Go {monster}!"),
+    ("item_removed", "{monster} lost its {item}!"),
+    ("item_transferred", "{receiver} obtained {giver}'s {item}!"),
+    ("items_swapped", "{monster} switched items with {other_monster}!"),
+    ("crit_stage_raised", "{monster} is getting pumped!"),
+    ("stat_raised", "{monster}'s {stat} was raised by {stages} stage(s)!"),
+    ("stat_lowered", "{monster}'s {stat} was lowered by {stages} stage(s)!"),
+    ("stat_raise_failed", "{monster}'s stats cannot get any higher."),
+    ("stat_lower_failed", "{monster}'s stats were not lowered."),
+    ("status_inflicted", "{monster} was {status}!"),
+    ("already_has_status", "{monster} is already {status}."),
+    ("not_affected", "It doesn't affect {monster}..."),
+    ("hurt_by_burn", "{monster} was hurt by its burn!"),
+    ("hurt_by_poison", "{monster} was hurt by its poison!"),
+    ("woke_up", "{monster} woke up!"),
+    ("thawed_out", "{monster} thawed out!"),
+    ("burn_healed", "{monster}'s burn was healed."),
+    ("paralysis_cured", "{monster} was cured of its paralysis."),
+    ("poisoning_cured", "{monster} was cured of its poisoning."),
+    ("effect_failed", "But it failed!"),
+    ("battle_tied", "Neither team has any usable Monsters, it's a tie!"),
+    ("ally_team_won", "Ally team won!"),
+    ("opponent_team_won", "Opponent team won!"),
+    ("battle_ended", "The battle ended."),
+    ("position_left_empty", "{position} is empty but {team} is out of switchable Monsters!"),
+    ("stat.hp", "HP"),
+    ("stat.attack", "Attack"),
+    ("stat.defense", "Defense"),
+    ("stat.special_attack", "Special Attack"),
+    ("stat.special_defense", "Special Defense"),
+    ("stat.speed", "Speed"),
+    ("stat.accuracy", "Accuracy"),
+    ("stat.evasion", "Evasiveness"),
+    ("status.burn", "burned"),
+    ("status.freeze", "frozen solid"),
+    ("status.paralysis", "paralysed"),
+    ("status.poison", "poisoned"),
+    ("status.bad_poison", "badly poisoned"),
+    ("status.sleep", "put to sleep"),
+    ("team.allies", "Ally Team"),
+    ("team.opponents", "Opponent Team"),
+    ("position.ally_side_left", "Ally Side Left"),
+    ("position.ally_side_centre", "Ally Side Centre"),
+    ("position.ally_side_right", "Ally Side Right"),
+    ("position.opponent_side_left", "Opponent Side Left"),
+    ("position.opponent_side_centre", "Opponent Side Centre"),
+    ("position.opponent_side_right", "Opponent Side Right"),
+];
+
+fn english_template(key: &str) -> Option<&'static str> {
+    ENGLISH_TEMPLATES
+        .iter()
+        .find(|(english_key, _)| *english_key == key)
+        .map(|(_, template)| *template)
+}
+
+/// Templates and species names that replace the built-in English ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageCatalog {
+    templates: BTreeMap<&'static str, String>,
+    /// Keyed by the `RegisteredSpecies::KIND` and dex number of the species.
+    species_names: BTreeMap<(&'static str, u16), String>,
+    /// Keyed by the `RegisteredSpecies::KIND` and dex number of the species, and the key of the `SpeciesText`.
+    species_templates: BTreeMap<(&'static str, u16, &'static str), String>,
+}
+
+/// The catalog with nothing replaced, used by `EnglishRenderer`.
+static ENGLISH_CATALOG: MessageCatalog = MessageCatalog::new();
+
+impl MessageCatalog {
+    pub const fn new() -> Self {
+        Self {
+            templates: BTreeMap::new(),
+            species_names: BTreeMap::new(),
+            species_templates: BTreeMap::new(),
+        }
+    }
+
+    /// Replaces the template for `key`, which has to be one of the keys in `ENGLISH_TEMPLATES`.
+    pub fn set_template(&mut self, key: &str, template: impl Into<String>) -> Result<(), LocalisationError> {
+        let Some(&(key, _)) = ENGLISH_TEMPLATES.iter().find(|(english_key, _)| *english_key == key) else {
+            return Err(LocalisationError::UnknownMessageKey(key.to_owned()));
+        };
+        self.templates.insert(key, template.into());
+        Ok(())
+    }
+
+    pub fn set_species_name<T: DexSpecies>(&mut self, species: &'static T, name: impl Into<String>) {
+        self.species_names.insert((T::KIND, species.dex_number()), name.into());
+    }
+
+    /// Replaces the template of the `SpeciesText` with `key` written by `species`.
+    pub fn set_species_template<T: DexSpecies>(&mut self, species: &'static T, key: &'static str, template: impl Into<String>) {
+        self.species_templates.insert((T::KIND, species.dex_number(), key), template.into());
+    }
+
+    /// The template for `key` in this catalog, or the English one if the catalog doesn't replace it.
+    pub fn template(&self, key: &str) -> Option<&str> {
+        self.templates.get(key).map(String::as_str).or_else(|| english_template(key))
+    }
+
+    /// The template for `message` in this catalog, which comes from the species that wrote it if it has a `SpeciesText`.
+    pub fn message_template(&self, message: &Message) -> Option<&str> {
+        match message.species_text {
+            Some(SpeciesText { kind, dex_number, key, english_template }) => Some(
+                self.species_templates
+                    .get(&(kind, dex_number, key))
+                    .map(String::as_str)
+                    .unwrap_or(english_template)
+            ),
+            None => self.template(message.key),
+        }
+    }
+
+    /// The localised name of `species`, or the name it was defined with if the catalog doesn't replace it.
+    pub fn species_name<T: DexSpecies>(&self, species: &T) -> &str {
+        self.species_names
+            .get(&(T::KIND, species.dex_number()))
+            .map(String::as_str)
+            .unwrap_or(species.name())
+    }
+
+    /// Puts `message` into words, using `battle` to look up the Monsters and moves it refers to.
+    pub fn render_message(&self, battle: &BattleState, message: &Message) -> String {
+        self.render_styled_message(battle, message).plain_text()
+    }
+
+    /// Like `render_message`, but with Monsters coloured by team, moves coloured by type and the
+    /// effectiveness of moves in bold.
+    pub fn render_styled_message(&self, battle: &BattleState, message: &Message) -> StyledText {
+        let Some(template) = self.message_template(message) else {
+            return StyledText::plain(message.key);
+        };
+        let message_style = message_style(message.key);
+        let mut rendered_message = StyledText::new();
+        let mut literal_text = String::new();
+        let mut characters = template.chars().peekable();
+        while let Some(character) = characters.next() {
+            match character {
+                '{' if characters.peek() == Some(&'{') => {
+                    characters.next();
+                    literal_text.push('{');
+                },
+                '}' if characters.peek() == Some(&'}') => {
+                    characters.next();
+                    literal_text.push('}');
+                },
+                '{' => {
+                    let name = characters.by_ref().take_while(|character| *character != '}').collect::<String>();
+                    match message.argument(&name) {
+                        Some(argument) => {
+                            rendered_message.push(std::mem::take(&mut literal_text), message_style);
+                            let argument_style = message_style.merged_with(argument_style(battle, argument));
+                            rendered_message.push(self.render_argument(battle, argument), argument_style);
+                        },
+                        None => literal_text.push_str(&format!["{{{name}}}"]),
+                    }
+                },
+                _ => literal_text.push(character),
+            }
+        }
+        rendered_message.push(literal_text, message_style);
+        rendered_message
+    }
+
+    fn render_argument(&self, battle: &BattleState, argument: MessageArgument) -> String {
+        match argument {
+            MessageArgument::Monster(monster_id) => {
+                let monster = battle.monster(monster_id);
+                match monster.nickname {
+                    Some(nickname) => nickname.to_owned(),
+                    None => self.species_name(monster.species).to_owned(),
+                }
+            },
+            MessageArgument::Move(move_id) => {
+                self.species_name(battle.move_(move_id).species).to_owned()
+            },
+            MessageArgument::Item(species) => self.species_name(species).to_owned(),
+            MessageArgument::FieldEffect(species) => self.species_name(species).to_owned(),
+            MessageArgument::SideCondition(species) => self.species_name(species).to_owned(),
+            MessageArgument::Stat(stat) => self.template(stat_key(stat)).unwrap_or_default().to_owned(),
+            MessageArgument::Status(status) => self.template(status_key(status)).unwrap_or_default().to_owned(),
+            MessageArgument::Team(team_id) => self.template(team_key(team_id)).unwrap_or_default().to_owned(),
+            MessageArgument::Position(field_position) => self.template(position_key(field_position)).unwrap_or_default().to_owned(),
+            MessageArgument::Number(number) => number.to_string(),
+        }
+    }
+}
+
+impl BattleLogRenderer for MessageCatalog {
+    fn render(&self, battle: &BattleState, entry: BattleLogEntry) -> Vec<String> {
+        entry.messages()
+            .iter()
+            .map(|message| self.render_message(battle, message))
+            .collect()
+    }
+
+    fn render_styled(&self, battle: &BattleState, entry: BattleLogEntry) -> Vec<StyledText> {
+        entry.messages()
+            .iter()
+            .map(|message| self.render_styled_message(battle, message))
+            .collect()
+    }
+}
+
+/// The renderer the simulator uses by default, which writes the log in English.
+#[derive(Debug, Clone, Copy)]
+pub struct EnglishRenderer;
+
+impl BattleLogRenderer for EnglishRenderer {
+    fn render(&self, battle: &BattleState, entry: BattleLogEntry) -> Vec<String> {
+        ENGLISH_CATALOG.render(battle, entry)
+    }
+
+    fn render_styled(&self, battle: &BattleState, entry: BattleLogEntry) -> Vec<StyledText> {
+        ENGLISH_CATALOG.render_styled(battle, entry)
+    }
+}
+
+/// The style of the text of a message outside of its arguments.
+fn message_style(key: &str) -> TextStyle {
+    match key {
+        "turn_started" => TextStyle::coloured(Colour::TURN_HEADER).bold(),
+        "critical_hit" | "not_very_effective" | "effective" | "super_effective" | "target_immune" => TextStyle::PLAIN.bold(),
+        _ => TextStyle::PLAIN,
+    }
+}
+
+fn argument_style(battle: &BattleState, argument: MessageArgument) -> TextStyle {
+    match argument {
+        MessageArgument::Monster(monster_id) => TextStyle::coloured(Colour::of_team(monster_id.team_id)),
+        MessageArgument::Move(move_id) => TextStyle::coloured(Colour::of_type(battle.move_(move_id).type_())),
+        _ => TextStyle::PLAIN,
+    }
+}
+
+fn stat_key(stat: Stat) -> &'static str {
+    match stat {
+        Stat::Hp => "stat.hp",
+        Stat::PhysicalAttack => "stat.attack",
+        Stat::PhysicalDefense => "stat.defense",
+        Stat::SpecialAttack => "stat.special_attack",
+        Stat::SpecialDefense => "stat.special_defense",
+        Stat::Speed => "stat.speed",
+        Stat::Accuracy => "stat.accuracy",
+        Stat::Evasion => "stat.evasion",
+    }
+}
+
+fn status_key(status: MajorStatus) -> &'static str {
+    match status {
+        MajorStatus::Burn => "status.burn",
+        MajorStatus::Freeze => "status.freeze",
+        MajorStatus::Paralysis => "status.paralysis",
+        MajorStatus::Poison => "status.poison",
+        MajorStatus::BadPoison => "status.bad_poison",
+        MajorStatus::Sleep => "status.sleep",
+    }
+}
+
+fn team_key(team_id: TeamID) -> &'static str {
+    match team_id {
+        TeamID::Allies => "team.allies",
+        TeamID::Opponents => "team.opponents",
+    }
+}
+
+fn position_key(field_position: FieldPosition) -> &'static str {
+    match field_position {
+        FieldPosition::AllySideLeft => "position.ally_side_left",
+        FieldPosition::AllySideCentre => "position.ally_side_centre",
+        FieldPosition::AllySideRight => "position.ally_side_right",
+        FieldPosition::OpponentSideLeft => "position.opponent_side_left",
+        FieldPosition::OpponentSideCentre => "position.opponent_side_centre",
+        FieldPosition::OpponentSideRight => "position.opponent_side_right",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalisationError {
+    UnknownMessageKey(String),
+}
+
+impl Error for LocalisationError {}
+
+impl Display for LocalisationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalisationError::UnknownMessageKey(key) => write!(f, "{} is not a message key.", key),
+        }
+    }
+}
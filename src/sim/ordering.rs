@@ -1,6 +1,7 @@
-use crate::sim::prng::Prng;
+use crate::sim::prng::{Prng, Rng};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ActivationOrder {
     pub priority: i8,
     pub speed: u16,
@@ -1,64 +1,95 @@
-use std::{time, u64::MAX};
+use std::time;
 
 use std::ops::RangeInclusive;
 
-// LCRNG -> Linear Congruential Random Number Generator
+/// A source of randomness for the simulator. Implementors only need to provide `next_u64`, the
+/// other methods are derived from it without bias.
+pub trait Rng {
+    /// Returns the next uniformly distributed u64 in the sequence.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns each u16 in the range with equal probability. If the range contains one number, it returns it with 100% certainty.
+    fn generate_random_u16_in_range(&mut self, range: RangeInclusive<u16>) -> u16 {
+        let (start, end) = (*range.start(), *range.end());
+        assert!(start <= end, "The range given to generate_random_u16_in_range must not be empty.");
+        let range_size = (end - start) as u64 + 1;
+        // Values below this threshold would make some remainders more likely than others, so they are rejected.
+        let rejection_threshold = range_size.wrapping_neg() % range_size;
+        loop {
+            let random_number = self.next_u64();
+            if random_number >= rejection_threshold {
+                return start + (random_number % range_size) as u16;
+            }
+        }
+    }
+
+    /// Returns `true` with probability `num / denom`.
+    fn chance(&mut self, num: u16, denom: u16) -> bool {
+        assert!(denom != 0, "The denominator of a chance cannot be zero.");
+        self.generate_random_u16_in_range(1..=denom) <= num
+    }
+
+    /// Rolls a die with `sides` faces numbered from 1.
+    fn roll_die(&mut self, sides: u16) -> u16 {
+        assert!(sides != 0, "A die must have at least one side.");
+        self.generate_random_u16_in_range(1..=sides)
+    }
+}
+
+/// xoshiro256** generator, with its state expanded from the 64-bit start seed using SplitMix64.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Prng {
     start_seed: u64,
-    current_seed: u64,
+    state: [u64; 4],
 }
 
-const A: u64 = 0x5D588B656C078965;
-const C: u64 = 0x00269EC3;
-
 impl Prng {
-    #[cfg(test)]
     pub(crate) fn new(start_seed: u64) -> Self {
-        Self {
-            start_seed,
-            current_seed: start_seed,
+        let mut splitmix_state = start_seed;
+        let mut state = [0; 4];
+        for word in state.iter_mut() {
+            *word = splitmix64(&mut splitmix_state);
         }
+        Self { start_seed, state }
     }
 
     pub(crate) fn from_current_time() -> Self {
-        let start_seed = Self::seed_from_current_time();
-        Self {
-            start_seed,
-            current_seed: start_seed,
-        }
+        Self::new(Self::seed_from_current_time())
     }
 
-    /// Returns each u16 in the range with equal probability. If the range contains one number, it returns it with 100% certainty.
-    pub(crate) fn generate_random_u16_in_range(&mut self, mut range: RangeInclusive<u16>) -> u16 {
-        let start = range.next().expect("The range given to generate_number_in_range must have a first element.");
-        let end = range
-            .next_back()
-            .expect("The range given to generate_number_in_range must have a last element.");
-        assert!(end >= 1u16, "The end of the range should be 1 or higher");
-        if end == start {
-            return start;
-        }
-        let random_number = self.next();
-        let range = (end - start + 1) as f64;
-
-        ((random_number as f64 / MAX as f64) * range) as u16 + start
+    /// The seed the generator was created with. Together with the choices made each turn, this is
+    /// enough to replay a battle exactly.
+    #[inline(always)]
+    pub fn start_seed(&self) -> u64 {
+        self.start_seed
     }
 
-    /// Uses the formula `x_{n+1} = (A * x_n) + C` where `A = 0x5D588B656C078965` and
-    /// C = 0x00269EC3.
-    fn next(&mut self) -> u64 {
-        self.current_seed = self.current_seed.wrapping_mul(A).wrapping_add(C);
-        self.current_seed
+    /// Nanosecond resolution, so that battles created in quick succession don't share a seed.
+    fn seed_from_current_time() -> u64 {
+        time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap().as_nanos() as u64
     }
+}
 
-    #[cfg(test)]
-    pub fn chance(&mut self, num: u16, denom: u16) -> bool {
-        assert!(denom != 0);
-        self.generate_random_u16_in_range(1..=denom) <= num
+impl Rng for Prng {
+    fn next_u64(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s1.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = *s1 << 17;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(45);
+        result
     }
+}
 
-    fn seed_from_current_time() -> u64 {
-        time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap().as_secs()
-    }
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
 }
@@ -0,0 +1,440 @@
+/*!
+Event handlers backed by [Rhai](https://rhai.rs) script functions, so that new abilities, items and
+so on can be prototyped without recompiling. A script handler is added to an `EventHandlerDeck` with
+`EventHandlerDeck::add_script` and is ordered and filtered exactly like any other handler.
+
+Script handlers are called with the effector's `MonsterID` and the event context, and return the
+event's return type: `bool` for `Outcome`s, an integer for `Percent`s and numbers, and nothing for
+`Nothing`. Handlers for modifier events, such as `OnModifyBasePower`, find the value being modified
+in `context.value` and return the modified value. If a script fails, returns the wrong type or runs
+for too long, the turn being simulated fails with `SimError::ScriptFailed`.
+
+```rhai
+// Raises the owner's Speed whenever it is hit by a Water move.
+fn speed_boost_on_hit(effector, context) {
+    if has_type(context.target, "Water") {
+        raise_stat(effector, "Speed", 1);
+    }
+    true
+}
+```
+
+Scripts can query the battle with `name`, `hp`, `max_hp`, `stat`, `stat_stage`, `has_type`,
+`types`, `status` and `is_fainted`, and can act on it with `deal_direct_damage`, `restore_health`,
+`raise_stat`, `lower_stat`, `activate_ability`, `push_message` and `chance`.
+*/
+
+use std::{any::Any, cell::{Cell, RefCell}, error::Error, fmt::Display, path::Path};
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, AST, INT};
+
+use crate::sim::{
+    not, AbilityUseContext, ActivateAbility, BattleSimulator, DamageContext, DealDirectDamage, Event, ItemUseContext, LowerStat, MonsterID, MoveHitContext,
+    MoveUseContext, Nothing, Outcome, Percent, RaiseStat, RestoreHealth, Rng, SimError, Stat, StatChangeContext, StatContext, StatusContext, SwitchInContext, TurnContext, Type,
+};
+
+struct ScriptLibrary {
+    engine: Engine,
+    ast: AST,
+}
+
+thread_local! {
+    static SCRIPT_LIBRARY: RefCell<ScriptLibrary> = RefCell::new(ScriptLibrary { engine: script_engine(), ast: AST::empty() });
+    /// The simulator running the current script handler, only set while a script is being called.
+    static CURRENT_SIMULATOR: Cell<*mut BattleSimulator> = const { Cell::new(std::ptr::null_mut()) };
+    /// The script handler the dispatcher is about to resolve.
+    static CURRENT_SCRIPT_HANDLER: RefCell<Option<ScriptHandlerCall>> = const { RefCell::new(None) };
+}
+
+struct ScriptHandlerCall {
+    script_function: &'static str,
+    /// The event's return value if the script fails.
+    fallback: Box<dyn Any>,
+}
+
+/// Compiles `source` and adds its functions to the script functions available to handlers on this
+/// thread. Functions with the same name as previously loaded ones replace them.
+pub fn load_script(source: &str) -> Result<(), ScriptError> {
+    SCRIPT_LIBRARY.with_borrow_mut(|library| {
+        let ast = library.engine.compile(source)?;
+        library.ast.combine(ast);
+        Ok(())
+    })
+}
+
+pub fn load_script_file(path: impl AsRef<Path>) -> Result<(), ScriptError> {
+    load_script(&std::fs::read_to_string(path)?)
+}
+
+/// Removes every loaded script function on this thread.
+pub fn clear_scripts() {
+    SCRIPT_LIBRARY.with_borrow_mut(|library| library.ast = AST::empty());
+}
+
+/// Runs `dispatch` with `script_function` marked as the function backing the handler being resolved, which
+/// returns `fallback` if the script fails.
+pub(crate) fn with_script_function<R: 'static>(script_function: &'static str, fallback: R, dispatch: impl FnOnce() -> R) -> R {
+    let previous_call = CURRENT_SCRIPT_HANDLER.replace(Some(ScriptHandlerCall { script_function, fallback: Box::new(fallback) }));
+    let result = dispatch();
+    CURRENT_SCRIPT_HANDLER.set(previous_call);
+    result
+}
+
+/// The `EffectFunction` of every script handler, it calls the script function the dispatcher marked as current.
+/// If the script fails or returns the wrong type, the failure is kept as the error `simulate_turn` returns,
+/// and the handler returns the fallback the dispatcher gave it.
+pub(crate) fn call_script_handler<E: Event>(sim: &mut BattleSimulator, effector_id: MonsterID, context: E::ContextType) -> E::EventReturnType
+where
+    E::ContextType: IntoScriptValue,
+    E::EventReturnType: FromScriptValue,
+{
+    let ScriptHandlerCall { script_function, fallback } = CURRENT_SCRIPT_HANDLER
+        .take()
+        .expect("Script handlers are only resolved by the event dispatcher.");
+    let previous_simulator = CURRENT_SIMULATOR.replace(sim as *mut BattleSimulator);
+    let result = SCRIPT_LIBRARY.with_borrow(|library| {
+        library.engine.call_fn::<Dynamic>(
+            &mut rhai::Scope::new(),
+            &library.ast,
+            script_function,
+            (Dynamic::from(effector_id), context.into_script_value()),
+        )
+    });
+    CURRENT_SIMULATOR.set(previous_simulator);
+
+    let return_value = result.map_err(|error| error.to_string()).and_then(|value| {
+        let type_name = value.type_name();
+        E::EventReturnType::from_script_value(value).ok_or_else(|| {
+            let event_name = std::any::type_name::<E>().rsplit("::").next().unwrap_or_default();
+            format!["It returned a {}, which is the wrong type for {}.", type_name, event_name]
+        })
+    });
+    return_value.unwrap_or_else(|message| {
+        sim.event_stack.record_error(SimError::ScriptFailed { script_function, message });
+        *fallback.downcast().expect("The dispatcher gives a fallback of the event's return type.")
+    })
+}
+
+/// Gives registered script functions access to the simulator running the current script.
+fn with_simulator<R>(f: impl FnOnce(&mut BattleSimulator) -> R) -> R {
+    let sim = CURRENT_SIMULATOR.get();
+    assert!(not!(sim.is_null()), "Battle functions can only be called from script handlers.");
+    // SAFETY: The pointer is set from a `&mut BattleSimulator` for exactly the duration of the script
+    // call in `call_script_handler`, during which that reference is not used by anything else.
+    f(unsafe { &mut *sim })
+}
+
+/// The number of operations a script handler can run before it is stopped, so that a script that never
+/// ends fails the turn instead of hanging the simulator.
+const MAX_SCRIPT_OPERATIONS: u64 = 100_000;
+/// How deeply script functions can call each other, which stops runaway recursion.
+const MAX_SCRIPT_CALL_LEVELS: usize = 32;
+const MAX_SCRIPT_EXPRESSION_DEPTH: usize = 64;
+const MAX_SCRIPT_COLLECTION_SIZE: usize = 1024;
+
+fn script_engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine
+        .set_max_operations(MAX_SCRIPT_OPERATIONS)
+        .set_max_call_levels(MAX_SCRIPT_CALL_LEVELS)
+        .set_max_expr_depths(MAX_SCRIPT_EXPRESSION_DEPTH, MAX_SCRIPT_EXPRESSION_DEPTH)
+        .set_max_string_size(MAX_SCRIPT_COLLECTION_SIZE)
+        .set_max_array_size(MAX_SCRIPT_COLLECTION_SIZE)
+        .set_max_map_size(MAX_SCRIPT_COLLECTION_SIZE);
+
+    engine
+        .register_type_with_name::<MonsterID>("MonsterID")
+        .register_fn("to_string", |monster_id: &mut MonsterID| monster_id.to_string())
+        .register_fn("to_debug", |monster_id: &mut MonsterID| monster_id.to_string())
+        .register_fn("==", |a: MonsterID, b: MonsterID| a == b)
+        .register_fn("!=", |a: MonsterID, b: MonsterID| a != b);
+
+    // Queries
+    engine
+        .register_fn("name", |monster_id: MonsterID| with_simulator(|sim| sim.battle.monster(monster_id).name()))
+        .register_fn("hp", |monster_id: MonsterID| with_simulator(|sim| sim.battle.monster(monster_id).current_health() as INT))
+        .register_fn("max_hp", |monster_id: MonsterID| with_simulator(|sim| sim.battle.monster(monster_id).max_health() as INT))
+        .register_fn("is_fainted", |monster_id: MonsterID| with_simulator(|sim| sim.battle.monster(monster_id).is_fainted()))
+        .register_fn("stat", |monster_id: MonsterID, stat: &str| -> Result<INT, Box<EvalAltResult>> {
+            let stat = parse_stat(stat)?;
+            if matches!(stat, Stat::Accuracy | Stat::Evasion) {
+                return Err(format!["{:?} has no value, only a stage, use `stat_stage` instead.", stat].into());
+            }
+            Ok(with_simulator(|sim| sim.effective_stat(monster_id, stat)) as INT)
+        })
+        .register_fn("stat_stage", |monster_id: MonsterID, stat: &str| -> Result<INT, Box<EvalAltResult>> {
+            let stat = parse_staged_stat(stat)?;
+            Ok(with_simulator(|sim| sim.battle.monster(monster_id).stat_modifier(stat)) as INT)
+        })
+        .register_fn("types", |monster_id: MonsterID| -> Array {
+            let (primary_type, secondary_type) = with_simulator(|sim| sim.battle.monster(monster_id).species().type_());
+            std::iter::once(primary_type).chain(secondary_type).map(|type_| Dynamic::from(format!["{:?}", type_])).collect()
+        })
+        .register_fn("has_type", |monster_id: MonsterID, type_: &str| -> Result<bool, Box<EvalAltResult>> {
+            let type_ = parse_type(type_)?;
+            let (primary_type, secondary_type) = with_simulator(|sim| sim.battle.monster(monster_id).species().type_());
+            Ok(primary_type == type_ || secondary_type == Some(type_))
+        })
+        .register_fn("status", |monster_id: MonsterID| -> Dynamic {
+            with_simulator(|sim| sim.battle.monster(monster_id).status())
+                .map_or(Dynamic::UNIT, |status| Dynamic::from(format!["{:?}", status]))
+        });
+
+    // Effects
+    engine
+        .register_fn("deal_direct_damage", |target_id: MonsterID, amount: INT| {
+            with_simulator(|sim| DealDirectDamage(sim, target_id, (target_id, clamp_to_u16(amount))) as INT)
+        })
+        .register_fn("restore_health", |target_id: MonsterID, amount: INT| {
+            with_simulator(|sim| RestoreHealth(sim, target_id, (target_id, clamp_to_u16(amount))) as INT)
+        })
+        .register_fn("raise_stat", |target_id: MonsterID, stat: &str, stages: INT| -> Result<bool, Box<EvalAltResult>> {
+            let stat = parse_staged_stat(stat)?;
+            Ok(with_simulator(|sim| RaiseStat(sim, target_id, (target_id, stat, stages.clamp(0, 3) as u8))).succeeded())
+        })
+        .register_fn("lower_stat", |target_id: MonsterID, stat: &str, stages: INT| -> Result<bool, Box<EvalAltResult>> {
+            let stat = parse_staged_stat(stat)?;
+            Ok(with_simulator(|sim| LowerStat(sim, target_id, (target_id, stat, stages.clamp(0, 3) as u8))).succeeded())
+        })
+        .register_fn("activate_ability", |owner_id: MonsterID| {
+            with_simulator(|sim| ActivateAbility(sim, owner_id, AbilityUseContext::new(owner_id))).succeeded()
+        })
+        .register_fn("push_message", |message: &str| with_simulator(|sim| sim.push_message(message)))
+        .register_fn("chance", |num: INT, denom: INT| -> Result<bool, Box<EvalAltResult>> {
+            if denom <= 0 {
+                return Err("The denominator of a chance must be positive.".into());
+            }
+            Ok(with_simulator(|sim| sim.prng().chance(clamp_to_u16(num), clamp_to_u16(denom))))
+        });
+
+    engine
+}
+
+fn clamp_to_u16(value: INT) -> u16 {
+    value.clamp(0, u16::MAX as INT) as u16
+}
+
+const STATS: [Stat; 8] = [
+    Stat::Hp,
+    Stat::PhysicalAttack,
+    Stat::PhysicalDefense,
+    Stat::SpecialAttack,
+    Stat::SpecialDefense,
+    Stat::Speed,
+    Stat::Accuracy,
+    Stat::Evasion,
+];
+
+const TYPES: [Type; 18] = [
+    Type::Bug, Type::Dark, Type::Dragon, Type::Electric, Type::Fairy, Type::Fighting, Type::Fire, Type::Flying, Type::Ghost,
+    Type::Grass, Type::Ground, Type::Ice, Type::Normal, Type::Poison, Type::Psychic, Type::Rock, Type::Steel, Type::Water,
+];
+
+/// Stats are named as in Rust, _e.g._ "PhysicalAttack".
+fn parse_stat(name: &str) -> Result<Stat, Box<EvalAltResult>> {
+    STATS.into_iter()
+        .find(|stat| format!["{:?}", stat] == name)
+        .ok_or_else(|| format!["{} is not a stat.", name].into())
+}
+
+/// Stats that have a stage, which is every stat but Hp.
+fn parse_staged_stat(name: &str) -> Result<Stat, Box<EvalAltResult>> {
+    match parse_stat(name)? {
+        Stat::Hp => Err("Hp has no stat stage.".into()),
+        stat => Ok(stat),
+    }
+}
+
+fn parse_type(name: &str) -> Result<Type, Box<EvalAltResult>> {
+    TYPES.into_iter()
+        .find(|type_| format!["{:?}", type_] == name)
+        .ok_or_else(|| format!["{} is not a type.", name].into())
+}
+
+/// An event context that can be passed to script handlers. Contexts become object maps with the
+/// same field names minus the `_id` suffixes, except that the move used is given by its index in the
+/// user's moveset as `move_index`, which is `()` for damage not dealt by a move.
+pub trait IntoScriptValue {
+    fn into_script_value(self) -> Dynamic;
+}
+
+/// An event return type that script handlers can produce.
+pub trait FromScriptValue: Sized {
+    fn from_script_value(value: Dynamic) -> Option<Self>;
+}
+
+impl IntoScriptValue for u16 {
+    fn into_script_value(self) -> Dynamic {
+        Dynamic::from(self as INT)
+    }
+}
+
+impl IntoScriptValue for Percent {
+    fn into_script_value(self) -> Dynamic {
+        Dynamic::from(self.0 as INT)
+    }
+}
+
+/// The context of modifier events, which is given to scripts as the context's map with the value
+/// being modified under `value`.
+impl<C: IntoScriptValue, V: IntoScriptValue> IntoScriptValue for (C, V) {
+    fn into_script_value(self) -> Dynamic {
+        let (context, value) = self;
+        let mut context = context.into_script_value().try_cast::<Map>().unwrap_or_default();
+        context.insert("value".into(), value.into_script_value());
+        Dynamic::from_map(context)
+    }
+}
+
+impl IntoScriptValue for Nothing {
+    fn into_script_value(self) -> Dynamic {
+        Dynamic::UNIT
+    }
+}
+
+impl IntoScriptValue for MonsterID {
+    fn into_script_value(self) -> Dynamic {
+        Dynamic::from(self)
+    }
+}
+
+fn script_map<const N: usize>(fields: [(&str, Dynamic); N]) -> Dynamic {
+    Dynamic::from_map(fields.into_iter().map(|(name, value)| (name.into(), value)).collect::<Map>())
+}
+
+impl IntoScriptValue for MoveUseContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([
+            ("move_user", Dynamic::from(self.move_user_id)),
+            ("move_index", Dynamic::from(self.move_used_id.move_number as INT)),
+            ("targets", Dynamic::from_array(self.target_ids.iter().copied().map(Dynamic::from).collect())),
+        ])
+    }
+}
+
+impl IntoScriptValue for MoveHitContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([
+            ("move_user", Dynamic::from(self.move_user_id)),
+            ("move_index", Dynamic::from(self.move_used_id.move_number as INT)),
+            ("target", Dynamic::from(self.target_id)),
+        ])
+    }
+}
+
+impl IntoScriptValue for AbilityUseContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([("ability_owner", Dynamic::from(self.ability_owner_id))])
+    }
+}
+
+impl IntoScriptValue for ItemUseContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([("item_holder", Dynamic::from(self.item_holder_id))])
+    }
+}
+
+impl IntoScriptValue for StatContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([("monster", Dynamic::from(self.monster_id)), ("stat", Dynamic::from(format!["{:?}", self.stat]))])
+    }
+}
+
+impl IntoScriptValue for StatusContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([("monster", Dynamic::from(self.monster_id)), ("status", Dynamic::from(format!["{:?}", self.status]))])
+    }
+}
+
+impl IntoScriptValue for DamageContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([
+            ("source", Dynamic::from(self.source_id)),
+            ("target", Dynamic::from(self.target_id)),
+            ("amount", Dynamic::from(self.amount as INT)),
+            ("move_index", self.move_used_id.map_or(Dynamic::UNIT, |move_used_id| Dynamic::from(move_used_id.move_number as INT))),
+        ])
+    }
+}
+
+impl IntoScriptValue for StatChangeContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([
+            ("monster", Dynamic::from(self.monster_id)),
+            ("stat", Dynamic::from(format!["{:?}", self.stat])),
+            ("stages", Dynamic::from(self.stages as INT)),
+        ])
+    }
+}
+
+impl IntoScriptValue for SwitchInContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([("monster", Dynamic::from(self.monster_id))])
+    }
+}
+
+impl IntoScriptValue for TurnContext {
+    fn into_script_value(self) -> Dynamic {
+        script_map([("turn_number", Dynamic::from(self.turn_number as INT))])
+    }
+}
+
+impl FromScriptValue for Nothing {
+    fn from_script_value(_value: Dynamic) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl FromScriptValue for Outcome {
+    fn from_script_value(value: Dynamic) -> Option<Self> {
+        value.as_bool().ok().map(Outcome::from)
+    }
+}
+
+impl FromScriptValue for Percent {
+    fn from_script_value(value: Dynamic) -> Option<Self> {
+        value.as_int().ok().map(|percent| Percent(clamp_to_u16(percent)))
+    }
+}
+
+impl FromScriptValue for u16 {
+    fn from_script_value(value: Dynamic) -> Option<Self> {
+        value.as_int().ok().map(clamp_to_u16)
+    }
+}
+
+impl FromScriptValue for u8 {
+    fn from_script_value(value: Dynamic) -> Option<Self> {
+        value.as_int().ok().map(|value| value.clamp(0, u8::MAX as INT) as u8)
+    }
+}
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(std::io::Error),
+    Parse(rhai::ParseError),
+}
+
+impl Error for ScriptError {}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptError::Io(error) => write!(f, "{}", error),
+            ScriptError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<std::io::Error> for ScriptError {
+    fn from(error: std::io::Error) -> Self {
+        ScriptError::Io(error)
+    }
+}
+
+impl From<rhai::ParseError> for ScriptError {
+    fn from(error: rhai::ParseError) -> Self {
+        ScriptError::Parse(error)
+    }
+}
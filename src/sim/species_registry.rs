@@ -0,0 +1,112 @@
+use crate::sim::{AbilitySpecies, FieldEffectSpecies, ItemSpecies, MonsterSpecies, MoveSpecies, SideConditionSpecies, VolatileStatusSpecies};
+
+/// Resolves species from their dex numbers. Battle state only stores references to species, so a
+/// registry is needed to turn a saved battle back into one that can be simulated.
+pub trait SpeciesRegistry {
+    fn monster_species(&self, dex_number: u16) -> Option<&'static MonsterSpecies>;
+    fn move_species(&self, dex_number: u16) -> Option<&'static MoveSpecies>;
+    fn ability_species(&self, dex_number: u16) -> Option<&'static AbilitySpecies>;
+    fn item_species(&self, dex_number: u16) -> Option<&'static ItemSpecies>;
+    fn volatile_status_species(&self, dex_number: u16) -> Option<&'static VolatileStatusSpecies>;
+    fn field_effect_species(&self, dex_number: u16) -> Option<&'static FieldEffectSpecies>;
+    fn side_condition_species(&self, dex_number: u16) -> Option<&'static SideConditionSpecies>;
+}
+
+/// A kind of species that can be looked up in a `SpeciesRegistry`.
+pub trait RegisteredSpecies: 'static {
+    /// Used in error messages, _e.g._ "Move".
+    const KIND: &'static str;
+
+    fn dex_number(&self) -> u16;
+
+    fn lookup(registry: &dyn SpeciesRegistry, dex_number: u16) -> Option<&'static Self>;
+}
+
+macro_rules! impl_registered_species {
+    ($($species: ty, $kind: literal, $lookup: ident;)*) => {
+        $(
+            impl RegisteredSpecies for $species {
+                const KIND: &'static str = $kind;
+
+                fn dex_number(&self) -> u16 {
+                    <$species>::dex_number(self)
+                }
+
+                fn lookup(registry: &dyn SpeciesRegistry, dex_number: u16) -> Option<&'static Self> {
+                    registry.$lookup(dex_number)
+                }
+            }
+        )*
+    };
+}
+
+impl_registered_species!(
+    MonsterSpecies, "Monster", monster_species;
+    MoveSpecies, "Move", move_species;
+    AbilitySpecies, "Ability", ability_species;
+    ItemSpecies, "Item", item_species;
+    VolatileStatusSpecies, "Volatile Status", volatile_status_species;
+    FieldEffectSpecies, "Field Effect", field_effect_species;
+    SideConditionSpecies, "Side Condition", side_condition_species;
+);
+
+#[cfg(feature = "serde")]
+pub use serialization::with_species_registry;
+
+#[cfg(feature = "serde")]
+pub(crate) mod serialization {
+    use std::cell::Cell;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::{RegisteredSpecies, SpeciesRegistry};
+
+    thread_local! {
+        static ACTIVE_REGISTRY: Cell<Option<&'static dyn SpeciesRegistry>> = const { Cell::new(None) };
+    }
+
+    /// Runs `f` with `registry` used to resolve the species of anything deserialized inside it on this
+    /// thread. Prefer `BattleState::deserialize_with_registry` for deserializing whole battles.
+    pub fn with_species_registry<R>(registry: &'static dyn SpeciesRegistry, f: impl FnOnce() -> R) -> R {
+        struct RestoreOnDrop(Option<&'static dyn SpeciesRegistry>);
+        impl Drop for RestoreOnDrop {
+            fn drop(&mut self) {
+                ACTIVE_REGISTRY.with(|active_registry| active_registry.set(self.0));
+            }
+        }
+
+        let _restore = RestoreOnDrop(ACTIVE_REGISTRY.with(|active_registry| active_registry.replace(Some(registry))));
+        f()
+    }
+
+    /// `serde(with = ...)` glue that stores a species reference as its dex number.
+    pub(crate) mod species_ref {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer, T: RegisteredSpecies>(species: &&'static T, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u16(species.dex_number())
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>, T: RegisteredSpecies>(deserializer: D) -> Result<&'static T, D::Error> {
+            let dex_number = u16::deserialize(deserializer)?;
+            let registry = ACTIVE_REGISTRY
+                .with(Cell::get)
+                .ok_or_else(|| D::Error::custom("species can only be deserialized inside `with_species_registry`"))?;
+            T::lookup(registry, dex_number)
+                .ok_or_else(|| D::Error::custom(format!["no {} species with dex number {} is registered", T::KIND, dex_number]))
+        }
+    }
+
+    /// `serde(with = ...)` glue for optional `&'static str`s such as nicknames, which are leaked on deserialization.
+    pub(crate) mod static_str_option {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer>(string: &Option<&'static str>, serializer: S) -> Result<S::Ok, S::Error> {
+            serde::Serialize::serialize(string, serializer)
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<&'static str>, D::Error> {
+            Ok(Option::<String>::deserialize(deserializer)?.map(|string| &*String::leak(string)))
+        }
+    }
+}
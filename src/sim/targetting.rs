@@ -10,6 +10,7 @@ in the battle are. Monsters in the Field zone have a value indicating which tile
 they are standing on.
 */
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoardPosition {
     Bench,
     Field(FieldPosition),
@@ -40,6 +41,7 @@ it "should" be that `OpponentLeft` faces `AllyRight` but I think it gets difficu
 to wrap your head around that every time.
 */
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FieldPosition {
     AllySideLeft,
     AllySideCentre,
@@ -90,7 +92,7 @@ impl FieldPosition {
         }
     }
 
-    fn to_coords(&self) -> (i8, i8) {
+    fn to_coords(self) -> (i8, i8) {
         match self {
             FieldPosition::AllySideLeft => (0, 0),
             FieldPosition::AllySideCentre => (1, 0),
@@ -107,12 +109,6 @@ impl FieldPosition {
         self_side != other_position_side
     }
     
-    pub(crate) fn is_on_the_same_side_as(&self, other_position: FieldPosition) -> bool {
-        let self_side = self.to_coords().1; // The second element tells us which side the position is on.
-        let other_position_side = other_position.to_coords().1;
-        self_side == other_position_side
-    }
-    
     pub(crate) fn side(&self) -> TeamID {
         match self {
             FieldPosition::AllySideLeft | FieldPosition::AllySideCentre | FieldPosition::AllySideRight => TeamID::Allies,
@@ -1,5 +1,27 @@
+#[cfg(test)]
+use crate::sim::{
+    test_ability_dex::FlashFire,
+    test_move_dex::Scratch,
+    BattleState, MonsterBuilder, MonsterBuilderExt, MonsterSpecies, MonsterTeam, MoveBuilderExt, AbilityBuilderExt,
+};
 
-#[cfg(all(test, feature = "debug"))]
+/// A single battle with one Monster on each team, which is where most of the tests below start from.
+#[cfg(test)]
+fn one_on_one_battle(ally: MonsterBuilder, opponent: MonsterBuilder) -> BattleState {
+    BattleState::spawn()
+        .add_ally_team(MonsterTeam::spawn().add_monster(ally))
+        .add_opponent_team(MonsterTeam::spawn().add_monster(opponent))
+        .build()
+}
+
+/// A Monster of the given species that only knows Scratch and has Flash Fire.
+#[cfg(test)]
+fn spawn_with_scratch(species: &'static MonsterSpecies) -> MonsterBuilder {
+    species.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn())
+}
+
+
+#[cfg(test)]
 mod battle {
 
     #[test]
@@ -88,14 +110,14 @@ Opponent Team
     }
 }
 
-#[cfg(all(test, feature = "debug"))]
+#[cfg(test)]
 mod event {
 
     #[test]
     #[cfg(feature = "debug")]
     fn test_print_event_handler() {
         use crate::sim::game_mechanics::test_ability_dex::FlashFire;
-        let event_handler = FlashFire.event_handlers().on_try_move_hit.unwrap();
+        let event_handler = FlashFire.event_handlers().on_try_move_hit[0];
         println!("{:?}", event_handler);
     }
 
@@ -105,75 +127,2334 @@ mod event {
         use crate::sim::test_ability_dex::FlashFire;
         println!("{:#?}", FlashFire.event_handlers());
     }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_several_handlers_for_one_event_are_ordered_and_filtered_separately() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+
+        #[allow(non_upper_case_globals)]
+        const Watchful: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Watchful",
+                event_handlers: | | {
+                    EventHandlerDeck::empty()
+                        .add(event_dex::OnTryMove, |sim, effector_id, _context| {
+                            sim.push_message(format!["{effector_id} watched an opponent"]);
+                            Outcome::Success
+                        }, source_code_location!())
+                        .add_handler(
+                            EventHandler::new(event_dex::OnTryMove, |sim, effector_id, _context| {
+                                sim.push_message(format!["{effector_id} watched itself"]);
+                                Outcome::Success
+                            }, source_code_location!())
+                            .with_order(1)
+                            .with_filtering_options(EventFilteringOptions {
+                                allowed_broadcaster_relation_flags: TargetFlags::SELF.union(TargetFlags::ADJACENT).union(TargetFlags::NONADJACENT),
+                                requires_being_active: true,
+                            })
+                        )
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        assert_eq!(Watchful.event_handlers().on_try_move.count(), 2);
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), Watchful.spawn()),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), Watchful.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        let ally_handler_orders = sim.battle.event_handlers_for(event_dex::OnTryMove)
+            .into_iter()
+            .filter(|owned_event_handler| owned_event_handler.owner == EventHandlerOwner::Monster(ALLY_1))
+            .map(|owned_event_handler| owned_event_handler.activation_order.order)
+            .collect::<Vec<_>>();
+        assert_eq!(ally_handler_orders, vec![0, 1]);
+
+        let context = MoveUseContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, monsim_utils::MaxSizedVec::from_slice(&[OPPONENT_1]));
+        assert_eq!(EventDispatcher::dispatch_trial_event(&mut sim, event_dex::OnTryMove, ALLY_1, context), Outcome::Success);
+        let messages = sim.battle.message_log.as_vec();
+        assert!(messages.iter().any(|message| message == &format!["{ALLY_1} watched itself"]));
+        assert!(messages.iter().any(|message| message == &format!["{OPPONENT_1} watched an opponent"]));
+        assert!(not!(messages.iter().any(|message| message == &format!["{ALLY_1} watched an opponent"])));
+        assert!(not!(messages.iter().any(|message| message == &format!["{OPPONENT_1} watched itself"])));
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_damage_hit_and_stat_change_events_carry_their_context() {
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::effects::UseMove;
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal, Zombler},
+            test_move_dex::Scratch,
+        };
+
+        // Blocks opponents from lowering its stats, hurts attackers and counts how often it is hit.
+        #[allow(non_upper_case_globals)]
+        const PricklyGuard: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Prickly Guard",
+                event_handlers: | | {
+                    EventHandlerDeck::empty()
+                        .add(event_dex::OnTryLowerStat, |_sim, effector_id, StatChangeContext { monster_id, .. }| {
+                            Outcome::from(monster_id != effector_id)
+                        }, source_code_location!())
+                        .add(event_dex::OnDamageDealt, |sim, effector_id, DamageContext { source_id, target_id, amount, move_used_id }| {
+                            if target_id == effector_id && move_used_id.is_some() {
+                                _ = DealDirectDamage(sim, effector_id, (source_id, (amount / 2).max(1)));
+                            }
+                        }, source_code_location!())
+                        .add(event_dex::OnHit, |sim, effector_id, MoveHitContext { target_id, .. }| {
+                            if target_id == effector_id {
+                                sim.push_message("Prickly Guard was hit");
+                            }
+                        }, source_code_location!())
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), PricklyGuard.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        // Only the opponent's own effects can lower its stats.
+        assert_eq!(LowerStat(&mut sim, ALLY_1, (OPPONENT_1, Stat::Speed, 1)), Outcome::Failure);
+        assert_eq!(LowerStat(&mut sim, OPPONENT_1, (OPPONENT_1, Stat::Speed, 1)), Outcome::Success);
+        assert_eq!(sim.battle.monster(OPPONENT_1).stat_modifier(Stat::Speed), -1);
+
+        let scratch_id = MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 };
+        UseMove(&mut sim, ALLY_1, MoveUseContext::new(scratch_id, monsim_utils::MaxSizedVec::from_slice(&[OPPONENT_1])));
+        let damage_taken = sim.battle.monster(OPPONENT_1).max_health() - sim.battle.monster(OPPONENT_1).current_health();
+        assert!(damage_taken > 0);
+        assert_eq!(sim.battle.monster(ALLY_1).max_health() - sim.battle.monster(ALLY_1).current_health(), (damage_taken / 2).max(1));
+        assert_eq!(sim.battle.message_log.as_vec().iter().filter(|message| *message == "Prickly Guard was hit").count(), 1);
+
+        // Zombler is immune to Scratch, so the hit doesn't connect.
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            Zombler.spawn((Scratch.spawn(), None, None, None), PricklyGuard.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        UseMove(&mut sim, ALLY_1, MoveUseContext::new(scratch_id, monsim_utils::MaxSizedVec::from_slice(&[OPPONENT_1])));
+        assert_eq!(sim.battle.monster(ALLY_1).current_health(), sim.battle.monster(ALLY_1).max_health());
+        assert!(not!(sim.battle.message_log.as_vec().iter().any(|message| message == "Prickly Guard was hit")));
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_modify_events_pass_values_through_the_handler_chain() {
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+
+        #[allow(non_upper_case_globals)]
+        const Phantasmal: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Phantasmal",
+                event_handlers: | | {
+                    EventHandlerDeck::empty()
+                        .add_handler(
+                            EventHandler::new(event_dex::OnModifyBasePower, |_sim, _effector_id, (_context, base_power)| {
+                                base_power * 2
+                            }, source_code_location!())
+                            .with_order(1)
+                        )
+                        .add(event_dex::OnModifyBasePower, |_sim, _effector_id, (_context, base_power)| {
+                            base_power + 10
+                        }, source_code_location!())
+                        .add(event_dex::OnModifyTypeEffectiveness, |_sim, effector_id, (MoveHitContext { target_id, .. }, effectiveness)| {
+                            if target_id == effector_id { Percent(0) } else { effectiveness }
+                        }, source_code_location!())
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), Phantasmal.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        // The handler with the higher order sees the initial value first.
+        let context = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, OPPONENT_1);
+        assert_eq!(EventDispatcher::dispatch_modify_event(&mut sim, event_dex::OnModifyBasePower, ALLY_1, context, 40), 90);
+        // Nothing responds to the opponent's own broadcasts, so the value is unchanged.
+        let context = MoveHitContext::new(MoveID { owner_id: OPPONENT_1, move_number: MoveNumber::_1 }, ALLY_1);
+        assert_eq!(EventDispatcher::dispatch_modify_event(&mut sim, event_dex::OnModifyBasePower, OPPONENT_1, context, 40), 40);
+
+        let context = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, OPPONENT_1);
+        assert_eq!(DealDefaultDamage(&mut sim, ALLY_1, context), Outcome::Failure);
+        assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), sim.battle.monster(OPPONENT_1).max_health());
+        assert!(sim.battle.message_log.as_vec().iter().any(|message| message == "It was ineffective..."));
+    }
 }
 
-#[cfg(all(test, feature = "debug"))]
-mod prng {
-    use crate::sim::prng::*;
+#[cfg(test)]
+mod event_stack {
 
     #[test]
-    fn test_prng_percentage_chance() {
-        let mut prng = Prng::from_current_time();
-        let mut dist = [0u64; 100];
-        for _ in 0..=10_000_000 {
-            let n = prng.generate_random_u16_in_range(0..=99) as usize;
-            dist[n] += 1;
-        }
-        let avg_deviation = dist
-            .iter()
-            .map(|it| ((*it as f32 / 100_000.0) - 1.0).abs())
-            .reduce(|it, acc| it + acc)
-            .expect("We should always get some average value.")
-            / 100.0;
-        let avg_deviation = f32::floor(avg_deviation * 100_000.0) / 100_000.0;
-        println!("LCRNG has {:?}% average deviation (threshold is at 0.005%)", avg_deviation);
-        assert!(avg_deviation < 5.0e-3);
+    fn test_recursive_events_stop_at_the_depth_limit_and_suppression_disables_handlers() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        #[cfg(feature = "debug")]
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+
+        // Each activation makes the opposing Echo activate in response, forever.
+        #[allow(non_upper_case_globals)]
+        const Echo: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Echo",
+                event_handlers: | | {
+                    #[cfg(feature="debug")]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        }, source_code_location!());
+                    #[cfg(not(feature="debug"))]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        });
+                    out
+                },
+                on_activate_effect: Effect::from(|sim, effector_id, _context| {
+                    sim.push_message(format!["{effector_id}'s Echo activated!"]);
+                }),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), Echo.spawn()),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), Echo.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        let count_activations = |sim: &BattleSimulator| sim.battle.message_log.as_vec().iter().filter(|message| message.ends_with("Echo activated!")).count();
+
+        _ = ActivateAbility(&mut sim, ALLY_1, AbilityUseContext::new(ALLY_1));
+        assert_eq!(sim.event_stack().depth(), 0);
+        let Some(SimError::EventDepthLimitReached(event_names)) = sim.event_stack.take_error() else {
+            panic!("Expected the event depth limit to be reached.");
+        };
+        assert_eq!(event_names.len(), MAX_EVENT_DEPTH + 1);
+        assert!(count_activations(&sim) > 1);
+
+        // With the opponent's Echo suppressed, nothing responds to the ally's activation.
+        let opponent_echo = EventHandlerSource::Ability(AbilityID { owner_id: OPPONENT_1 });
+        let activations_before = count_activations(&sim);
+        sim.with_suppressed(opponent_echo, |sim| {
+            _ = ActivateAbility(sim, ALLY_1, AbilityUseContext::new(ALLY_1));
+        });
+        assert_eq!(count_activations(&sim), activations_before + 1);
+        assert!(sim.event_stack.take_error().is_none());
+        assert!(sim.battle.suppressions().is_empty());
+
+        sim.suppress_for_turns(opponent_echo, 1);
+        assert!(sim.battle.is_suppressed(opponent_echo));
+        sim.tick_suppressions();
+        assert!(not!(sim.battle.is_suppressed(opponent_echo)));
     }
 
     #[test]
-    fn test_if_prng_is_deterministic_for_specific_seed() {
-        let mut prng1 = Prng::from_current_time();
-        let mut prng2 = prng1;
-        for i in 0..10_000 {
-            let generated_number_1 = prng1.generate_random_u16_in_range(0..=u16::MAX - 1);
-            let generated_number_2 = prng2.generate_random_u16_in_range(0..=u16::MAX - 1);
-            assert_eq!(generated_number_1, generated_number_2, "iteration {}", i);
-        }
+    fn test_reaching_the_depth_limit_rolls_the_battle_back_to_before_the_action_and_resuming_retries_it() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        #[cfg(feature = "debug")]
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+        use monsim_utils::MaxSizedVec;
+
+        // Any move used by an opponent starts Echoes activating in response to each other, forever.
+        #[allow(non_upper_case_globals)]
+        const Echo: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Echo",
+                event_handlers: | | {
+                    #[cfg(feature="debug")]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnMoveUsed, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        }, source_code_location!())
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        }, source_code_location!());
+                    #[cfg(not(feature="debug"))]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnMoveUsed, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        })
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        });
+                    out
+                },
+                on_activate_effect: Effect::from(|sim, effector_id, _context| {
+                    sim.push_message(format!["{effector_id}'s Echo activated!"]);
+                }),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), Echo.spawn()),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), Echo.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        let scratch = FullySpecifiedActionChoice::Move {
+            move_id: MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 },
+            target_positions: MaxSizedVec::from_vec(vec![FieldPosition::OpponentSideCentre]),
+            activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+        };
+
+        let result = sim.simulate_turn(vec![scratch]);
+        assert!(matches!(result, Err(SimError::EventDepthLimitReached(_))));
+        // Nothing the action did is left behind, and the action is still waiting to be resolved.
+        assert_eq!(sim.battle.turn_number, 1);
+        assert_eq!(sim.battle.monster(ALLY_1).moveset()[0].current_power_points(), Scratch.max_power_points());
+        assert!(not!(sim.battle.message_log.as_vec().iter().any(|message| message.ends_with("Echo activated!"))));
+        assert_eq!(sim.battle.action_queue.front().map(|action_choice| action_choice.actor_id()), Some(ALLY_1));
+        assert!(sim.pending_requests().is_empty());
+        assert_eq!(sim.simulate_turn(Vec::new()), Err(SimError::TurnInProgress));
+
+        // Once the opponent's Echo stops responding, the turn goes on from the action that failed.
+        sim.suppress_for_turns(EventHandlerSource::Ability(AbilityID { owner_id: OPPONENT_1 }), 1);
+        assert_eq!(sim.resume(), Ok(NOTHING));
+        assert_eq!(sim.battle.turn_number, 1);
+        assert_eq!(sim.battle.monster(ALLY_1).moveset()[0].current_power_points(), Scratch.max_power_points() - 1);
+        assert!(sim.battle.monster(OPPONENT_1).current_health() < sim.battle.monster(OPPONENT_1).max_health());
+        assert!(sim.battle.action_queue.is_empty());
+        assert_eq!(sim.pending_requests().len(), 2);
+        assert_eq!(sim.resume(), Ok(NOTHING));
     }
 
     #[test]
-    fn test_prng_chance() {
-        let mut prng = Prng::from_current_time();
+    fn test_a_turn_that_fails_to_start_keeps_the_last_turn_action_pending() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        #[cfg(feature = "debug")]
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+        use monsim_utils::MaxSizedVec;
 
-        let mut success = 0.0;
-        for _ in 0..=10_000_000 {
-            if prng.chance(33, 100) {
-                success += 1.0;
+        // Each turn starts with the Echoes activating in response to each other, forever.
+        #[allow(non_upper_case_globals)]
+        const Echo: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Echo",
+                event_handlers: | | {
+                    #[cfg(feature="debug")]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnTurnStart, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        }, source_code_location!())
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        }, source_code_location!());
+                    #[cfg(not(feature="debug"))]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnTurnStart, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        })
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        });
+                    out
+                },
+                on_activate_effect: Effect::from(|sim, effector_id, _context| {
+                    sim.push_message(format!["{effector_id}'s Echo activated!"]);
+                }),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), Echo.spawn()),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), Echo.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        let scratch = |owner_id: MonsterID, target_position: FieldPosition| Decision::TurnAction(FullySpecifiedActionChoice::Move {
+            move_id: MoveID { owner_id, move_number: MoveNumber::_1 },
+            target_positions: MaxSizedVec::from_vec(vec![target_position]),
+            activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+        });
+
+        assert_eq!(sim.submit(scratch(ALLY_1, FieldPosition::OpponentSideCentre)), Ok(NOTHING));
+        assert!(matches!(sim.submit(scratch(OPPONENT_1, FieldPosition::AllySideCentre)), Err(SimError::EventDepthLimitReached(_))));
+        // The turn has not started, and only the opponent's action has to be submitted again.
+        assert_eq!(sim.battle.turn_number, 0);
+        assert_eq!(sim.resume(), Ok(NOTHING));
+        let pending_requests = sim.pending_requests();
+        assert_eq!(pending_requests.len(), 1);
+        assert!(matches!(pending_requests[0], DecisionRequest::TurnAction { monster_id: OPPONENT_1, .. }));
+
+        sim.suppress_for_turns(EventHandlerSource::Ability(AbilityID { owner_id: OPPONENT_1 }), 1);
+        assert_eq!(sim.submit(scratch(OPPONENT_1, FieldPosition::AllySideCentre)), Ok(NOTHING));
+        assert_eq!(sim.battle.turn_number, 1);
+        assert_eq!(sim.battle.monster(ALLY_1).moveset()[0].current_power_points(), Scratch.max_power_points() - 1);
+        assert_eq!(sim.battle.monster(OPPONENT_1).moveset()[0].current_power_points(), Scratch.max_power_points() - 1);
+        assert_eq!(sim.pending_requests().len(), 2);
+    }
+}
+
+#[cfg(test)]
+mod end_of_turn {
+
+    #[test]
+    fn test_turn_start_and_turn_end_handlers_resolve_for_every_monster_in_speed_order() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        #[cfg(feature = "debug")]
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+
+        // Speed Boost, with a message at the start of each turn as well.
+        #[allow(non_upper_case_globals)]
+        const Momentum: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 102,
+                name: "Momentum",
+                event_handlers: | | {
+                    #[cfg(feature="debug")]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnTurnStart, |sim, effector_id, TurnContext { turn_number }| {
+                            sim.push_message(format!["{effector_id} readies itself for turn {turn_number}."]);
+                        }, source_code_location!())
+                        .add(event_dex::OnTurnEnd, |sim, effector_id, _context| {
+                            sim.push_message(format!["{effector_id} gains momentum."]);
+                            _ = RaiseStat(sim, effector_id, (effector_id, Stat::Speed, 1));
+                        }, source_code_location!());
+                    #[cfg(not(feature="debug"))]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnTurnStart, |sim, effector_id, TurnContext { turn_number }| {
+                            sim.push_message(format!["{effector_id} readies itself for turn {turn_number}."]);
+                        })
+                        .add(event_dex::OnTurnEnd, |sim, effector_id, _context| {
+                            sim.push_message(format!["{effector_id} gains momentum."]);
+                            _ = RaiseStat(sim, effector_id, (effector_id, Stat::Speed, 1));
+                        });
+                    out
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), Momentum.spawn()),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), Momentum.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        assert!(sim.simulate_turn(Vec::new()).is_ok());
+
+        let messages = sim.battle.message_log.as_vec();
+        let position_of = |message: String| messages.iter().position(|logged| *logged == message).expect("Each handler should have resolved once.");
+        assert!(position_of(format!["{ALLY_1} readies itself for turn 1."]) < position_of(format!["{OPPONENT_1} gains momentum."]));
+        // Dandyleo is faster than Squirecoal, so its residual resolves first.
+        assert!(position_of(format!["{OPPONENT_1} gains momentum."]) < position_of(format!["{ALLY_1} gains momentum."]));
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifier(Stat::Speed), 1);
+        assert_eq!(sim.battle.monster(OPPONENT_1).stat_modifier(Stat::Speed), 1);
+    }
+}
+
+#[cfg(test)]
+mod decisions {
+
+    #[test]
+    fn test_simulator_requests_turn_actions_mid_move_switches_and_forced_switches() {
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Merkey, Squirecoal, Zombler},
+            test_move_dex::Scratch,
+        };
+        use monsim_utils::MaxSizedVec;
+
+        #[allow(non_upper_case_globals)]
+        const UTurn: MoveSpecies = MoveSpecies::from_dex_entry(
+            MoveDexEntry {
+                dex_number: 101,
+                name: "U-turn",
+                on_hit_effect: EffectDescription::Custom(Effect::from(|sim, effector_id, context| {
+                    let outcome = DealDefaultDamage(sim, effector_id, context);
+                    if outcome.succeeded() {
+                        _ = RequestSwitchOut(sim, context.move_user_id, context.move_user_id);
+                    }
+                    outcome
+                })),
+                base_accuracy: Some(100),
+                base_power: 70,
+                category: MoveCategory::Physical,
+                crit_ratio: 0,
+                max_power_points: 20,
+                hits_per_target: Hits::Once,
+                priority: 0,
+                targets: TargetFlags::ANY.union(TargetFlags::ADJACENT).union(TargetFlags::OPPONENTS),
+                type_: Type::Bug,
+                event_handlers: EventHandlerDeck::empty,
+                event_filtering_options: EventFilteringOptions::default(),
+            }
+        );
+
+        let mut test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(Squirecoal.spawn((UTurn.spawn(), None, None, None), FlashFire.spawn()))
+                    .add_monster(Zombler.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .add_opponent_team(
+                MonsterTeam::spawn()
+                    .add_monster(Dandyleo.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+                    .add_monster(Merkey.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .build();
+        // U-turn will knock out the opposing Dandyleo.
+        test_battle.monster_mut(OPPONENT_1).current_health = 1;
+        let mut sim = BattleSimulator::init(test_battle);
+
+        let pending_monster_ids = |sim: &BattleSimulator| sim.pending_requests()
+            .into_iter()
+            .map(|request| match request {
+                DecisionRequest::TurnAction { monster_id, .. } => monster_id,
+                _ => panic!("Expected only turn actions to be requested."),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(pending_monster_ids(&sim), vec![ALLY_1, OPPONENT_1]);
+
+        let forced_switch = Decision::ForcedSwitch { field_position: FieldPosition::AllySideCentre, benched_monster_id: ALLY_2 };
+        assert_eq!(sim.submit(forced_switch), Err(SimError::InvalidDecision(forced_switch)));
+
+        let move_decision = |owner_id: MonsterID, target_position: FieldPosition| Decision::TurnAction(FullySpecifiedActionChoice::Move {
+            move_id: MoveID { owner_id, move_number: MoveNumber::_1 },
+            target_positions: MaxSizedVec::from_vec(vec![target_position]),
+            activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+        });
+        assert!(sim.submit(move_decision(ALLY_1, FieldPosition::OpponentSideCentre)).is_ok());
+        assert_eq!(sim.battle.turn_number, 0);
+        assert!(sim.submit(move_decision(OPPONENT_1, FieldPosition::AllySideCentre)).is_ok());
+
+        // The turn waits on the switch that U-turn asked for.
+        assert_eq!(sim.battle.turn_number, 1);
+        assert_eq!(
+            sim.pending_requests(),
+            vec![DecisionRequest::MidMoveSwitch { active_monster_id: ALLY_1, switchable_benched_monster_ids: MaxSizedVec::from_vec(vec![ALLY_2]) }]
+        );
+        assert_eq!(sim.simulate_turn(Vec::new()), Err(SimError::DecisionsPending(sim.pending_requests())));
+        assert!(sim.submit(Decision::MidMoveSwitch { active_monster_id: ALLY_1, benched_monster_id: ALLY_2 }).is_ok());
+        assert!(sim.battle.monster(ALLY_2).is_active());
+
+        // Dandyleo fainted, so its position has to be filled before the next turn.
+        assert_eq!(
+            sim.pending_requests(),
+            vec![DecisionRequest::ForcedSwitch { field_position: FieldPosition::OpponentSideCentre, switchable_benched_monster_ids: MaxSizedVec::from_vec(vec![OPPONENT_2]) }]
+        );
+        assert_eq!(sim.simulate_turn(Vec::new()), Err(SimError::DecisionsPending(sim.pending_requests())));
+        assert!(sim.submit(Decision::ForcedSwitch { field_position: FieldPosition::OpponentSideCentre, benched_monster_id: OPPONENT_2 }).is_ok());
+        assert_eq!(pending_monster_ids(&sim), vec![ALLY_2, OPPONENT_2]);
+    }
+
+    #[test]
+    fn test_a_mid_move_switch_that_fails_is_rolled_back_and_can_be_submitted_again() {
+        use crate::sim::*;
+        #[cfg(feature = "debug")]
+        use crate::source_code_location;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Merkey, Squirecoal, Zombler},
+            test_move_dex::Scratch,
+        };
+        use monsim_utils::MaxSizedVec;
+
+        #[allow(non_upper_case_globals)]
+        const UTurn: MoveSpecies = MoveSpecies::from_dex_entry(
+            MoveDexEntry {
+                dex_number: 101,
+                name: "U-turn",
+                on_hit_effect: EffectDescription::Custom(Effect::from(|sim, effector_id, context| {
+                    let outcome = DealDefaultDamage(sim, effector_id, context);
+                    if outcome.succeeded() {
+                        _ = RequestSwitchOut(sim, context.move_user_id, context.move_user_id);
+                    }
+                    outcome
+                })),
+                base_accuracy: Some(100),
+                base_power: 70,
+                category: MoveCategory::Physical,
+                crit_ratio: 0,
+                max_power_points: 20,
+                hits_per_target: Hits::Once,
+                priority: 0,
+                targets: TargetFlags::ANY.union(TargetFlags::ADJACENT).union(TargetFlags::OPPONENTS),
+                type_: Type::Bug,
+                event_handlers: EventHandlerDeck::empty,
+                event_filtering_options: EventFilteringOptions::default(),
+            }
+        );
+
+        // An opposing Monster switching in starts Echoes activating in response to each other, forever.
+        #[allow(non_upper_case_globals)]
+        const Echo: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Echo",
+                event_handlers: | | {
+                    #[cfg(feature="debug")]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnSwitchIn, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        }, source_code_location!())
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        }, source_code_location!());
+                    #[cfg(not(feature="debug"))]
+                    let out = EventHandlerDeck::empty()
+                        .add(event_dex::OnSwitchIn, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        })
+                        .add(event_dex::OnAbilityActivated, |sim, effector_id, _context| {
+                            _ = ActivateAbility(sim, effector_id, AbilityUseContext::new(effector_id));
+                        });
+                    out
+                },
+                on_activate_effect: Effect::from(|sim, effector_id, _context| {
+                    sim.push_message(format!["{effector_id}'s Echo activated!"]);
+                }),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(Squirecoal.spawn((UTurn.spawn(), None, None, None), FlashFire.spawn()))
+                    .add_monster(Zombler.spawn((Scratch.spawn(), None, None, None), Echo.spawn()))
+            )
+            .add_opponent_team(
+                MonsterTeam::spawn()
+                    .add_monster(Dandyleo.spawn((Scratch.spawn(), None, None, None), Echo.spawn()))
+                    .add_monster(Merkey.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .build();
+        let mut sim = BattleSimulator::init(test_battle);
+        let u_turn = FullySpecifiedActionChoice::Move {
+            move_id: MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 },
+            target_positions: MaxSizedVec::from_vec(vec![FieldPosition::OpponentSideCentre]),
+            activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+        };
+        assert!(sim.simulate_turn(vec![u_turn]).is_ok());
+
+        let mid_move_switch = Decision::MidMoveSwitch { active_monster_id: ALLY_1, benched_monster_id: ALLY_2 };
+        assert!(matches!(sim.submit(mid_move_switch), Err(SimError::EventDepthLimitReached(_))));
+        // The switch is undone and still waiting to be submitted.
+        assert!(sim.battle.monster(ALLY_1).is_active());
+        assert!(not!(sim.battle.monster(ALLY_2).is_active()));
+        assert!(matches!(sim.pending_requests()[..], [DecisionRequest::MidMoveSwitch { active_monster_id: ALLY_1, .. }]));
+
+        sim.suppress_for_turns(EventHandlerSource::Ability(AbilityID { owner_id: OPPONENT_1 }), 1);
+        assert_eq!(sim.submit(mid_move_switch), Ok(NOTHING));
+        assert!(sim.battle.monster(ALLY_2).is_active());
+        assert_eq!(sim.pending_requests().len(), 2);
+    }
+}
+
+#[cfg(test)]
+mod choice_validation {
+
+    #[test]
+    fn test_invalid_action_choices_are_all_reported_before_simulating() {
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal, Zombler},
+            test_move_dex::{Growl, Scratch},
+        };
+        use monsim_utils::MaxSizedVec;
+
+        let mut test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(Squirecoal.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+                    .add_monster(Zombler.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .add_opponent_team(MonsterTeam::spawn().add_monster(Dandyleo.spawn((Scratch.spawn(), Some(Growl.spawn()), None, None), FlashFire.spawn())))
+            .build();
+        let ally_scratch = MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 };
+        let opponent_scratch = MoveID { owner_id: OPPONENT_1, move_number: MoveNumber::_1 };
+        test_battle.move_mut(ally_scratch).current_power_points = 0;
+        test_battle.monster_mut(ALLY_2).current_health = 0;
+        let mut sim = BattleSimulator::init(test_battle);
+
+        let move_choice = |move_id: MoveID, target_positions: Vec<FieldPosition>| FullySpecifiedActionChoice::Move {
+            move_id,
+            target_positions: MaxSizedVec::from_vec(target_positions),
+            activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+        };
+        let switch_choice = FullySpecifiedActionChoice::SwitchOut {
+            active_monster_id: ALLY_1,
+            benched_monster_id: ALLY_2,
+            activation_order: ActivationOrder { priority: 8, speed: 0, order: 0 },
+        };
+
+        let action_choices = vec![
+            move_choice(ally_scratch, vec![FieldPosition::OpponentSideCentre]),
+            switch_choice,
+            move_choice(opponent_scratch, vec![FieldPosition::OpponentSideCentre]),
+        ];
+        assert_eq!(
+            sim.battle.validate_choices(&action_choices),
+            Err(SimError::InvalidChoices(vec![
+                ChoiceError::MoveNotAvailable(ally_scratch),
+                ChoiceError::DuplicateAction(ALLY_1),
+                ChoiceError::InvalidTargetPosition { move_id: opponent_scratch, target_position: FieldPosition::OpponentSideCentre },
+            ]))
+        );
+        assert!(sim.simulate_turn(action_choices).is_err());
+        assert_eq!(sim.battle.turn_number, 0);
+
+        assert_eq!(
+            sim.battle.validate_choices(&[switch_choice, move_choice(opponent_scratch, vec![FieldPosition::AllySideCentre, FieldPosition::AllySideCentre])]),
+            Err(SimError::InvalidChoices(vec![
+                ChoiceError::InvalidSwitchIn { active_monster_id: ALLY_1, benched_monster_id: ALLY_2 },
+                ChoiceError::WrongNumberOfTargets { move_id: opponent_scratch, number_of_targets: 2 },
+            ]))
+        );
+        assert!(sim.battle.validate_choices(&[move_choice(opponent_scratch, vec![FieldPosition::AllySideCentre])]).is_ok());
+
+        // The priority has to be the move's own, and moves that can reach a target have to be given one.
+        let opponent_growl = MoveID { owner_id: OPPONENT_1, move_number: MoveNumber::_2 };
+        let mut quick_scratch = move_choice(opponent_scratch, vec![FieldPosition::AllySideCentre]);
+        quick_scratch.activation_order_mut().priority = 1;
+        assert_eq!(
+            sim.battle.validate_choices(&[quick_scratch]),
+            Err(SimError::InvalidChoices(vec![ChoiceError::WrongPriority { actor_id: OPPONENT_1, priority: 1, expected_priority: 0 }]))
+        );
+        assert_eq!(
+            sim.battle.validate_choices(&[move_choice(opponent_growl, Vec::new())]),
+            Err(SimError::InvalidChoices(vec![ChoiceError::NoTargets(opponent_growl)]))
+        );
+        assert!(sim.battle.validate_choices(&[move_choice(opponent_growl, vec![FieldPosition::AllySideCentre])]).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod battle_log {
+
+    #[test]
+    fn test_any_type_matchup_multiplier_has_an_effectiveness() {
+        use crate::sim::*;
+
+        assert_eq!(Effectiveness::from_multiplier(Percent(50)), Effectiveness::NotVeryEffective);
+        assert_eq!(Effectiveness::from_multiplier(Percent(75)), Effectiveness::NotVeryEffective);
+        assert_eq!(Effectiveness::from_multiplier(Percent(100)), Effectiveness::Effective);
+        assert_eq!(Effectiveness::from_multiplier(Percent(150)), Effectiveness::SuperEffective);
+        assert_eq!(Effectiveness::from_multiplier(Percent(400)), Effectiveness::SuperEffective);
+    }
+
+    #[test]
+    fn test_effects_record_battle_log_entries() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use monsim_utils::MaxSizedVec;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+        };
+
+        #[derive(Debug)]
+        struct TerseRenderer;
+
+        impl BattleLogRenderer for TerseRenderer {
+            fn render(&self, _battle: &BattleState, entry: BattleLogEntry) -> Vec<String> {
+                match entry {
+                    BattleLogEntry::Fainted { monster_id } => vec![format!["{monster_id} is out"]],
+                    _ => Vec::new(),
+                }
             }
         }
-        let avg_probability_deviation = (((success / 10_000_000.0) - 0.3333333333) as f64).abs();
-        let avg_probability_deviation = f64::floor(avg_probability_deviation * 100_000.0) / 100_000.0;
-        println!("Average probability of LCRNG is off by {}% (threshold is at 0.005%)", avg_probability_deviation);
-        assert!(avg_probability_deviation < 5.0e-3);
+
+        let mut test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            spawn_with_scratch(&Dandyleo),
+        );
+        test_battle.monster_mut(OPPONENT_1).current_health = 1;
+        let mut sim = BattleSimulator::init(test_battle);
+
+        let scratch_id = MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 };
+        let action_choices = vec![
+            FullySpecifiedActionChoice::Move {
+                move_id: scratch_id,
+                target_positions: MaxSizedVec::from_vec(vec![FieldPosition::OpponentSideCentre]),
+                activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+            },
+            FullySpecifiedActionChoice::Move {
+                move_id: MoveID { owner_id: OPPONENT_1, move_number: MoveNumber::_1 },
+                target_positions: MaxSizedVec::from_vec(vec![FieldPosition::AllySideCentre]),
+                activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+            },
+        ];
+        assert!(sim.simulate_turn(action_choices).is_ok());
+
+        let battle_log = sim.battle.battle_log();
+        assert_eq!(battle_log.first(), Some(&BattleLogEntry::TurnStarted { turn_number: 1 }));
+        assert!(battle_log.contains(&BattleLogEntry::MoveUsed { move_id: scratch_id }));
+        assert!(battle_log.iter().any(|entry| matches!(
+            entry, 
+            BattleLogEntry::DamageDealt { source_id: ALLY_1, target_id: OPPONENT_1, amount: 1, remaining_health: 0, move_used_id: Some(_), effectiveness: Some(_), .. }
+        )));
+        assert_eq!(&battle_log[battle_log.len() - 2..], &[
+            BattleLogEntry::Fainted { monster_id: OPPONENT_1 },
+            BattleLogEntry::BattleEnded { winner: Some(TeamID::Allies) },
+        ]);
+        
+        // The English rendering is what ends up in the message log.
+        let messages = sim.battle.message_log.as_vec();
+        assert!(messages.iter().any(|message| message == "Squirecoal used Scratch"));
+        assert!(messages.iter().any(|message| message == "Dandyleo fainted!"));
+        assert!(messages.iter().any(|message| message == "Ally team won!"));
+
+        assert_eq!(sim.battle.render_battle_log(&TerseRenderer), vec![format!["{OPPONENT_1} is out"]]);
     }
 }
 
-#[cfg(all(test, feature = "debug"))]
-mod utils {
-    use monsim_utils::{Ally, TeamAffl};
+#[cfg(test)]
+mod localisation {
 
     #[test]
-    #[should_panic]
-    fn test_expect_wrong_team() {
-        let item = Ally::new(10usize);
-        let item = TeamAffl::ally(item);
-        (item.map(|i| { i - 1 }).expect_opponent());
+    fn test_message_catalog_renders_battle_log_with_replaced_templates_and_names() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            localisation::LocalisationError,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            spawn_with_scratch(&Dandyleo),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        let scratch_id = MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 };
+        sim.log(BattleLogEntry::MoveUsed { move_id: scratch_id });
+        sim.log(BattleLogEntry::StatChanged { monster_id: OPPONENT_1, stat: Stat::Speed, direction: StatChangeDirection::Lowered, stages: 1 });
+
+        let mut catalog = MessageCatalog::new();
+        catalog.set_template("move_used", "{monster} setzt {move} ein!").unwrap();
+        catalog.set_template("stat.speed", "Initiative").unwrap();
+        catalog.set_species_name(&Dandyleo, "Löwenzahn");
+        catalog.set_species_name(&Scratch, "Kratzer");
+        assert_eq!(catalog.set_template("used_move", "{monster}!"), Err(LocalisationError::UnknownMessageKey("used_move".to_owned())));
+
+        // Anything the catalog doesn't replace is still rendered in English.
+        assert_eq!(sim.battle.render_battle_log(&catalog), vec![
+            "Squirecoal setzt Kratzer ein!".to_owned(),
+            "Löwenzahn's Initiative was lowered by 1 stage(s)!".to_owned(),
+        ]);
+        assert_eq!(sim.battle.render_battle_log(&EnglishRenderer), sim.battle.message_log.as_vec()[..]);
     }
 
     #[test]
-    fn test_expect_right_team() {
-        let item = Ally::new(10usize);
-        let item = TeamAffl::ally(item);
-        item.map(|i| {i + 1}).expect_ally();
+    fn test_messages_written_by_species_are_logged_and_can_be_replaced_for_each_species() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_field_effect_dex::Rain,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_side_condition_dex::Spikes,
+        };
+
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            spawn_with_scratch(&Dandyleo),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        _ = SetWeather(&mut sim, ALLY_1, &Rain);
+        _ = AddSideCondition(&mut sim, ALLY_1, (TeamID::Opponents, &Spikes));
+        _ = ActivateAbility(&mut sim, ALLY_1, AbilityUseContext::new(ALLY_1));
+        sim.log(BattleLogEntry::PositionLeftEmpty { field_position: FieldPosition::OpponentSideCentre });
+
+        assert_eq!(sim.battle.render_battle_log(&EnglishRenderer), vec![
+            "It started to rain!".to_owned(),
+            "Opponent Team is surrounded by spikes!".to_owned(),
+            "Squirecoal's Flash Fire activated!".to_owned(),
+            "Opponent Side Centre is empty but Opponent Team is out of switchable Monsters!".to_owned(),
+        ]);
+        assert_eq!(sim.battle.render_battle_log(&EnglishRenderer), sim.battle.message_log.as_vec()[..]);
+
+        let mut catalog = MessageCatalog::new();
+        catalog.set_species_template(&Rain, "start_message", "Es beginnt zu regnen!");
+        catalog.set_species_template(&Spikes, "start_message", "Stacheln umgeben {team}!");
+        catalog.set_species_template(&FlashFire, "activated", "Feuerfänger von {monster} wirkt!");
+        catalog.set_template("team.opponents", "das gegnerische Team").unwrap();
+        assert_eq!(sim.battle.render_battle_log(&catalog)[..3], [
+            "Es beginnt zu regnen!".to_owned(),
+            "Stacheln umgeben das gegnerische Team!".to_owned(),
+            "Feuerfänger von Squirecoal wirkt!".to_owned(),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod rich_text {
+
+    #[test]
+    fn test_logged_messages_are_styled_alongside_plain_text() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Ember,
+        };
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Ember.spawn(), None, None, None), FlashFire.spawn()),
+            Dandyleo.spawn((Ember.spawn(), None, None, None), FlashFire.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        let ember_id = MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 };
+        sim.push_message("<plain> & simple");
+        sim.log(BattleLogEntry::MoveUsed { move_id: ember_id });
+        sim.log(BattleLogEntry::DamageDealt {
+            source_id: ALLY_1,
+            target_id: OPPONENT_1,
+            amount: 10,
+            remaining_health: 20,
+            move_used_id: Some(ember_id),
+            effectiveness: Some(Effectiveness::SuperEffective),
+            is_critical_hit: false,
+        });
+
+        let message_log = &sim.battle.message_log;
+        let styled_messages = message_log.styled_messages();
+        assert_eq!(styled_messages.iter().map(StyledText::plain_text).collect::<Vec<_>>(), message_log.as_vec()[..]);
+        assert_eq!(styled_messages[1].spans(), &[
+            StyledSpan { text: "Squirecoal".to_owned(), style: TextStyle::coloured(Colour::ALLY_TEAM) },
+            StyledSpan { text: " used ".to_owned(), style: TextStyle::PLAIN },
+            StyledSpan { text: "Ember".to_owned(), style: TextStyle::coloured(Colour::of_type(Type::Fire)) },
+        ]);
+        assert_eq!(styled_messages[2].spans(), &[StyledSpan { text: "It was super effective!".to_owned(), style: TextStyle::PLAIN.bold() }]);
+        assert_eq!(styled_messages[2].to_ansi(), "\x1b[1mIt was super effective!\x1b[0m");
+        assert_eq!(styled_messages[3].to_html(), "<span style=\"color: #e24a4a\">Dandyleo</span> took 10 damage!");
+        assert!(message_log.to_html().contains("<p>&lt;plain&gt; &amp; simple</p>"));
+    }
+}
+
+#[cfg(test)]
+mod stats {
+
+    #[test]
+    fn test_stat_stages_and_natures_modify_stats() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Squirecoal, Zombler},
+            test_move_dex::{Ember, Scratch},
+        };
+
+        let mut test_battle = one_on_one_battle(
+            Squirecoal.spawn((Ember.spawn(), Some(Scratch.spawn()), None, None), FlashFire.spawn()),
+            spawn_with_scratch(&Zombler),
+        );
+
+        let unmodified_attack = test_battle.monster(ALLY_1).stat(Stat::PhysicalAttack);
+        assert_eq!(unmodified_attack, test_battle.monster(ALLY_1).unmodified_stat(Stat::PhysicalAttack));
+
+        test_battle.monster_mut(ALLY_1).stat_modifiers.raise_stat(Stat::PhysicalAttack, 2);
+        assert_eq!(test_battle.monster(ALLY_1).stat(Stat::PhysicalAttack), unmodified_attack * 2);
+
+        test_battle.monster_mut(ALLY_1).stat_modifiers.lower_stat(Stat::PhysicalAttack, 3);
+        assert_eq!(test_battle.monster(ALLY_1).stat(Stat::PhysicalAttack), unmodified_attack * 2 / 3);
+
+        test_battle.monster_mut(ALLY_1).nature = MonsterNature::Adamant;
+        let adamant_attack = test_battle.monster(ALLY_1).unmodified_stat(Stat::PhysicalAttack);
+        assert_eq!(adamant_attack, (unmodified_attack as f64 * 1.1) as u16);
+        let adamant_special_attack = test_battle.monster(ALLY_1).unmodified_stat(Stat::SpecialAttack);
+        test_battle.monster_mut(ALLY_1).nature = MonsterNature::Serious;
+        assert!(adamant_special_attack < test_battle.monster(ALLY_1).unmodified_stat(Stat::SpecialAttack));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_accuracy_and_evasion_only_have_stages() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_monster_dex::{Squirecoal, Zombler},
+        };
+
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            spawn_with_scratch(&Zombler),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        assert_eq!(RaiseStat(&mut sim, ALLY_1, (ALLY_1, Stat::Evasion, 1)), Outcome::Success);
+        for stat in [Stat::Accuracy, Stat::Evasion] {
+            assert_eq!(Squirecoal.base_stat(stat), 0);
+            assert_eq!(sim.battle.monster(ALLY_1).stat(stat), 0);
+            assert_eq!(sim.effective_stat(ALLY_1, stat), 0);
+        }
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifier(Stat::Evasion), 1);
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifier(Stat::Hp), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_accuracy_is_rolled_for_each_target() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::source_code_location;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Merkey, Squirecoal, Zombler},
+            test_move_dex::Bubble,
+        };
+
+        // Moves used against a Monster with this Ability always miss it.
+        #[allow(non_upper_case_globals)]
+        const Elusive: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Elusive",
+                event_handlers: | | {
+                    EventHandlerDeck::empty()
+                        .add(event_dex::OnModifyAccuracy, |_sim, effector_id, (MoveHitContext { target_id, .. }, accuracy)| {
+                            if target_id == effector_id { Percent(0) } else { accuracy }
+                        }, source_code_location!())
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(Squirecoal.spawn((Bubble.spawn(), None, None, None), FlashFire.spawn()))
+                    .add_monster(Zombler.spawn((Bubble.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .add_opponent_team(
+                MonsterTeam::spawn()
+                    .add_monster(Dandyleo.spawn((Bubble.spawn(), None, None, None), Elusive.spawn()))
+                    .add_monster(Merkey.spawn((Bubble.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .with_format(BattleFormat::Double)
+            .build();
+        let mut sim = BattleSimulator::init(test_battle);
+
+        let bubble_id = MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 };
+        UseMove(&mut sim, ALLY_1, MoveUseContext::new(bubble_id, monsim_utils::MaxSizedVec::from_slice(&[OPPONENT_1, OPPONENT_2])));
+
+        assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), sim.battle.monster(OPPONENT_1).max_health());
+        assert!(sim.battle.monster(OPPONENT_2).current_health() < sim.battle.monster(OPPONENT_2).max_health());
+        assert_eq!(
+            sim.battle.battle_log().iter().filter(|entry| matches!(entry, BattleLogEntry::MoveMissed { .. })).collect::<Vec<_>>(),
+            vec![&BattleLogEntry::MoveMissed { move_id: bubble_id, target_id: OPPONENT_1 }]
+        );
+    }
+
+    #[test]
+    fn test_switching_out_resets_stat_stages() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal, Zombler},
+            test_move_dex::{Ember, Scratch},
+        };
+
+        let test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(
+                        Squirecoal.spawn(
+                            (Ember.spawn(), Some(Scratch.spawn()), None, None),
+                            FlashFire.spawn()
+                        )
+                    )
+                    .add_monster(
+                        Dandyleo.spawn(
+                            (Scratch.spawn(), None, None, None),
+                            FlashFire.spawn()
+                        )
+                    )
+            )
+            .add_opponent_team(
+                MonsterTeam::spawn()
+                    .add_monster(
+                        Zombler.spawn(
+                            (Scratch.spawn(), None, None, None),
+                            FlashFire.spawn()
+                        )
+                    )
+            )
+            .build();
+        let mut sim = BattleSimulator::init(test_battle);
+
+        assert_eq!(RaiseStat(&mut sim, ALLY_1, (ALLY_1, Stat::PhysicalAttack, 2)), Outcome::Success);
+        assert_eq!(LowerStat(&mut sim, ALLY_1, (ALLY_1, Stat::Speed, 1)), Outcome::Success);
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifier(Stat::PhysicalAttack), 2);
+
+        PerformSwitchOut(&mut sim, ALLY_1, SwitchContext::new(ALLY_1, ALLY_2));
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifiers, StatModifierSet::default());
+        assert_eq!(sim.battle.monster(ALLY_1).stat(Stat::PhysicalAttack), sim.battle.monster(ALLY_1).unmodified_stat(Stat::PhysicalAttack));
+    }
+}
+
+#[cfg(test)]
+mod status {
+
+    #[test]
+    fn test_inflicting_and_curing_major_statuses() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Squirecoal, Zombler},
+            test_move_dex::{Ember, Scratch},
+        };
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Ember.spawn(), Some(Scratch.spawn()), None, None), FlashFire.spawn()),
+            spawn_with_scratch(&Zombler),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        // Fire types cannot be burned.
+        assert_eq!(InflictStatus(&mut sim, OPPONENT_1, (ALLY_1, MajorStatus::Burn)), Outcome::Failure);
+        assert_eq!(sim.battle.monster(ALLY_1).status(), None);
+
+        // Paralysis halves Speed.
+        let unparalysed_speed = sim.battle.monster(ALLY_1).stat(Stat::Speed);
+        assert_eq!(InflictStatus(&mut sim, OPPONENT_1, (ALLY_1, MajorStatus::Paralysis)), Outcome::Success);
+        assert_eq!(sim.battle.monster(ALLY_1).stat(Stat::Speed), unparalysed_speed / 2);
+        
+        // A Monster can only have one status at a time.
+        assert_eq!(InflictStatus(&mut sim, OPPONENT_1, (ALLY_1, MajorStatus::Poison)), Outcome::Failure);
+        assert_eq!(CureStatus(&mut sim, ALLY_1, ALLY_1), Outcome::Success);
+        assert_eq!(sim.battle.monster(ALLY_1).stat(Stat::Speed), unparalysed_speed);
+
+        // Poison deals 1/8 of max HP at the end of the turn.
+        assert_eq!(InflictStatus(&mut sim, ALLY_1, (OPPONENT_1, MajorStatus::Poison)), Outcome::Success);
+        let max_health = sim.battle.monster(OPPONENT_1).max_health();
+        DealStatusDamage(&mut sim, OPPONENT_1, OPPONENT_1);
+        assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), max_health - max_health / 8);
+    }
+
+    #[test]
+    fn test_bad_poison_damage_stops_growing_at_fifteen_sixteenths() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_monster_dex::{Squirecoal, Zombler},
+        };
+
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            spawn_with_scratch(&Zombler),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        assert_eq!(InflictStatus(&mut sim, ALLY_1, (OPPONENT_1, MajorStatus::BadPoison)), Outcome::Success);
+        let max_health = sim.battle.monster(OPPONENT_1).max_health();
+        DealStatusDamage(&mut sim, OPPONENT_1, OPPONENT_1);
+        assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), max_health - max_health / 16);
+
+        sim.battle.monster_mut(OPPONENT_1).status_turn_counter = 15;
+        sim.battle.monster_mut(OPPONENT_1).current_health = max_health;
+        DealStatusDamage(&mut sim, OPPONENT_1, OPPONENT_1);
+        assert_eq!(sim.battle.monster(OPPONENT_1).status_turn_counter, 15);
+        assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), max_health - max_health * 15 / 16);
+    }
+}
+
+#[cfg(test)]
+mod volatile_status {
+
+    #[test]
+    fn test_volatile_statuses_contribute_event_handlers_until_they_expire() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Squirecoal, Zombler},
+            test_move_dex::{Ember, Scratch},
+            test_volatile_status_dex::Flinch,
+        };
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Ember.spawn(), Some(Scratch.spawn()), None, None), FlashFire.spawn()),
+            spawn_with_scratch(&Zombler),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        let number_of_handlers = sim.battle.event_handlers_for(event_dex::OnTryMove).len();
+
+        assert_eq!(AddVolatileStatus(&mut sim, OPPONENT_1, (ALLY_1, &Flinch)), Outcome::Success);
+        assert!(sim.battle.monster(ALLY_1).has_volatile_status(&Flinch));
+        // The same volatile status can't be added twice.
+        assert_eq!(AddVolatileStatus(&mut sim, OPPONENT_1, (ALLY_1, &Flinch)), Outcome::Failure);
+        assert_eq!(sim.battle.event_handlers_for(event_dex::OnTryMove).len(), number_of_handlers + 1);
+
+        // Flinching only lasts until the end of the turn.
+        TickVolatileStatuses(&mut sim, ALLY_1, ALLY_1);
+        assert!(not!(sim.battle.monster(ALLY_1).has_volatile_status(&Flinch)));
+        assert_eq!(sim.battle.event_handlers_for(event_dex::OnTryMove).len(), number_of_handlers);
+    }
+
+    #[test]
+    fn test_volatile_statuses_count_turns_and_are_removed_by_expiring_or_switching_out() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::spawn_with_scratch;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal, Zombler},
+            test_move_dex::{Growl, Scratch},
+            test_volatile_status_dex::{Bound, Confusion, Taunt},
+        };
+        use monsim_utils::MaxSizedVec;
+
+        let test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(Squirecoal.spawn((Scratch.spawn(), Some(Growl.spawn()), None, None), FlashFire.spawn()))
+                    .add_monster(spawn_with_scratch(&Dandyleo))
+            )
+            .add_opponent_team(MonsterTeam::spawn().add_monster(spawn_with_scratch(&Zombler)))
+            .with_seed(7)
+            .build();
+        let mut sim = BattleSimulator::init(test_battle);
+        let count_messages = |sim: &BattleSimulator, text: &str| sim.battle.message_log.as_vec().iter().filter(|message| message.ends_with(text)).count();
+
+        // Taunt stops status moves, but not damaging ones, for three turns.
+        assert_eq!(AddVolatileStatus(&mut sim, OPPONENT_1, (ALLY_1, &Taunt)), Outcome::Success);
+        let scratch_context = MoveUseContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, MaxSizedVec::from_slice(&[OPPONENT_1]));
+        let growl_context = MoveUseContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_2 }, MaxSizedVec::from_slice(&[OPPONENT_1]));
+        assert_eq!(EventDispatcher::dispatch_trial_event(&mut sim, event_dex::OnTryMove, ALLY_1, scratch_context), Outcome::Success);
+        assert_eq!(EventDispatcher::dispatch_trial_event(&mut sim, event_dex::OnTryMove, ALLY_1, growl_context), Outcome::Failure);
+        for turns_passed in 1..=2 {
+            TickVolatileStatuses(&mut sim, ALLY_1, ALLY_1);
+            assert_eq!(sim.battle.monster(ALLY_1).volatile_statuses()[0].turn_counter(), turns_passed);
+        }
+        TickVolatileStatuses(&mut sim, ALLY_1, ALLY_1);
+        assert!(not!(sim.battle.monster(ALLY_1).has_volatile_status(&Taunt)));
+        assert_eq!(EventDispatcher::dispatch_trial_event(&mut sim, event_dex::OnTryMove, ALLY_1, growl_context), Outcome::Success);
+
+        // Being bound hurts at the end of each of the four turns it lasts, and its end is announced.
+        assert_eq!(AddVolatileStatus(&mut sim, ALLY_1, (OPPONENT_1, &Bound)), Outcome::Success);
+        let max_health = sim.battle.monster(OPPONENT_1).max_health();
+        for turns_passed in 1..=4 {
+            EventDispatcher::dispatch_residual_event(&mut sim, event_dex::OnTurnEnd, TurnContext::new(turns_passed));
+            assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), max_health - turns_passed * (max_health / 8));
+            TickVolatileStatuses(&mut sim, OPPONENT_1, OPPONENT_1);
+        }
+        assert!(not!(sim.battle.monster(OPPONENT_1).has_volatile_status(&Bound)));
+        assert_eq!(count_messages(&sim, "was freed!"), 1);
+
+        // Confusion lasts for between two and five turns, rolled when it starts.
+        assert_eq!(AddVolatileStatus(&mut sim, OPPONENT_1, (ALLY_1, &Confusion)), Outcome::Success);
+        let duration = sim.battle.monster(ALLY_1).volatile_statuses()[0].duration().unwrap();
+        assert!((2..=5).contains(&duration));
+        let confused_tries = (0..30)
+            .map(|_| EventDispatcher::dispatch_trial_event(&mut sim, event_dex::OnTryMove, ALLY_1, scratch_context))
+            .collect::<Vec<_>>();
+        let number_of_self_hits = confused_tries.iter().filter(|outcome| **outcome == Outcome::Failure).count();
+        assert!(0 < number_of_self_hits && number_of_self_hits < 30);
+        assert_eq!(count_messages(&sim, "hurt itself in its confusion!"), number_of_self_hits);
+        for _ in 1..duration {
+            TickVolatileStatuses(&mut sim, ALLY_1, ALLY_1);
+            assert!(sim.battle.monster(ALLY_1).has_volatile_status(&Confusion));
+        }
+        TickVolatileStatuses(&mut sim, ALLY_1, ALLY_1);
+        assert!(not!(sim.battle.monster(ALLY_1).has_volatile_status(&Confusion)));
+        assert_eq!(count_messages(&sim, "snapped out of its confusion!"), 1);
+
+        // Switching out removes every volatile status at once, without resolving their end effects.
+        assert_eq!(AddVolatileStatus(&mut sim, OPPONENT_1, (ALLY_1, &Confusion)), Outcome::Success);
+        assert_eq!(AddVolatileStatus(&mut sim, OPPONENT_1, (ALLY_1, &Taunt)), Outcome::Success);
+        PerformSwitchOut(&mut sim, ALLY_1, SwitchContext::new(ALLY_1, ALLY_2));
+        assert!(sim.battle.monster(ALLY_1).volatile_statuses().is_empty());
+        assert_eq!(count_messages(&sim, "snapped out of its confusion!"), 1);
+    }
+}
+
+#[cfg(test)]
+mod field_state {
+
+    #[test]
+    fn test_weather_contributes_event_handlers_until_it_runs_out() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_field_effect_dex::Rain,
+            test_monster_dex::{Squirecoal, Zombler},
+            test_move_dex::{Ember, Scratch},
+        };
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Ember.spawn(), Some(Scratch.spawn()), None, None), FlashFire.spawn()),
+            spawn_with_scratch(&Zombler),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        assert!(sim.battle.event_handlers_for(event_dex::OnModifyFinalDamage).is_empty());
+        assert_eq!(SetWeather(&mut sim, ALLY_1, &Rain), Outcome::Success);
+        assert_eq!(SetWeather(&mut sim, ALLY_1, &Rain), Outcome::Failure);
+        assert!(sim.battle.field_state().is_weather(&Rain));
+
+        let field_handlers = sim.battle.event_handlers_for(event_dex::OnModifyFinalDamage);
+        assert_eq!(field_handlers.len(), 1);
+        assert_eq!(field_handlers[0].owner, EventHandlerOwner::Field);
+
+        for _ in 0..4 {
+            sim.tick_field_state();
+        }
+        assert_eq!(sim.battle.field_state().weather().and_then(|weather| weather.turns_remaining()), Some(1));
+        sim.tick_field_state();
+        assert_eq!(sim.battle.field_state().weather(), None);
+        assert!(sim.battle.event_handlers_for(event_dex::OnModifyFinalDamage).is_empty());
+    }
+
+    #[test]
+    fn test_terrain_sits_beside_the_weather_and_only_in_its_own_slot() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_field_effect_dex::{GrassyTerrain, Rain},
+            test_monster_dex::{Squirecoal, Zombler},
+        };
+
+        let test_battle = one_on_one_battle(
+            spawn_with_scratch(&Squirecoal),
+            spawn_with_scratch(&Zombler),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        // Neither kind of field effect can be put in the other's slot.
+        assert_eq!(SetWeather(&mut sim, ALLY_1, &GrassyTerrain), Outcome::Failure);
+        assert_eq!(SetTerrain(&mut sim, ALLY_1, &Rain), Outcome::Failure);
+        assert_eq!(sim.battle.field_state(), &FieldState::default());
+
+        assert_eq!(SetTerrain(&mut sim, ALLY_1, &GrassyTerrain), Outcome::Success);
+        assert_eq!(SetWeather(&mut sim, ALLY_1, &Rain), Outcome::Success);
+        assert!(sim.battle.field_state().is_terrain(&GrassyTerrain));
+        assert!(sim.battle.field_state().is_weather(&Rain));
+
+        let max_health = sim.battle.monster(ALLY_1).max_health();
+        sim.battle.monster_mut(ALLY_1).current_health = max_health / 2;
+        ResolveFieldEndOfTurnEffects(&mut sim, ALLY_1, ALLY_1);
+        assert_eq!(sim.battle.monster(ALLY_1).current_health(), max_health / 2 + max_health / 16);
+
+        for _ in 0..5 {
+            sim.tick_field_state();
+        }
+        assert_eq!(sim.battle.field_state().terrain(), None);
+        assert_eq!(ClearTerrain(&mut sim, ALLY_1, NOTHING), Outcome::Failure);
+    }
+}
+
+#[cfg(test)]
+mod side_condition {
+
+    #[test]
+    fn test_entry_hazards_stack_and_trigger_on_switch_in() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal, Zombler},
+            test_move_dex::{Ember, Scratch},
+            test_side_condition_dex::Spikes,
+        };
+
+        let test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(
+                        Squirecoal.spawn(
+                            (Ember.spawn(), Some(Scratch.spawn()), None, None),
+                            FlashFire.spawn()
+                        )
+                    )
+                    .add_monster(
+                        Dandyleo.spawn(
+                            (Scratch.spawn(), None, None, None),
+                            FlashFire.spawn()
+                        )
+                    )
+            )
+            .add_opponent_team(
+                MonsterTeam::spawn()
+                    .add_monster(
+                        Zombler.spawn(
+                            (Scratch.spawn(), None, None, None),
+                            FlashFire.spawn()
+                        )
+                    )
+            )
+            .build();
+        let mut sim = BattleSimulator::init(test_battle);
+
+        for _ in 0..3 {
+            assert_eq!(AddSideCondition(&mut sim, OPPONENT_1, (TeamID::Allies, &Spikes)), Outcome::Success);
+        }
+        assert_eq!(AddSideCondition(&mut sim, OPPONENT_1, (TeamID::Allies, &Spikes)), Outcome::Failure);
+        assert_eq!(sim.battle.ally_team().side_condition(&Spikes).map(|spikes| spikes.layers()), Some(3));
+
+        // Three layers of Spikes deal 1/4 of the max HP of the Monster switching in.
+        let max_health = sim.battle.monster(ALLY_2).max_health();
+        PerformSwitchOut(&mut sim, ALLY_1, SwitchContext::new(ALLY_1, ALLY_2));
+        assert_eq!(sim.battle.monster(ALLY_2).current_health(), max_health - max_health / 4);
+        // The opposing side is unaffected.
+        assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), sim.battle.monster(OPPONENT_1).max_health());
+
+        // Spikes requires being active, so it ignores broadcasts from the Monster that just left the field.
+        EventDispatcher::dispatch_event(&mut sim, event_dex::OnSwitchIn, ALLY_1, SwitchInContext::new(ALLY_1), NOTHING, None);
+        assert_eq!(sim.battle.monster(ALLY_1).current_health(), sim.battle.monster(ALLY_1).max_health());
+    }
+
+    #[test]
+    fn test_weather_screens_and_items_all_scale_the_damage_of_one_hit() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_field_effect_dex::Rain,
+            test_item_dex::Charcoal,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::{FireFang, Scratch},
+            test_side_condition_dex::Reflect,
+        };
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((FireFang.spawn(), Some(Scratch.spawn()), None, None), FlashFire.spawn()).with_item(Charcoal.spawn()),
+            spawn_with_scratch(&Dandyleo),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        assert_eq!(SetWeather(&mut sim, OPPONENT_1, &Rain), Outcome::Success);
+        assert_eq!(AddSideCondition(&mut sim, OPPONENT_1, (TeamID::Opponents, &Reflect)), Outcome::Success);
+
+        // Each handler scales the damage left by the previous one: Charcoal by 120%, Rain and Reflect by 50% each.
+        let fire_fang = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, OPPONENT_1);
+        assert_eq!(EventDispatcher::dispatch_modify_event(&mut sim, event_dex::OnModifyFinalDamage, ALLY_1, fire_fang, 100), 30);
+        // Only Reflect applies to a Normal move.
+        let scratch = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_2 }, OPPONENT_1);
+        assert_eq!(EventDispatcher::dispatch_modify_event(&mut sim, event_dex::OnModifyFinalDamage, ALLY_1, scratch, 100), 50);
+        // Charcoal only boosts its holder's moves, and Reflect only protects its own side.
+        let opponent_scratch = MoveHitContext::new(MoveID { owner_id: OPPONENT_1, move_number: MoveNumber::_1 }, ALLY_1);
+        assert_eq!(EventDispatcher::dispatch_modify_event(&mut sim, event_dex::OnModifyFinalDamage, OPPONENT_1, opponent_scratch, 100), 100);
+
+        // Suppressing the weather or the screen leaves the other active.
+        let damage_with_suppressed = |sim: &mut BattleSimulator, source| sim.with_suppressed(source, |sim| {
+            EventDispatcher::dispatch_modify_event(sim, event_dex::OnModifyFinalDamage, ALLY_1, fire_fang, 100)
+        });
+        assert_eq!(damage_with_suppressed(&mut sim, EventHandlerSource::FieldEffect(&Rain)), 60);
+        assert_eq!(damage_with_suppressed(&mut sim, EventHandlerSource::SideCondition(TeamID::Opponents, &Reflect)), 60);
+        assert_eq!(damage_with_suppressed(&mut sim, EventHandlerSource::SideCondition(TeamID::Allies, &Reflect)), 30);
+    }
+}
+
+#[cfg(test)]
+mod item {
+
+    #[test]
+    fn test_consuming_and_swapping_held_items() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_item_dex::SitrusBerry,
+            test_monster_dex::{Squirecoal, Zombler},
+            test_move_dex::{Ember, Scratch},
+        };
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Ember.spawn(), Some(Scratch.spawn()), None, None), FlashFire.spawn()) .with_item(SitrusBerry.spawn()),
+            spawn_with_scratch(&Zombler),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        // Trick moves the berry over to the opponent, and back again.
+        assert_eq!(SwapItems(&mut sim, ALLY_1, (ALLY_1, OPPONENT_1)), Outcome::Success);
+        assert!(sim.battle.monster(ALLY_1).held_item().is_none());
+        assert_eq!(sim.battle.monster(OPPONENT_1).held_item().map(|item| item.name()), Some("Sitrus Berry"));
+        assert_eq!(SwapItems(&mut sim, ALLY_1, (ALLY_1, OPPONENT_1)), Outcome::Success);
+
+        // The berry is eaten once the holder drops to half health, and is used up.
+        let max_health = sim.battle.monster(ALLY_1).max_health();
+        _ = DealDirectDamage(&mut sim, OPPONENT_1, (ALLY_1, max_health / 2 + 1));
+        assert!(sim.battle.monster(ALLY_1).held_item().is_none());
+        assert_eq!(sim.battle.monster(ALLY_1).current_health(), max_health - (max_health / 2 + 1) + max_health / 4);
+
+        // Knock Off fails when there is nothing to knock off.
+        assert_eq!(RemoveItem(&mut sim, OPPONENT_1, ALLY_1), Outcome::Failure);
+    }
+
+    #[test]
+    fn test_sitrus_berry_only_reacts_to_damage_to_its_holder() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_item_dex::SitrusBerry,
+            test_monster_dex::{Dandyleo, Merkey, Squirecoal, Zombler},
+            test_move_dex::Scratch,
+        };
+
+        let test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(Squirecoal.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()).with_item(SitrusBerry.spawn()))
+                    .add_monster(Zombler.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .add_opponent_team(
+                MonsterTeam::spawn()
+                    .add_monster(Dandyleo.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+                    .add_monster(Merkey.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .with_format(BattleFormat::Double)
+            .build();
+        let mut sim = BattleSimulator::init(test_battle);
+        let max_health = sim.battle.monster(ALLY_1).max_health();
+        sim.battle.monster_mut(ALLY_1).current_health = max_health / 2 + 2;
+
+        // Damage to the holder's partner leaves the berry alone.
+        _ = DealDirectDamage(&mut sim, OPPONENT_1, (ALLY_2, 1));
+        assert!(sim.battle.monster(ALLY_1).held_item().is_some());
+
+        // An opponent fainting from its own residual damage is not the holder's concern either.
+        assert_eq!(InflictStatus(&mut sim, ALLY_1, (OPPONENT_1, MajorStatus::Poison)), Outcome::Success);
+        sim.battle.monster_mut(OPPONENT_1).current_health = 1;
+        DealStatusDamage(&mut sim, OPPONENT_1, OPPONENT_1);
+        assert!(sim.battle.monster(OPPONENT_1).is_fainted());
+        assert!(sim.battle.monster(ALLY_1).held_item().is_some());
+
+        // Residual damage the holder takes on its own triggers the berry.
+        assert_eq!(InflictStatus(&mut sim, OPPONENT_2, (ALLY_1, MajorStatus::Poison)), Outcome::Success);
+        DealStatusDamage(&mut sim, ALLY_1, ALLY_1);
+        assert!(sim.battle.monster(ALLY_1).held_item().is_none());
+        assert_eq!(sim.battle.monster(ALLY_1).current_health(), max_health / 2 + 2 - max_health / 8 + max_health / 4);
+    }
+}
+
+#[cfg(test)]
+mod critical_hit {
+
+    #[test]
+    fn test_max_crit_stage_always_crits() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::{Ember, Scratch},
+        };
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), Some(Ember.spawn()), None, None), FlashFire.spawn()),
+            spawn_with_scratch(&Dandyleo),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        assert_eq!(RaiseCritStage(&mut sim, ALLY_1, (ALLY_1, 3)), Outcome::Success);
+        assert_eq!(RaiseCritStage(&mut sim, ALLY_1, (ALLY_1, 1)), Outcome::Failure);
+        // Raising by more stages than are left stops at the highest stage.
+        assert_eq!(RaiseCritStage(&mut sim, OPPONENT_1, (OPPONENT_1, 1)), Outcome::Success);
+        assert_eq!(RaiseCritStage(&mut sim, OPPONENT_1, (OPPONENT_1, u8::MAX)), Outcome::Success);
+        assert_eq!(sim.battle.monster(OPPONENT_1).crit_stage(), 3);
+        let scratch_id = MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 };
+        assert_eq!(DealDefaultDamage(&mut sim, ALLY_1, MoveHitContext { move_user_id: ALLY_1, move_used_id: scratch_id, target_id: OPPONENT_1 }), Outcome::Success);
+        assert!(sim.battle.message_log.as_vec().iter().any(|message| message == "A critical hit!"));
+        assert!(sim.battle.monster(OPPONENT_1).current_health() < sim.battle.monster(OPPONENT_1).max_health());
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_critical_hits_ignore_unfavourable_stat_stages() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::source_code_location;
+        use crate::sim::{
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+
+        // Reports the attack and defense used in the damage calculation of its holder's moves.
+        #[allow(non_upper_case_globals)]
+        const Observant: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Observant",
+                event_handlers: | | {
+                    EventHandlerDeck::empty()
+                        .add(event_dex::OnModifyAttackStat, |sim, _effector_id, (_context, attack)| {
+                            sim.push_message(format!["attack {attack}"]);
+                            attack
+                        }, source_code_location!())
+                        .add(event_dex::OnModifyDefenseStat, |sim, _effector_id, (_context, defense)| {
+                            sim.push_message(format!["defense {defense}"]);
+                            defense
+                        }, source_code_location!())
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions {
+                    allowed_broadcaster_relation_flags: TargetFlags::SELF,
+                    requires_being_active: true,
+                },
+                order: 0,
+            }
+        );
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), Observant.spawn()),
+            spawn_with_scratch(&Dandyleo),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        assert_eq!(RaiseCritStage(&mut sim, ALLY_1, (ALLY_1, 3)), Outcome::Success);
+
+        let unmodified_attack = sim.battle.monster(ALLY_1).unmodified_stat(Stat::PhysicalAttack);
+        let unmodified_defense = sim.battle.monster(OPPONENT_1).unmodified_stat(Stat::PhysicalDefense);
+        let scratch_context = MoveHitContext { move_user_id: ALLY_1, move_used_id: MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, target_id: OPPONENT_1 };
+        let used_stats = |sim: &BattleSimulator| sim.battle.message_log.as_vec()
+            .iter()
+            .filter(|message| message.starts_with("attack") || message.starts_with("defense"))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // The attacker's lowered Attack and the defender's raised Defense are ignored.
+        assert_eq!(LowerStat(&mut sim, ALLY_1, (ALLY_1, Stat::PhysicalAttack, 2)), Outcome::Success);
+        assert_eq!(RaiseStat(&mut sim, OPPONENT_1, (OPPONENT_1, Stat::PhysicalDefense, 2)), Outcome::Success);
+        _ = DealDefaultDamage(&mut sim, ALLY_1, scratch_context);
+        assert_eq!(used_stats(&sim), vec![format!["attack {unmodified_attack}"], format!["defense {unmodified_defense}"]]);
+
+        // The attacker's raised Attack and the defender's lowered Defense still count.
+        assert_eq!(RaiseStat(&mut sim, ALLY_1, (ALLY_1, Stat::PhysicalAttack, 3)), Outcome::Success);
+        assert_eq!(LowerStat(&mut sim, OPPONENT_1, (OPPONENT_1, Stat::PhysicalDefense, 3)), Outcome::Success);
+        _ = DealDefaultDamage(&mut sim, ALLY_1, scratch_context);
+        assert_eq!(used_stats(&sim)[2..], [
+            format!["attack {}", sim.battle.monster(ALLY_1).stat_at_stage(Stat::PhysicalAttack, 1)],
+            format!["defense {}", sim.battle.monster(OPPONENT_1).stat_at_stage(Stat::PhysicalDefense, -1)],
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod dex {
+
+    #[test]
+    fn test_dex_lookup_and_duplicate_detection() {
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Merkey, Squirecoal, Zombler},
+            test_move_dex::{Scratch, Tackle},
+        };
+
+        let mut dex = Dex::new();
+        dex.register_all(&[&Zombler, &Dandyleo, &Squirecoal, &Merkey]).unwrap();
+        dex.register_all(&[&Tackle, &Scratch]).unwrap();
+        dex.register(&FlashFire).unwrap();
+
+        assert_eq!(dex.monsters().len(), 4);
+        assert!(dex.monsters().iter().map(|species| species.dex_number()).is_sorted());
+        assert_eq!(dex.monsters().find_by_dex_number(Squirecoal.dex_number()), Some(&Squirecoal));
+        assert_eq!(dex.abilities().find_by_name("flash fire"), Some(&FlashFire));
+        assert_eq!(dex.moves().find_closest_by_name("Scrach"), Some(&Scratch));
+        assert_eq!(dex.moves().find_closest_by_name("Hyper Beam"), None);
+        assert_eq!(dex.move_species(Tackle.dex_number()), Some(&Tackle));
+
+        assert!(matches!(dex.register(&Dandyleo), Err(DexError::DuplicateDexNumber { .. })));
+        assert_eq!(dex.monsters().len(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    #[should_panic(expected = "cannot hold more than 4 handlers for OnTurnEnd")]
+    fn test_adding_too_many_handlers_for_an_event_panics() {
+        use crate::sim::*;
+        use crate::source_code_location;
+
+        #[allow(non_upper_case_globals)]
+        const Fidgety: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Fidgety",
+                event_handlers: | | {
+                    let mut deck = EventHandlerDeck::empty();
+                    for _ in 0..=MAX_EVENT_HANDLERS_PER_EVENT {
+                        deck = deck.add(event_dex::OnTurnEnd, |_sim, _effector_id, _context| {}, source_code_location!());
+                    }
+                    deck
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let _ = Fidgety.event_handlers();
+    }
+}
+
+#[cfg(test)]
+mod effect_description {
+
+    #[test]
+    fn test_composed_effect_descriptions() {
+        use crate::sim::*;
+        use crate::test::{one_on_one_battle, spawn_with_scratch};
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::{Ember, Scratch},
+        };
+        use EffectDescription::*;
+
+        // Deal damage, then always lower the target's speed, then heal half the damage dealt if the user's attack could be raised.
+        const DRAINING_SCRATCH: EffectDescription = Sequence(&[
+            BuiltIn(BuiltInEffect::DealDefaultDamage),
+            Chance { chance: 100, then: &BuiltIn(BuiltInEffect::LowerStat { target: EffectTarget::Target, stat: Stat::Speed, stages: 1 }) },
+            Conditional {
+                condition: &BuiltIn(BuiltInEffect::RaiseStat { target: EffectTarget::User, stat: Stat::PhysicalAttack, stages: 1 }),
+                then: &BuiltIn(BuiltInEffect::RestoreHealthFromDamageDealt { target: EffectTarget::User, percent: 50 }),
+                otherwise: None,
+            },
+        ]);
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), Some(Ember.spawn()), None, None), FlashFire.spawn()),
+            spawn_with_scratch(&Dandyleo),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+        _ = DealDirectDamage(&mut sim, OPPONENT_1, (ALLY_1, 30));
+
+        let context = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, OPPONENT_1);
+        assert_eq!(DRAINING_SCRATCH.resolve(&mut sim, ALLY_1, context), Outcome::Success);
+        let damage_dealt = sim.battle.monster(OPPONENT_1).max_health() - sim.battle.monster(OPPONENT_1).current_health();
+        assert!(damage_dealt > 0);
+        assert_eq!(sim.battle.monster(OPPONENT_1).stat_modifier(Stat::Speed), -1);
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifier(Stat::PhysicalAttack), 1);
+        assert_eq!(sim.battle.monster(ALLY_1).current_health(), sim.battle.monster(ALLY_1).max_health() - 30 + damage_dealt / 2);
+
+        // The condition fails, so nothing happens.
+        const NEVER: EffectDescription = Conditional {
+            condition: &Chance { chance: 0, then: &BuiltIn(BuiltInEffect::DealDefaultDamage) },
+            then: &BuiltIn(BuiltInEffect::CureStatus { target: EffectTarget::User }),
+            otherwise: None,
+        };
+        assert_eq!(NEVER.resolve(&mut sim, ALLY_1, context), Outcome::Failure);
+
+        // Flash Fire blocks the Fire type Ember, so dealing damage fails and the user raises its Speed instead.
+        const DAMAGE_OR_HASTE: EffectDescription = Conditional {
+            condition: &BuiltIn(BuiltInEffect::DealDefaultDamage),
+            then: &BuiltIn(BuiltInEffect::CureStatus { target: EffectTarget::User }),
+            otherwise: Some(&BuiltIn(BuiltInEffect::RaiseStat { target: EffectTarget::User, stat: Stat::Speed, stages: 1 })),
+        };
+        let health_before_ember = sim.battle.monster(OPPONENT_1).current_health();
+        let ember_context = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_2 }, OPPONENT_1);
+        assert_eq!(DAMAGE_OR_HASTE.resolve(&mut sim, ALLY_1, ember_context), Outcome::Success);
+        assert_eq!(sim.battle.monster(OPPONENT_1).current_health(), health_before_ember);
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifier(Stat::Speed), 1);
+    }
+}
+
+#[cfg(test)]
+mod prng {
+    use crate::sim::prng::*;
+
+    #[test]
+    fn test_prng_percentage_chance() {
+        let mut prng = Prng::from_current_time();
+        let mut dist = [0u64; 100];
+        for _ in 0..=10_000_000 {
+            let n = prng.generate_random_u16_in_range(0..=99) as usize;
+            dist[n] += 1;
+        }
+        let avg_deviation = dist
+            .iter()
+            .map(|it| ((*it as f32 / 100_000.0) - 1.0).abs())
+            .reduce(|it, acc| it + acc)
+            .expect("We should always get some average value.")
+            / 100.0;
+        let avg_deviation = f32::floor(avg_deviation * 100_000.0) / 100_000.0;
+        println!("PRNG has {:?}% average deviation (threshold is at 0.005%)", avg_deviation);
+        assert!(avg_deviation < 5.0e-3);
+    }
+
+    #[test]
+    fn test_if_prng_is_deterministic_for_specific_seed() {
+        let mut prng1 = Prng::from_current_time();
+        let mut prng2 = prng1;
+        for i in 0..10_000 {
+            let generated_number_1 = prng1.generate_random_u16_in_range(0..=u16::MAX - 1);
+            let generated_number_2 = prng2.generate_random_u16_in_range(0..=u16::MAX - 1);
+            assert_eq!(generated_number_1, generated_number_2, "iteration {}", i);
+        }
+    }
+
+    #[test]
+    fn test_prng_chance() {
+        let mut prng = Prng::from_current_time();
+
+        let mut success = 0.0;
+        for _ in 0..=10_000_000 {
+            if prng.chance(33, 100) {
+                success += 1.0;
+            }
+        }
+        let avg_probability_deviation = ((success / 10_000_000.0) - 0.3333333333_f64).abs();
+        let avg_probability_deviation = f64::floor(avg_probability_deviation * 100_000.0) / 100_000.0;
+        println!("Average probability of PRNG is off by {}% (threshold is at 0.005%)", avg_probability_deviation);
+        assert!(avg_probability_deviation < 5.0e-3);
+    }
+
+    #[test]
+    fn test_range_generation_stays_in_bounds_at_extremes() {
+        struct MaxRng;
+        impl Rng for MaxRng {
+            fn next_u64(&mut self) -> u64 {
+                u64::MAX
+            }
+        }
+
+        assert_eq!(MaxRng.generate_random_u16_in_range(85..=100), 85 + (u64::MAX % 16) as u16);
+        assert_eq!(MaxRng.generate_random_u16_in_range(0..=u16::MAX), u16::MAX);
+        assert_eq!(Prng::new(7).generate_random_u16_in_range(0..=0), 0);
+        assert!(MaxRng.chance(24, 24));
+    }
+
+    #[test]
+    fn test_same_seed_and_choices_replay_battle_exactly() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+        use monsim_utils::MaxSizedVec;
+
+        let build_battle = |seed: u64| {
+            BattleState::spawn()
+                .add_ally_team(MonsterTeam::spawn().add_monster(Squirecoal.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn())))
+                .add_opponent_team(MonsterTeam::spawn().add_monster(Dandyleo.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn())))
+                .with_seed(seed)
+                .build()
+        };
+        let choices = || {
+            vec![
+                FullySpecifiedActionChoice::Move {
+                    move_id: MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 },
+                    target_positions: MaxSizedVec::from_vec(vec![FieldPosition::OpponentSideCentre]),
+                    activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+                },
+                FullySpecifiedActionChoice::Move {
+                    move_id: MoveID { owner_id: OPPONENT_1, move_number: MoveNumber::_1 },
+                    target_positions: MaxSizedVec::from_vec(vec![FieldPosition::AllySideCentre]),
+                    activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+                },
+            ]
+        };
+
+        let original_battle = build_battle(0xC0FFEE);
+        assert_eq!(original_battle.start_seed(), 0xC0FFEE);
+        let mut original = BattleSimulator::init(original_battle);
+        let mut replay = BattleSimulator::init(build_battle(original.battle.start_seed()));
+        for _ in 0..3 {
+            if original.battle.is_finished() { break; }
+            _ = original.simulate_turn(choices());
+            _ = replay.simulate_turn(choices());
+        }
+        assert_eq!(original.battle, replay.battle);
+    }
+}
+
+#[cfg(test)]
+mod utils {
+    use monsim_utils::{Ally, TeamAffl};
+
+    #[test]
+    #[should_panic]
+    fn test_expect_wrong_team() {
+        let item = Ally::new(10usize);
+        let item = TeamAffl::ally(item);
+        (item.map(|i| { i - 1 }).expect_opponent());
+    }
+
+    #[test]
+    fn test_expect_right_team() {
+        let item = Ally::new(10usize);
+        let item = TeamAffl::ally(item);
+        item.map(|i| {i + 1}).expect_ally();
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serialization {
+
+    #[test]
+    fn test_battle_round_trips_through_json() {
+        extern crate self as monsim;
+        use crate::sim::*;
+        use crate::sim::{
+            test_ability_dex::FlashFire,
+            test_field_effect_dex::Rain,
+            test_item_dex::SitrusBerry,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::{Ember, Scratch},
+            test_side_condition_dex::Spikes,
+            test_volatile_status_dex::Flinch,
+        };
+        use monsim_utils::MaxSizedVec;
+
+        struct TestRegistry;
+        impl SpeciesRegistry for TestRegistry {
+            fn monster_species(&self, dex_number: u16) -> Option<&'static MonsterSpecies> {
+                [&Dandyleo, &Squirecoal].into_iter().find(|species| species.dex_number() == dex_number)
+            }
+            fn move_species(&self, dex_number: u16) -> Option<&'static MoveSpecies> {
+                [&Scratch, &Ember].into_iter().find(|species| species.dex_number() == dex_number)
+            }
+            fn ability_species(&self, dex_number: u16) -> Option<&'static AbilitySpecies> {
+                Some(&FlashFire).filter(|species| species.dex_number() == dex_number)
+            }
+            fn item_species(&self, dex_number: u16) -> Option<&'static ItemSpecies> {
+                Some(&SitrusBerry).filter(|species| species.dex_number() == dex_number)
+            }
+            fn volatile_status_species(&self, dex_number: u16) -> Option<&'static VolatileStatusSpecies> {
+                Some(&Flinch).filter(|species| species.dex_number() == dex_number)
+            }
+            fn field_effect_species(&self, dex_number: u16) -> Option<&'static FieldEffectSpecies> {
+                Some(&Rain).filter(|species| species.dex_number() == dex_number)
+            }
+            fn side_condition_species(&self, dex_number: u16) -> Option<&'static SideConditionSpecies> {
+                Some(&Spikes).filter(|species| species.dex_number() == dex_number)
+            }
+        }
+
+        let test_battle = BattleState::spawn()
+            .add_ally_team(
+                MonsterTeam::spawn()
+                    .add_monster(Squirecoal.spawn((Scratch.spawn(), Some(Ember.spawn()), None, None), FlashFire.spawn()).with_nickname("Torchy").with_item(SitrusBerry.spawn()))
+            )
+            .add_opponent_team(
+                MonsterTeam::spawn()
+                    .add_monster(Dandyleo.spawn((Scratch.spawn(), None, None, None), FlashFire.spawn()))
+            )
+            .with_seed(42)
+            .build();
+        let mut sim = BattleSimulator::init(test_battle);
+        _ = SetWeather(&mut sim, ALLY_1, &Rain);
+        _ = AddSideCondition(&mut sim, ALLY_1, (TeamID::Opponents, &Spikes));
+        _ = AddVolatileStatus(&mut sim, ALLY_1, (OPPONENT_1, &Flinch));
+        _ = InflictStatus(&mut sim, ALLY_1, (OPPONENT_1, MajorStatus::Burn));
+        sim.log_species_message(&Spikes, "hurt", "{monster} is hurt by the spikes!", OPPONENT_1);
+        let ember = Decision::TurnAction(FullySpecifiedActionChoice::Move {
+            move_id: MoveID { owner_id: ALLY_1, move_number: MoveNumber::_2 },
+            target_positions: MaxSizedVec::from_vec(vec![FieldPosition::OpponentSideCentre]),
+            activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+        });
+        assert!(sim.submit(ember).is_ok());
+
+        let json = serde_json::to_string(&sim.battle).unwrap();
+        let reloaded_battle = BattleState::deserialize_with_registry(&mut serde_json::Deserializer::from_str(&json), &TestRegistry).unwrap();
+        assert_eq!(reloaded_battle, sim.battle);
+        assert_eq!(format!["{:?}", reloaded_battle], format!["{:?}", sim.battle]);
+        // The reloaded battle is still waiting on the opponent's action, with the ally's already submitted.
+        let reloaded_sim = BattleSimulator::init(reloaded_battle);
+        assert_eq!(reloaded_sim.pending_requests(), sim.pending_requests());
+        assert_eq!(reloaded_sim.battle.submitted_action_choices, sim.battle.submitted_action_choices);
+
+        // Logs saved before messages had styles get plain styled messages.
+        let mut unstyled_json = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        unstyled_json["message_log"].as_object_mut().unwrap().remove("styled_messages");
+        let reloaded_battle = BattleState::deserialize_with_registry(unstyled_json, &TestRegistry).unwrap();
+        let styled_messages = reloaded_battle.message_log.styled_messages();
+        assert_eq!(styled_messages.iter().map(StyledText::plain_text).collect::<Vec<_>>(), sim.battle.message_log.as_vec()[..]);
+
+        // Species missing from the registry are reported rather than silently dropped.
+        struct EmptyRegistry;
+        impl SpeciesRegistry for EmptyRegistry {
+            fn monster_species(&self, _: u16) -> Option<&'static MonsterSpecies> { None }
+            fn move_species(&self, _: u16) -> Option<&'static MoveSpecies> { None }
+            fn ability_species(&self, _: u16) -> Option<&'static AbilitySpecies> { None }
+            fn item_species(&self, _: u16) -> Option<&'static ItemSpecies> { None }
+            fn volatile_status_species(&self, _: u16) -> Option<&'static VolatileStatusSpecies> { None }
+            fn field_effect_species(&self, _: u16) -> Option<&'static FieldEffectSpecies> { None }
+            fn side_condition_species(&self, _: u16) -> Option<&'static SideConditionSpecies> { None }
+        }
+        assert!(BattleState::deserialize_with_registry(&mut serde_json::Deserializer::from_str(&json), &EmptyRegistry).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "loader"))]
+mod loader {
+
+    #[test]
+    fn test_loaded_species_battle_with_built_in_effects() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        use crate::sim::loader::*;
+        use crate::sim::test_ability_dex::FlashFire;
+
+        let mut dex = Dex::new();
+        load_species_from_toml(r#"
+            [[monsters]]
+            dex_number = 101
+            name = "Toxipup"
+            primary_type = "Poison"
+            base_stats = { hp = 60, att = 70, def = 60, spa = 70, spd = 60, spe = 80 }
+
+            [[moves]]
+            dex_number = 101
+            name = "Acid Spray"
+            type = "Poison"
+            category = "Special"
+            base_power = 40
+            max_power_points = 20
+            targets = ["ADJACENT", "OPPONENTS"]
+            effects = [
+                { effect = "DealDefaultDamage" },
+                { chance = 100, then = { effect = "LowerStat", target = "Target", stat = "SpecialDefense", stages = 2 } },
+                { if = { effect = "CureStatus", target = "User" }, then = { effect = "DealDefaultDamage" }, else = { effect = "RaiseStat", target = "User", stat = "Speed", stages = 1 } },
+            ]
+        "#, &mut dex).unwrap();
+        load_species_from_json(r#"{
+            "monsters": [{ "dex_number": 102, "name": "Boulderbun", "primary_type": "Rock", "secondary_type": "Normal",
+                           "base_stats": { "hp": 80, "att": 90, "def": 110, "spa": 30, "spd": 60, "spe": 20 } }]
+        }"#, &mut dex).unwrap();
+
+        let acid_spray = dex.moves().find_by_name("acid spray").unwrap();
+        assert_eq!(acid_spray.category(), MoveCategory::Special);
+        let test_battle = one_on_one_battle(
+            dex.monsters().find_by_name("Toxipup").unwrap().spawn((acid_spray.spawn(), None, None, None), FlashFire.spawn()),
+            dex.monsters().find_by_dex_number(102).unwrap().spawn((acid_spray.spawn(), None, None, None), FlashFire.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        let context = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, OPPONENT_1);
+        _ = sim.battle.move_(context.move_used_id).on_hit_effect().resolve(&mut sim, ALLY_1, context);
+        assert!(sim.battle.monster(OPPONENT_1).current_health() < sim.battle.monster(OPPONENT_1).max_health());
+        assert_eq!(sim.battle.monster(OPPONENT_1).stat_modifier(Stat::SpecialDefense), -2);
+        assert_eq!(sim.battle.monster(ALLY_1).stat_modifier(Stat::Speed), 1);
+
+        let unknown_flag = load_species_from_toml(r#"
+            [[moves]]
+            dex_number = 103
+            name = "Wild Swing"
+            type = "Normal"
+            category = "Physical"
+            max_power_points = 10
+            targets = ["EVERYONE"]
+        "#, &mut dex);
+        assert!(matches!(unknown_flag, Err(LoadError::UnknownTargetFlag(_))));
+        assert!(matches!(load_species_from_toml(r#"
+            [[monsters]]
+            dex_number = 101
+            name = "Copypup"
+            primary_type = "Poison"
+            base_stats = { hp = 1, att = 1, def = 1, spa = 1, spd = 1, spe = 1 }
+        "#, &mut dex), Err(LoadError::Dex(DexError::DuplicateDexNumber { .. }))));
+    }
+
+    #[test]
+    fn test_invalid_moves_are_rejected() {
+        use crate::sim::*;
+        use crate::sim::loader::*;
+
+        let mut dex = Dex::new();
+        // Status moves deal default damage unless they are given other effects.
+        assert!(matches!(load_species_from_toml(r#"
+            [[moves]]
+            dex_number = 101
+            name = "Stare"
+            type = "Normal"
+            category = "Status"
+            max_power_points = 10
+            targets = ["ADJACENT", "OPPONENTS"]
+        "#, &mut dex), Err(LoadError::StatusMoveDealsDamage(name)) if name == "Stare"));
+        assert!(matches!(load_species_from_json(r#"{
+            "moves": [{ "dex_number": 102, "name": "Sly Glare", "type": "Dark", "category": "Status", "max_power_points": 10,
+                        "targets": ["ADJACENT", "OPPONENTS"],
+                        "effects": { "chance": 50, "then": [{ "effect": "LowerStat", "target": "Target", "stat": "Speed", "stages": 1 }, { "effect": "DealDefaultDamage" }] } }]
+        }"#, &mut dex), Err(LoadError::StatusMoveDealsDamage(_))));
+        assert!(matches!(load_species_from_toml(r#"
+            [[moves]]
+            dex_number = 103
+            name = "Empty Swing"
+            type = "Normal"
+            category = "Physical"
+            base_power = 40
+            max_power_points = 0
+            targets = ["ADJACENT", "OPPONENTS"]
+        "#, &mut dex), Err(LoadError::NoPowerPoints(name)) if name == "Empty Swing"));
+        assert!(dex.moves().find_by_dex_number(101).is_none());
+        assert!(matches!(load_species_from_json(r#"{
+            "moves": [{ "dex_number": 105, "name": "Sure Shot", "type": "Normal", "category": "Physical", "base_power": 40,
+                        "base_accuracy": 10000, "max_power_points": 10, "targets": ["ADJACENT", "OPPONENTS"] }]
+        }"#, &mut dex), Err(LoadError::BaseAccuracyOutOfRange { base_accuracy: 10000, .. })));
+        assert!(matches!(load_species_from_json(r#"{
+            "moves": [{ "dex_number": 105, "name": "Mega Swing", "type": "Normal", "category": "Physical", "base_power": 5000,
+                        "max_power_points": 10, "targets": ["ADJACENT", "OPPONENTS"] }]
+        }"#, &mut dex), Err(LoadError::BasePowerOutOfRange { base_power: 5000, .. })));
+        assert!(matches!(load_species_from_json(r#"{
+            "moves": [{ "dex_number": 105, "name": "First Swing", "type": "Normal", "category": "Physical", "base_power": 40,
+                        "priority": 100, "max_power_points": 10, "targets": ["ADJACENT", "OPPONENTS"] }]
+        }"#, &mut dex), Err(LoadError::PriorityOutOfRange { priority: 100, .. })));
+
+        // Nothing in a file with an error is registered, even the species before the one with the error.
+        assert!(matches!(load_species_from_toml(r#"
+            [[monsters]]
+            dex_number = 101
+            name = "Toxipup"
+            primary_type = "Poison"
+            base_stats = { hp = 1, att = 1, def = 1, spa = 1, spd = 1, spe = 1 }
+
+            [[moves]]
+            dex_number = 106
+            name = "Swing"
+            type = "Normal"
+            category = "Physical"
+            base_power = 40
+            max_power_points = 10
+            targets = ["ADJACENT", "OPPONENTS"]
+
+            [[moves]]
+            dex_number = 107
+            name = "Empty Swing"
+            type = "Normal"
+            category = "Physical"
+            base_power = 40
+            max_power_points = 0
+            targets = ["ADJACENT", "OPPONENTS"]
+        "#, &mut dex), Err(LoadError::NoPowerPoints(_))));
+        assert!(dex.monsters().find_by_dex_number(101).is_none());
+        assert!(dex.moves().find_by_dex_number(106).is_none());
+
+        load_species_from_toml(r#"
+            [[moves]]
+            dex_number = 104
+            name = "Glare"
+            type = "Normal"
+            category = "Status"
+            max_power_points = 10
+            targets = ["ADJACENT", "OPPONENTS"]
+            effects = { effect = "InflictStatus", target = "Target", status = "Paralysis" }
+        "#, &mut dex).unwrap();
+    }
+
+    #[test]
+    fn test_message_catalog_loaded_from_file_renames_registered_species() {
+        use crate::sim::*;
+        use crate::sim::loader::*;
+        use crate::sim::test_monster_dex::Dandyleo;
+
+        let mut dex = Dex::new();
+        dex.register(&Dandyleo).unwrap();
+        let catalog = load_message_catalog_from_toml(r#"
+            [messages]
+            fainted = "{monster} ist besiegt!"
+
+            [monsters]
+            dandyleo = "Löwenzahn"
+        "#, &dex).unwrap();
+        assert_eq!(catalog.template("fainted"), Some("{monster} ist besiegt!"));
+        assert_eq!(catalog.template("sent_in"), Some("Go {monster}!"));
+        assert_eq!(catalog.species_name(&Dandyleo), "Löwenzahn");
+
+        assert!(matches!(load_message_catalog_from_json(r#"{ "messages": { "fainted_badly": "" } }"#, &dex), Err(LoadError::Localisation(_))));
+        assert!(matches!(load_message_catalog_from_json(r#"{ "monsters": { "Squirecoal": "Knappkohle" } }"#, &dex), Err(LoadError::UnknownSpecies { .. })));
+    }
+}
+
+#[cfg(all(test, feature = "scripting"))]
+mod scripting {
+
+    #[test]
+    fn test_script_event_handler_is_filtered_and_acts_on_battle() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        use crate::sim::scripting::*;
+        use crate::sim::{
+            event_dex::OnTryMoveHit,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+
+        #[allow(non_upper_case_globals)]
+        const FireproofHide: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Fireproof Hide",
+                event_handlers: | | {
+                    EventHandlerDeck::empty()
+                        .add_script(OnTryMoveHit, "fireproof_hide")
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        load_script(r#"
+            fn fireproof_hide(effector, context) {
+                if has_type(context.move_user, "Fire") && hp(effector) == max_hp(effector) {
+                    push_message(name(effector) + " singed " + name(context.move_user) + "!");
+                    deal_direct_damage(context.move_user, 10);
+                    return false;
+                }
+                true
+            }
+        "#).unwrap();
+
+        let test_battle = one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), FireproofHide.spawn()),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), FireproofHide.spawn()),
+        );
+        let mut sim = BattleSimulator::init(test_battle);
+
+        // Only the opponent's handler responds to the ally's broadcast, so the ally's own Fire type doesn't matter.
+        let context = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, OPPONENT_1);
+        assert_eq!(EventDispatcher::dispatch_trial_event(&mut sim, OnTryMoveHit, ALLY_1, context), Outcome::Failure);
+        assert_eq!(sim.battle.monster(ALLY_1).current_health(), sim.battle.monster(ALLY_1).max_health() - 10);
+        assert!(sim.battle.message_log.as_vec().iter().any(|message| message.ends_with("singed Squirecoal!")));
+
+        let context = MoveHitContext::new(MoveID { owner_id: OPPONENT_1, move_number: MoveNumber::_1 }, ALLY_1);
+        assert_eq!(EventDispatcher::dispatch_trial_event(&mut sim, OnTryMoveHit, OPPONENT_1, context), Outcome::Success);
+
+        assert!(matches!(load_script("fn unfinished("), Err(ScriptError::Parse(_))));
+        clear_scripts();
+    }
+
+    #[test]
+    fn test_failing_script_handler_fails_the_turn() {
+        use crate::sim::*;
+        use crate::test::one_on_one_battle;
+        use crate::sim::scripting::*;
+        use crate::sim::{
+            event_dex::OnTryMoveHit,
+            test_monster_dex::{Dandyleo, Squirecoal},
+            test_move_dex::Scratch,
+        };
+        use monsim_utils::MaxSizedVec;
+
+        #[allow(non_upper_case_globals)]
+        const Nitpick: AbilitySpecies = AbilitySpecies::from_dex_data(
+            AbilityDexEntry {
+                dex_number: 101,
+                name: "Nitpick",
+                event_handlers: | | {
+                    EventHandlerDeck::empty()
+                        .add_script(OnTryMoveHit, "nitpick")
+                },
+                on_activate_effect: Effect::from(|_sim, _effector_id, _context| {}),
+                event_filtering_options: EventFilteringOptions::default(),
+                order: 0,
+            }
+        );
+
+        let test_battle = || one_on_one_battle(
+            Squirecoal.spawn((Scratch.spawn(), None, None, None), Nitpick.spawn()),
+            Dandyleo.spawn((Scratch.spawn(), None, None, None), Nitpick.spawn()),
+        );
+        let context = MoveHitContext::new(MoveID { owner_id: ALLY_1, move_number: MoveNumber::_1 }, OPPONENT_1);
+
+        // Hp has no stage, and Accuracy and Evasion only have a stage.
+        for (script, failing_call) in [
+            (r#"fn nitpick(effector, context) { stat_stage(effector, "Hp") > 0 }"#, "Hp has no stat stage."),
+            (r#"fn nitpick(effector, context) { stat(effector, "Evasion") > 0 }"#, "Evasion has no value"),
+            (r#"fn nitpick(effector, context) { 1 }"#, "wrong type for OnTryMoveHit"),
+            // Scripts that would never end are stopped.
+            (r#"fn nitpick(effector, context) { loop {} }"#, "Too many operations"),
+            (r#"fn nitpick(effector, context) { nitpick(effector, context) }"#, "Stack overflow"),
+        ] {
+            load_script(script).unwrap();
+            let mut sim = BattleSimulator::init(test_battle());
+            // The failed handler falls back to the event's default.
+            assert_eq!(EventDispatcher::dispatch_trial_event(&mut sim, OnTryMoveHit, ALLY_1, context), Outcome::Success);
+            let error = sim.event_stack.take_error();
+            assert!(
+                matches!(&error, Some(SimError::ScriptFailed { script_function: "nitpick", message }) if message.contains(failing_call)),
+                "{:?}", error
+            );
+
+            let mut sim = BattleSimulator::init(test_battle());
+            let move_choice = |owner_id: MonsterID, target_position: FieldPosition| FullySpecifiedActionChoice::Move {
+                move_id: MoveID { owner_id, move_number: MoveNumber::_1 },
+                target_positions: MaxSizedVec::from_vec(vec![target_position]),
+                activation_order: ActivationOrder { priority: 0, speed: 0, order: 0 },
+            };
+            let result = sim.simulate_turn(vec![move_choice(ALLY_1, FieldPosition::OpponentSideCentre), move_choice(OPPONENT_1, FieldPosition::AllySideCentre)]);
+            assert!(matches!(result, Err(SimError::ScriptFailed { .. })));
+        }
+        clear_scripts();
+    }
+}
@@ -1,9 +1,9 @@
 use std::io::Stdout;
 
 use monsim_utils::{Ally, MaxSizedVec, Opponent};
-use tui::{backend::CrosstermBackend, layout::{Alignment, Constraint, Direction, Layout, Rect}, style::{Color, Modifier, Style}, terminal::CompletedFrame, text::{Span, Spans}, widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap}, Frame, Terminal};
+use tui::{backend::CrosstermBackend, layout::{Alignment, Constraint, Direction, Layout, Rect}, style::{Color, Modifier, Style}, terminal::CompletedFrame, text::Span, widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap}, Frame, Terminal};
 
-use crate::sim::{AvailableChoicesForTeam, BattleState, MonsterID, PartiallySpecifiedChoice, PerTeam, TeamID};
+use crate::sim::{AvailableChoicesForTeam, BattleState, MonsterID, PartiallySpecifiedChoice, PerTeam, StyledText, TeamID};
 
 use super::{AppState, InputMode};
 
@@ -190,7 +190,7 @@ impl<'a> Ui<'a> {
             frame.render_stateful_widget(ally_team_choice_menu_widget, ally_team_panel_chunks[1], &mut self.choice_selection_menus[TeamID::Allies].list_state.clone());
             frame.render_widget(ally_team_status_widget, ally_team_panel_chunks[2]);
 
-            let message_log_widget = self.message_log_panel.as_renderable_widget(&battle.message_log.as_vec(), self.currently_selected_panel == SelectablePanelID::MessageLog);
+            let message_log_widget = self.message_log_panel.as_renderable_widget(battle.message_log.styled_messages(), self.currently_selected_panel == SelectablePanelID::MessageLog);
             // If we are on the SwitcheePrompt, then show the SwitcheePrompt widget
             if let AppState::AcceptingInput(InputMode::SwitcheePrompt {
                 switchable_benched_monster_ids,
@@ -420,25 +420,11 @@ impl<'a> ChoiceSelectionMenu<'a> {
 }
 
 impl MessageLogPanel {
-    fn as_renderable_widget<'a>(&self, message_log: &'a [String], is_selected: bool) -> Paragraph<'a> {
+    fn as_renderable_widget<'a>(&self, message_log: &'a [StyledText], is_selected: bool) -> Paragraph<'a> {
         let text = message_log
             .iter()
-            .enumerate()
-            .filter_map(|(index, element)| {
-                if index >= self.scroll_cursor {
-                    // FIXME: Possible bug that will highlight any message with "Turn" in Cyan. Most problematic for messages involving moves like "U-Turn" or "Flip Turn". 
-                    if element.contains("Turn") {
-                        Some(Spans::from(Span::styled(element, Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD)
-                        )))
-                    } else {
-                        Some(Spans::from(Span::raw(element)))
-                    }
-                } else {
-                    None
-                }
-            })
+            .skip(self.scroll_cursor)
+            .map(StyledText::to_tui_spans)
             .collect::<Vec<_>>();
         Paragraph::new(text)
             .block(